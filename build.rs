@@ -5,6 +5,9 @@ use std::process::Command;
 use std::collections::HashMap;
 use std::env;
 use std::fmt;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 
 use regex::Regex;
 use version_check::{supports_features, is_min_version, is_min_date};
@@ -39,6 +42,33 @@ impl fmt::Display for PythonVersion {
     }
 }
 
+/// Implementation of the python interpreter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PythonImplementation {
+    CPython,
+    /// PyPy with its `cpyext` emulation of the C API
+    PyPy,
+}
+
+impl PythonImplementation {
+    fn from_name(name: &str) -> Result<PythonImplementation, String> {
+        match name {
+            "CPython" => Ok(PythonImplementation::CPython),
+            "PyPy" => Ok(PythonImplementation::PyPy),
+            other => Err(format!("Unsupported python implementation {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for PythonImplementation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            PythonImplementation::CPython => f.write_str("CPython"),
+            PythonImplementation::PyPy => f.write_str("PyPy"),
+        }
+    }
+}
+
 const PY3_MIN_MINOR: u8 = 5;
 
 const CFG_KEY: &'static str = "py_sys_config";
@@ -63,7 +93,6 @@ static NEWLINE_SEQUENCE: &'static str = "\n";
 //
 // (hrm, this is sort of re-implementing what distutils does, except 
 // by passing command line args instead of referring to a python.h)
-#[cfg(not(target_os="windows"))]
 static SYSCONFIG_FLAGS: [&'static str; 7] = [
     "Py_USING_UNICODE",
     "Py_UNICODE_WIDE",
@@ -139,6 +168,10 @@ config = sysconfig.get_config_vars();".to_owned();
 
 #[cfg(target_os="windows")]
 fn get_config_vars(_: &String) -> Result<HashMap<String, String>, String> {
+    Ok(windows_config_vars())
+}
+
+fn windows_config_vars() -> HashMap<String, String> {
     // sysconfig is missing all the flags on windows, so we can't actually
     // query the interpreter directly for its build flags. 
     //
@@ -163,7 +196,7 @@ fn get_config_vars(_: &String) -> Result<HashMap<String, String>, String> {
     // map.insert("Py_REF_DEBUG", "1");
     // map.insert("Py_TRACE_REFS", "1");
     // map.insert("COUNT_ALLOCS", 1");
-    Ok(map)
+    map
 }
 
 fn is_value(key: &str) -> bool {
@@ -268,11 +301,12 @@ fn get_rustc_link_lib(version: &PythonVersion, _: &str, _: bool) -> Result<Strin
 /// If the environment variable `PYTHON_SYS_EXECUTABLE`, use the provided
 /// path a Python executable, and raises an error if the version doesn't match.
 /// Else tries to execute the interpreter as "python", "python{major version}",
-/// "python{major version}.{minor version}" in order until one
-/// is of the version we are expecting.
+/// "python{major version}.{minor version}" and, for python 3, "pypy3" in order
+/// until one is of the version we are expecting.
 fn find_interpreter_and_get_config(expected_version: &PythonVersion)
                                    -> Result<(PythonVersion, String, Vec<String>), String>
 {
+    println!("cargo:rerun-if-env-changed=PYTHON_SYS_EXECUTABLE");
     if let Some(sys_executable) = env::var_os("PYTHON_SYS_EXECUTABLE") {
         let interpreter_path = sys_executable.to_str()
             .expect("Unable to get PYTHON_SYS_EXECUTABLE value");
@@ -286,38 +320,45 @@ fn find_interpreter_and_get_config(expected_version: &PythonVersion)
                                interpreter_path, expected_version, interpreter_version));
         }
     }
-    // check default python
-    let interpreter_path = "python";
-    let (interpreter_version, lines) = try!(get_config_from_interpreter(interpreter_path));
-    if expected_version == &interpreter_version {
-        return Ok((interpreter_version, interpreter_path.to_owned(), lines));
-    }
 
-    let major_interpreter_path = &format!("python{}", expected_version.major);
-    let (interpreter_version, lines) = try!(get_config_from_interpreter(major_interpreter_path));
-    if expected_version == &interpreter_version {
-        return Ok((interpreter_version, major_interpreter_path.to_owned(), lines));
+    let mut candidates = vec!["python".to_owned(), format!("python{}", expected_version.major)];
+    if let Some(minor) = expected_version.minor {
+        candidates.push(format!("python{}.{}", expected_version.major, minor));
+    }
+    if expected_version.major == 3 {
+        candidates.push("pypy3".to_owned());
     }
 
-    if let Some(minor) = expected_version.minor {
-        let minor_interpreter_path = &format!("python{}.{}", expected_version.major, minor);
-        let (interpreter_version, lines) = try!(get_config_from_interpreter(
-            minor_interpreter_path));
-        if expected_version == &interpreter_version {
-            return Ok((interpreter_version, minor_interpreter_path.to_owned(), lines));
+    // interpreters which are not installed are skipped
+    let mut errors = Vec::new();
+    for interpreter_path in candidates {
+        match get_config_from_interpreter(&interpreter_path) {
+            Ok((interpreter_version, lines)) => {
+                if expected_version == &interpreter_version {
+                    return Ok((interpreter_version, interpreter_path, lines));
+                }
+                errors.push(format!("{}: found version {}", interpreter_path,
+                                    interpreter_version));
+            },
+            Err(err) => errors.push(err),
         }
     }
 
-    Err(format!("No python interpreter found"))
+    Err(format!("No python interpreter of version {} found, \
+                 set PYTHON_SYS_EXECUTABLE to the interpreter to use:\n\t{}",
+                expected_version, errors.join("\n\t")))
 }
 
 /// Extract compilation vars from the specified interpreter.
 fn get_config_from_interpreter(interpreter: &str) -> Result<(PythonVersion, Vec<String>), String> {
-    let script = "import sys; import sysconfig; print(sys.version_info[0:2]); \
+    let script = "import sys; import sysconfig; import platform; print(sys.version_info[0:2]); \
 print(sysconfig.get_config_var('LIBDIR')); \
 print(sysconfig.get_config_var('Py_ENABLE_SHARED')); \
 print(sysconfig.get_config_var('LDVERSION') or sysconfig.get_config_var('py_version_short')); \
-print(sys.exec_prefix);";
+print(sys.exec_prefix); \
+print(sysconfig.get_config_var('INCLUDEPY')); \
+print(getattr(sys, 'abiflags', '')); \
+print(platform.python_implementation());";
     let out = try!(run_python_script(interpreter, script));
     let lines: Vec<String> = out.split(NEWLINE_SEQUENCE).map(|line| line.to_owned()).collect();
     let interpreter_version = try!(get_interpreter_version(&lines[0]));
@@ -328,7 +369,7 @@ print(sys.exec_prefix);";
 /// cargo vars to stdout.
 ///
 /// Note that if the python doesn't satisfy expected_version, this will error.
-fn configure_from_path(expected_version: &PythonVersion) -> Result<(String, String), String> {
+fn configure_from_path(expected_version: &PythonVersion) -> Result<(BuildConfig, String), String> {
 
     let (interpreter_version, interpreter_path, lines) = try!(
         find_interpreter_and_get_config(expected_version));
@@ -337,28 +378,103 @@ fn configure_from_path(expected_version: &PythonVersion) -> Result<(String, Stri
     let enable_shared: &str = &lines[2];
     let ld_version: &str = &lines[3];
     let exec_prefix: &str = &lines[4];
+    let include_dir: &str = &lines[5];
+    let abi_flags: &str = &lines[6];
+    let implementation = try!(PythonImplementation::from_name(&lines[7]));
+
+    let lib_dir = if implementation == PythonImplementation::PyPy {
+        // libpypy3-c lives next to the pypy3 executable
+        Some(format!("{}/bin", exec_prefix))
+    } else if libpath != "None" {
+        Some(libpath.to_owned())
+    } else if cfg!(target_os="windows") {
+        Some(format!("{}\\libs", exec_prefix))
+    } else {
+        None
+    };
+    let flags = {
+        let link_lib = || match implementation {
+            PythonImplementation::PyPy =>
+                Ok(format!("cargo:rustc-link-lib=pypy{}-c", interpreter_version.major)),
+            PythonImplementation::CPython =>
+                get_rustc_link_lib(&interpreter_version, ld_version, enable_shared == "1"),
+        };
+        try!(emit_python_config(
+            &interpreter_version, implementation, &link_lib, lib_dir.clone(),
+            cfg!(target_os="windows")))
+    };
+
+    let config = BuildConfig {
+        interpreter: Some(interpreter_path),
+        implementation: implementation,
+        version: interpreter_version,
+        include_dir: if include_dir != "None" { Some(include_dir.to_owned()) } else { None },
+        lib_dir: lib_dir,
+        ld_version: ld_version.to_owned(),
+        shared: enable_shared == "1" || cfg!(target_os="windows"),
+        abi_flags: abi_flags.to_owned(),
+    };
+    return Ok((config, flags));
+}
+
+/// Print the cargo link and cfg lines for a python of version `interpreter_version`
+/// with the library in `lib_dir`, `link_lib` returns the link line of the library.
+///
+/// Returns the version flags that are exported to dependents.
+fn emit_python_config(interpreter_version: &PythonVersion,
+                      implementation: PythonImplementation,
+                      link_lib: &Fn() -> Result<String, String>,
+                      lib_dir: Option<String>,
+                      is_windows: bool) -> Result<String, String> {
+    let abi3_minor = abi3_min_minor();
+
+    if implementation == PythonImplementation::PyPy {
+        if interpreter_version.major != 3 {
+            return Err("PyPy is only supported with Python 3".to_owned())
+        }
+        if abi3_minor.is_some() {
+            return Err("The abi3 feature is not supported on PyPy".to_owned())
+        }
+    }
 
     let is_extension_module = env::var_os("CARGO_FEATURE_EXTENSION_MODULE").is_some();
-    if !is_extension_module || cfg!(target_os="windows") {
-        println!("{}", get_rustc_link_lib(&interpreter_version,
-            ld_version, enable_shared == "1").unwrap());
-        if libpath != "None" {
-            println!("cargo:rustc-link-search=native={}", libpath);
-        } else if cfg!(target_os="windows") {
-            println!("cargo:rustc-link-search=native={}\\libs", exec_prefix);
+    if abi3_minor.is_some() && is_windows {
+        // python3.dll exports the stable ABI of every python 3 version
+        println!("cargo:rustc-link-lib=pythonXY:python3");
+        if let Some(ref lib_dir) = lib_dir {
+            println!("cargo:rustc-link-search=native={}", lib_dir);
+        }
+    } else if !is_extension_module || is_windows {
+        println!("{}", try!(link_lib()));
+        if let Some(ref lib_dir) = lib_dir {
+            println!("cargo:rustc-link-search=native={}", lib_dir);
         }
     }
 
     let mut flags = String::new();
 
-    if let PythonVersion { major: 3, minor: some_minor} = interpreter_version {
-        if env::var_os("CARGO_FEATURE_PEP_384").is_some() {
+    if implementation == PythonImplementation::PyPy {
+        println!("cargo:rustc-cfg=PyPy");
+        flags += "CFG_PyPy,";
+    }
+
+    if let PythonVersion { major: 3, minor: some_minor} = *interpreter_version {
+        if abi3_minor.is_some() {
             println!("cargo:rustc-cfg=Py_LIMITED_API");
         }
         if let Some(minor) = some_minor {
             if minor < PY3_MIN_MINOR {
                 return Err(format!("Python 3 required version is 3.{}, current version is 3.{}", PY3_MIN_MINOR, minor))
             }
+            // with the stable ABI only the api of the minimum version may be used,
+            // behavior which changed in later versions is checked at runtime
+            let minor = match abi3_minor {
+                Some(abi3_minor) if abi3_minor > minor =>
+                    return Err(format!("The abi3 minimum version is 3.{}, current version is 3.{}",
+                                       abi3_minor, minor)),
+                Some(abi3_minor) => abi3_minor,
+                None => minor,
+            };
             for i in 5..(minor+1) {
                 println!("cargo:rustc-cfg=Py_3_{}", i);
                 flags += format!("CFG_Py_3_{},", i).as_ref();
@@ -366,10 +482,333 @@ fn configure_from_path(expected_version: &PythonVersion) -> Result<(String, Stri
             println!("cargo:rustc-cfg=Py_3");
         }
     } else {
+        if abi3_minor.is_some() {
+            return Err("The abi3 feature requires Python 3".to_owned())
+        }
         println!("cargo:rustc-cfg=Py_2");
         flags += format!("CFG_Py_2,").as_ref();
     }
-    return Ok((interpreter_path, flags));
+    Ok(flags)
+}
+
+/// Configuration of the python we build against.
+///
+/// It is exported to the build scripts of dependent crates
+/// as `DEP_PYTHON_*` variables and as a json file, see `export_build_config`.
+struct BuildConfig {
+    /// Path of the interpreter, `None` when cross compiling
+    interpreter: Option<String>,
+    implementation: PythonImplementation,
+    version: PythonVersion,
+    /// Directory of `Python.h`
+    include_dir: Option<String>,
+    /// Directory of the python library
+    lib_dir: Option<String>,
+    /// Version part of the library name, i.e. `3.6m` for `libpython3.6m.so`
+    ld_version: String,
+    /// Whether python is a shared library
+    shared: bool,
+    /// ABI flags of the interpreter, i.e. `m`
+    abi_flags: String,
+}
+
+/// Escapes `s` as a json string.
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option(s: &Option<String>) -> String {
+    match *s {
+        Some(ref s) => json_string(s),
+        None => "null".to_owned(),
+    }
+}
+
+/// Export `config` and the cfg `flags` to the build scripts of dependent crates.
+///
+/// With the `links = "python"` key of the manifest every `cargo:KEY=VALUE` line
+/// is visible to them as the `DEP_PYTHON_KEY` environment variable.
+/// The complete configuration is written to `pyo3-build-config.json` in `OUT_DIR`,
+/// its path is `DEP_PYTHON_CONFIG_FILE`.
+fn export_build_config(config: &BuildConfig, flags: &[&str]) -> Result<(), String> {
+    let version = format!("{}.{}", config.version.major, config.version.minor.unwrap_or(0));
+
+    println!("cargo:version={}", version);
+    println!("cargo:implementation={}", config.implementation);
+    if let Some(ref interpreter) = config.interpreter {
+        println!("cargo:interpreter={}", interpreter);
+    }
+    if let Some(ref include_dir) = config.include_dir {
+        println!("cargo:include_dir={}", include_dir);
+    }
+    if let Some(ref lib_dir) = config.lib_dir {
+        println!("cargo:lib_dir={}", lib_dir);
+    }
+    println!("cargo:ld_version={}", config.ld_version);
+    println!("cargo:shared={}", config.shared);
+    println!("cargo:abi_flags={}", config.abi_flags);
+
+    let flags: Vec<String> = flags.iter().map(|flag| json_string(flag)).collect();
+    let json = format!(
+        "{{\n  \"interpreter\": {},\n  \"implementation\": {},\n  \"version\": {},\n  \
+         \"include_dir\": {},\n  \"lib_dir\": {},\n  \"ld_version\": {},\n  \
+         \"shared\": {},\n  \"abi_flags\": {},\n  \"limited_api\": {},\n  \"flags\": [{}]\n}}\n",
+        json_option(&config.interpreter), json_string(&config.implementation.to_string()),
+        json_string(&version),
+        json_option(&config.include_dir), json_option(&config.lib_dir),
+        json_string(&config.ld_version), config.shared, json_string(&config.abi_flags),
+        abi3_min_minor().is_some(), flags.join(", "));
+
+    let out_dir = try!(env::var("OUT_DIR").map_err(|_| "OUT_DIR is not set".to_owned()));
+    let path = Path::new(&out_dir).join("pyo3-build-config.json");
+    try!(File::create(&path)
+         .and_then(|mut f| f.write_all(json.as_bytes()))
+         .map_err(|e| format!("failed to write {}: {}", path.display(), e)));
+    println!("cargo:config_file={}", path.display());
+    Ok(())
+}
+
+/// Location of the target python when cross compiling,
+/// set with the `PYO3_CROSS_*` environment variables.
+struct CrossCompileConfig {
+    /// `PYO3_CROSS_LIB_DIR`, the directory of the python library of the target
+    lib_dir: PathBuf,
+    /// `PYO3_CROSS_INCLUDE_DIR`, the directory of `pyconfig.h`
+    include_dir: Option<PathBuf>,
+    /// `PYO3_CROSS_PYTHON_VERSION`, i.e. `3.7`
+    version: Option<PythonVersion>,
+}
+
+/// Returns the cross compile configuration if `PYO3_CROSS_LIB_DIR` is set.
+fn cross_compile_config() -> Result<Option<CrossCompileConfig>, String> {
+    for var in &["PYO3_CROSS_LIB_DIR", "PYO3_CROSS_INCLUDE_DIR", "PYO3_CROSS_PYTHON_VERSION"] {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    let lib_dir = match env::var_os("PYO3_CROSS_LIB_DIR") {
+        Some(lib_dir) => PathBuf::from(lib_dir),
+        None => return Ok(None),
+    };
+    if !lib_dir.is_dir() {
+        return Err(format!("PYO3_CROSS_LIB_DIR={} is not a directory", lib_dir.display()))
+    }
+    let version = match env::var("PYO3_CROSS_PYTHON_VERSION") {
+        Ok(version) => {
+            let re = Regex::new(r"^(\d+)\.(\d+)$").unwrap();
+            match re.captures(version.trim()) {
+                Some(cap) => Some(PythonVersion {
+                    major: cap.get(1).unwrap().as_str().parse().unwrap(),
+                    minor: Some(cap.get(2).unwrap().as_str().parse().unwrap()),
+                }),
+                None => return Err(format!(
+                    "PYO3_CROSS_PYTHON_VERSION={} is not of the form major.minor", version)),
+            }
+        },
+        Err(_) => None,
+    };
+    Ok(Some(CrossCompileConfig {
+        lib_dir: lib_dir,
+        include_dir: env::var_os("PYO3_CROSS_INCLUDE_DIR").map(PathBuf::from),
+        version: version,
+    }))
+}
+
+/// Searches `dir` and its subdirectories for the `_sysconfigdata*.py` file of the target,
+/// i.e. `lib/python3.7/_sysconfigdata_m_linux_aarch64-linux-gnu.py`.
+///
+/// Python 2 names the file `_sysconfigdata.py`.
+fn find_sysconfigdata(dir: &Path, version: Option<&PythonVersion>, depth: u8) -> Option<PathBuf> {
+    let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+        Err(_) => return None,
+    };
+    entries.sort();
+
+    for path in entries.iter() {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        if name.starts_with("_sysconfigdata") && name.ends_with(".py") && path.is_file() {
+            return Some(path.clone())
+        }
+    }
+    if depth == 0 {
+        return None
+    }
+    for path in entries.iter() {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        // skip the standard library directories of other python versions
+        if let Some(&PythonVersion { major, minor: Some(minor) }) = version {
+            if name.starts_with("python") && name != format!("python{}.{}", major, minor) {
+                continue
+            }
+        }
+        if path.is_dir() {
+            if let Some(found) = find_sysconfigdata(path, version, depth - 1) {
+                return Some(found)
+            }
+        }
+    }
+    None
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    let mut content = String::new();
+    try!(File::open(path)
+         .and_then(|mut f| f.read_to_string(&mut content))
+         .map_err(|e| format!("failed to read {}: {}", path.display(), e)));
+    Ok(content)
+}
+
+/// Parses the `build_time_vars` dict of a `_sysconfigdata*.py` file.
+///
+/// Only string and integer values are extracted, which covers all the variables we use.
+fn parse_sysconfigdata(path: &Path) -> Result<HashMap<String, String>, String> {
+    let content = try!(read_file(path));
+    let re = Regex::new(r"'(\w+)':\s*(?:'([^'\\]*)'|(-?\d+))\s*[,}]").unwrap();
+    Ok(re.captures_iter(&content)
+       .map(|cap| {
+           let value = cap.get(2).or(cap.get(3)).unwrap().as_str();
+           (cap.get(1).unwrap().as_str().to_owned(), value.to_owned())
+       })
+       .collect())
+}
+
+/// Parses the `#define`s of a `pyconfig.h` file, defines without value are set to `1`.
+fn parse_pyconfig_h(path: &Path) -> Result<HashMap<String, String>, String> {
+    let content = try!(read_file(path));
+    let re = Regex::new(r"(?m)^\s*#\s*define\s+(\w+)[ \t]*([^\r\n]*?)\s*$").unwrap();
+    Ok(re.captures_iter(&content)
+       .map(|cap| {
+           let value = match cap.get(2).unwrap().as_str() {
+               "" => "1",
+               value => value,
+           };
+           (cap.get(1).unwrap().as_str().to_owned(), value.to_owned())
+       })
+       .collect())
+}
+
+/// Configure the build for the target python described by `cross`
+/// without running an interpreter, and print cargo vars to stdout.
+///
+/// The version, the library name and the build flags are read from the `_sysconfigdata*.py`
+/// file in the library directory, missing flags are read from `pyconfig.h`.
+/// Windows targets have no `_sysconfigdata*.py`, `PYO3_CROSS_PYTHON_VERSION` is required.
+fn configure_cross(expected_version: &PythonVersion, cross: &CrossCompileConfig)
+                   -> Result<(BuildConfig, String, HashMap<String, String>), String>
+{
+    let is_windows = env::var("CARGO_CFG_TARGET_OS").map(|os| os == "windows").unwrap_or(false);
+
+    let sysconfigdata = if is_windows {
+        HashMap::new()
+    } else {
+        match find_sysconfigdata(&cross.lib_dir, cross.version.as_ref(), 2) {
+            Some(path) => try!(parse_sysconfigdata(&path)),
+            None => return Err(format!(
+                "no _sysconfigdata*.py found in PYO3_CROSS_LIB_DIR={}", cross.lib_dir.display())),
+        }
+    };
+
+    let interpreter_version = match cross.version {
+        Some(PythonVersion { major, minor }) => PythonVersion { major: major, minor: minor },
+        None => match sysconfigdata.get("VERSION") {
+            Some(version) => try!(get_interpreter_version(
+                &format!("({})", version.replace(".", ", ")))),
+            None => return Err(
+                "PYO3_CROSS_PYTHON_VERSION must be set when cross compiling".to_owned()),
+        }
+    };
+    if expected_version != &interpreter_version {
+        return Err(format!("Unsupported python version of the cross compile target\n\
+                            \tmin version {} != found {}", expected_version, interpreter_version));
+    }
+
+    let minor = interpreter_version.minor.unwrap();
+    let ld_version = match sysconfigdata.get("LDVERSION") {
+        Some(ld_version) => ld_version.clone(),
+        None => format!("{}.{}", interpreter_version.major, minor),
+    };
+    let enable_shared = sysconfigdata.get("Py_ENABLE_SHARED").map(|v| v == "1").unwrap_or(false);
+    let major = interpreter_version.major;
+    let link_lib = || if is_windows {
+        Ok(format!("cargo:rustc-link-lib=pythonXY:python{}{}", major, minor))
+    } else if enable_shared {
+        Ok(format!("cargo:rustc-link-lib=python{}", ld_version))
+    } else {
+        Ok(format!("cargo:rustc-link-lib=static=python{}", ld_version))
+    };
+    let lib_dir = cross.lib_dir.to_str().map(|dir| dir.to_owned());
+    let flags = try!(emit_python_config(&interpreter_version, PythonImplementation::CPython,
+                                        &link_lib, lib_dir.clone(), is_windows));
+
+    let include_dir = match cross.include_dir {
+        Some(ref include_dir) => include_dir.clone(),
+        None => cross.lib_dir.join("..").join("include").join(format!("python{}", ld_version)),
+    };
+    let pyconfig_h = include_dir.join("pyconfig.h");
+    let pyconfig = if pyconfig_h.is_file() {
+        try!(parse_pyconfig_h(&pyconfig_h))
+    } else {
+        HashMap::new()
+    };
+
+    let mut config_map = if is_windows { windows_config_vars() } else { HashMap::new() };
+    for key in SYSCONFIG_FLAGS.iter().chain(SYSCONFIG_VALUES.iter()) {
+        let value = sysconfigdata.get(*key).or(pyconfig.get(*key));
+        match value {
+            Some(value) => {
+                config_map.insert(key.to_string(), value.clone());
+            },
+            None => if !is_value(key) && !config_map.contains_key(*key) {
+                config_map.insert(key.to_string(), "0".to_owned());
+            }
+        }
+    }
+    if config_map.get("Py_DEBUG").map(|val| val == "1").unwrap_or(false) {
+        config_map.insert("Py_REF_DEBUG".to_owned(), "1".to_owned());
+        config_map.insert("Py_TRACE_REFS".to_owned(), "1".to_owned());
+        config_map.insert("COUNT_ALLOCS".to_owned(), "1".to_owned());
+    }
+
+    let config = BuildConfig {
+        interpreter: None,
+        implementation: PythonImplementation::CPython,
+        version: interpreter_version,
+        include_dir: include_dir.to_str().map(|dir| dir.to_owned()),
+        lib_dir: lib_dir,
+        ld_version: ld_version.clone(),
+        shared: enable_shared || is_windows,
+        abi_flags: sysconfigdata.get("ABIFLAGS").cloned().unwrap_or_default(),
+    };
+    Ok((config, flags, config_map))
+}
+
+/// Determine the minimum python 3 minor version of a stable ABI build
+/// from the `abi3` features, `None` if the full API is used.
+///
+/// The `pep-384` feature is accepted as an alias of `abi3`.
+fn abi3_min_minor() -> Option<u8> {
+    if env::var_os("CARGO_FEATURE_ABI3").is_none() &&
+        env::var_os("CARGO_FEATURE_PEP_384").is_none() {
+        return None
+    }
+    let re = Regex::new(r"CARGO_FEATURE_ABI3_PY3(\d+)").unwrap();
+    let minor = env::vars()
+        .filter_map(|(key, _)| re.captures(&key)
+                    .map(|cap| cap.get(1).unwrap().as_str().parse::<u8>().unwrap()))
+        .max();
+    Some(minor.unwrap_or(PY3_MIN_MINOR))
 }
 
 /// Determine the python version we're supposed to be building
@@ -452,12 +891,23 @@ fn main() {
         Ok(v) => v,
         Err(_) => PythonVersion{major: 3, minor: None}
     };
-    let (python_interpreter_path, flags) = configure_from_path(&version).unwrap();
-    let mut config_map = get_config_vars(&python_interpreter_path).unwrap();
+    //
+    // When cross compiling the configuration is read from the files of the target
+    // python instead, see `configure_cross`.
+    let (build_config, flags, mut config_map) = match cross_compile_config().unwrap() {
+        Some(cross) => configure_cross(&version, &cross).unwrap(),
+        None => {
+            let (build_config, flags) = configure_from_path(&version).unwrap();
+            let config_map = get_config_vars(
+                build_config.interpreter.as_ref().unwrap()).unwrap();
+            (build_config, flags, config_map)
+        }
+    };
 
-    // WITH_THREAD is always on for 3.7
-    let (interpreter_version, _, _) = find_interpreter_and_get_config(&version).unwrap();
-    if interpreter_version.major == 3 && interpreter_version.minor.unwrap_or(0) >= 7 {
+    // WITH_THREAD is always on for 3.7 and PyPy
+    let interpreter_version = &build_config.version;
+    if (interpreter_version.major == 3 && interpreter_version.minor.unwrap_or(0) >= 7) ||
+        build_config.implementation == PythonImplementation::PyPy {
         config_map.insert("WITH_THREAD".to_owned(), "1".to_owned());
     }
 
@@ -470,7 +920,7 @@ fn main() {
 
     // 2. Export python interpreter compilation flags as cargo variables that 
     // will be visible to dependents. All flags will be available to dependent
-    // build scripts in the environment variable DEP_PYTHON27_PYTHON_FLAGS as 
+    // build scripts in the environment variable DEP_PYTHON_PYTHON_FLAGS as 
     // comma separated list; each item in the list looks like
     //
     // {VAL,FLAG}_{flag_name}=val;
@@ -492,7 +942,11 @@ fn main() {
         }
     }) + flags.as_str();
 
-    println!("cargo:python_flags={}", 
-             if flags.len() > 0 { &flags[..flags.len()-1] } else { "" });
+    let flags = if flags.len() > 0 { &flags[..flags.len()-1] } else { "" };
+    println!("cargo:python_flags={}", flags);
 
+    // 3. Export the location and the build of python, so that dependents can
+    // compile and link their own C code against it.
+    let flags: Vec<&str> = flags.split(',').filter(|flag| !flag.is_empty()).collect();
+    export_build_config(&build_config, &flags).unwrap();
 }
@@ -3,16 +3,27 @@ use syn;
 
 #[derive(Debug, PartialEq)]
 pub enum Argument {
+    /// `"/"`, the preceding arguments are positional-only
+    PosOnlySeparator,
+    /// `"*"`, the following arguments are keyword-only
     VarArgsSeparator,
     VarArgs(String),
     KeywordArgs(String),
     Arg(String, Option<String>),
-    Kwarg(String, String),
+    Kwarg(String, Option<String>),
 }
 
+/// Parses the items of `#[args(...)]`, following the rules of a python 3 signature.
+///
+/// Default values are rust expressions, either written as a literal
+/// or as a string, i.e. `#[args(a = 10, b = "Vec::new()")]`.
+/// `args = "*"` and `kwargs = "**"` may be placed anywhere in the list,
+/// arguments after `"*"` or `args = "*"` are keyword-only and
+/// arguments before `"/"` are positional-only.
 pub fn parse_arguments(items: &[syn::NestedMetaItem]) -> Vec<Argument> {
     let mut arguments = Vec::new();
-    let mut has_kw = false;
+    let mut has_default = false;
+    let mut has_posonly = false;
     let mut has_varargs = false;
     let mut has_kwargs = false;
 
@@ -24,112 +35,102 @@ pub fn parse_arguments(items: &[syn::NestedMetaItem]) -> Vec<Argument> {
         match item {
             &syn::NestedMetaItem::MetaItem(syn::MetaItem::Word(ref ident)) => {
                 // arguments in form #[args(somename)]
-                if has_kwargs {
-                    println!("syntax error, keyword arguments is defined: {:?}", args_str);
-                    return Vec::new();
-                }
-                if has_kw {
-                    println!("syntax error, argument is not allowed after keyword argument: {:?}",
-                             args_str);
-                    return Vec::new()
+                let name = ident.as_ref().to_owned();
+                if has_varargs {
+                    arguments.push(Argument::Kwarg(name, None))
+                } else {
+                    if has_default {
+                        println!("syntax error, non-default argument follows default argument: {:?}",
+                                 args_str);
+                        return Vec::new()
+                    }
+                    arguments.push(Argument::Arg(name, None))
                 }
-                arguments.push(Argument::Arg(ident.as_ref().to_owned(), None))
             }
             &syn::NestedMetaItem::MetaItem(syn::MetaItem::NameValue(ref ident, ref lit)) => {
                 let name = ident.as_ref().to_owned();
-                match lit {
-                    &syn::Lit::Str(ref s, _) => {
-                        if s == "*" {  // #[args(args="*")]
-                            if has_kwargs {
-                                println!("* - syntax error, keyword arguments is defined: {:?}",
-                                         args_str);
-                                return Vec::new()
-                            }
-                            if has_varargs {
-                                println!("*(var args) is defined: {:?}", args_str);
-                                return Vec::new()
-                            }
-                            has_varargs = true;
-                            arguments.push(Argument::VarArgs(name));
-                        }
-                        else if s == "**" {  // #[args(kwargs="**")]
-                            if has_kwargs {
-                                println!("arguments already define ** (kw args): {:?}",
-                                         args_str);
-                                return Vec::new()
-                            }
-                            has_kwargs = true;
-                            arguments.push(Argument::KeywordArgs(name));
-                        } else {
-                            if has_varargs {
-                                arguments.push(Argument::Kwarg(name, s.clone()))
-                            } else {
-                                if has_kwargs {
-                                    println!("syntax error, keyword arguments is defined: {:?}",
-                                             args_str);
-                                    return Vec::new()
-                                }
-                                has_kw = true;
-                                arguments.push(Argument::Arg(name, Some(s.clone())))
-                            }
+                let default = match lit {
+                    &syn::Lit::Str(ref s, _) if s == "*" => {  // #[args(args="*")]
+                        if has_varargs {
+                            println!("syntax error, * argument may appear only once: {:?}",
+                                     args_str);
+                            return Vec::new()
                         }
+                        has_varargs = true;
+                        arguments.push(Argument::VarArgs(name));
+                        continue
                     }
-                    &syn::Lit::Int(ref s, _) => {
-                        if has_varargs {
-                            arguments.push(Argument::Kwarg(name, format!("{}", s)));
-                        } else {
-                            if has_kwargs {
-                                println!("syntax error, keyword arguments is defined: {:?}",
-                                         args_str);
-                                return Vec::new()
-                            }
-                            has_kw = true;
-                            arguments.push(Argument::Arg(name, Some(format!("{}", s))));
+                    &syn::Lit::Str(ref s, _) if s == "**" => {  // #[args(kwargs="**")]
+                        if has_kwargs {
+                            println!("syntax error, ** argument may appear only once: {:?}",
+                                     args_str);
+                            return Vec::new()
                         }
+                        has_kwargs = true;
+                        arguments.push(Argument::KeywordArgs(name));
+                        continue
                     }
-                    &syn::Lit::Bool(ref b) => {
-                        if has_varargs {
-                            arguments.push(Argument::Kwarg(name, format!("{}", b)));
-                        } else {
-                            if has_kwargs {
-                                println!("syntax error, keyword arguments is defined: {:?}",
-                                         args_str);
-                                return Vec::new()
-                            }
-                            has_kw = true;
-                            arguments.push(Argument::Arg(name, Some(format!("{}", b))));
+                    &syn::Lit::Str(ref s, _) => {
+                        if let Err(err) = syn::parse_expr(s) {
+                            println!("syntax error, invalid default value {:?} ({}): {:?}",
+                                     s, err, args_str);
+                            return Vec::new()
                         }
+                        s.clone()
                     }
+                    &syn::Lit::Int(ref s, _) => format!("{}", s),
+                    &syn::Lit::Float(ref s, _) => s.clone(),
+                    &syn::Lit::Bool(ref b) => format!("{}", b),
                     _ => {
-                        println!("Only string literal is supported, got: {:?}", lit);
+                        println!("Only string, integer, float and bool literals are supported, \
+                                  got: {:?}", lit);
                         return Vec::new()
                     }
+                };
+                if has_varargs {
+                    arguments.push(Argument::Kwarg(name, Some(default)))
+                } else {
+                    has_default = true;
+                    arguments.push(Argument::Arg(name, Some(default)))
                 }
             }
             &syn::NestedMetaItem::Literal(ref lit) => {
                 match lit {
-                    &syn::Lit::Str(ref s, _) => {
+                    &syn::Lit::Str(ref s, _) if s == "*" => {
                         // #[args("*")]
-                        if s == "*" {
-                            if has_kwargs {
-                                println!(
-                                    "syntax error, keyword arguments is defined: {:?}",
-                                    args_str);
-                                return Vec::new()
-                            }
-                            if has_varargs {
-                                println!(
-                                    "arguments already define * (var args): {:?}",
-                                    args_str);
-                                return Vec::new()
-                            }
-                            has_varargs = true;
-                            arguments.push(Argument::VarArgsSeparator);
-                        } else {
-                            println!("Unknown string literal, got: {:?} args: {:?}",
-                                     s, args_str);
+                        if has_varargs {
+                            println!(
+                                "syntax error, * argument may appear only once: {:?}",
+                                args_str);
+                            return Vec::new()
+                        }
+                        has_varargs = true;
+                        arguments.push(Argument::VarArgsSeparator);
+                    }
+                    &syn::Lit::Str(ref s, _) if s == "/" => {
+                        // #[args(a, "/")]
+                        if has_posonly {
+                            println!("syntax error, / may appear only once: {:?}", args_str);
+                            return Vec::new()
+                        }
+                        if has_varargs {
+                            println!("syntax error, / must be ahead of *: {:?}", args_str);
                             return Vec::new()
                         }
+                        if !arguments.iter().any(|arg| match *arg {
+                            Argument::Arg(..) => true, _ => false })
+                        {
+                            println!("syntax error, at least one argument must precede /: {:?}",
+                                     args_str);
+                            return Vec::new()
+                        }
+                        has_posonly = true;
+                        arguments.push(Argument::PosOnlySeparator);
+                    }
+                    &syn::Lit::Str(ref s, _) => {
+                        println!("Unknown string literal, got: {:?} args: {:?}",
+                                 s, args_str);
+                        return Vec::new()
                     }
                     _ => {
                         println!("Only string literal is supported, got: {:?} args: {:?}",
@@ -145,6 +146,13 @@ pub fn parse_arguments(items: &[syn::NestedMetaItem]) -> Vec<Argument> {
         }
     }
 
+    if arguments.contains(&Argument::VarArgsSeparator) &&
+        !arguments.iter().any(|arg| match *arg { Argument::Kwarg(..) => true, _ => false })
+    {
+        println!("syntax error, named arguments must follow bare *: {:?}", args_str);
+        return Vec::new()
+    }
+
     arguments
 }
 
@@ -169,8 +177,12 @@ mod test {
     fn test_errs() {
         assert!(parse_arguments(&items("#[args(test=\"1\", test2)]")).is_empty());
         assert!(parse_arguments(&items("#[args(test=1, \"*\", args=\"*\")]")).is_empty());
-        assert!(parse_arguments(&items("#[args(test=1, kwargs=\"**\", args=\"*\")]")).is_empty());
         assert!(parse_arguments(&items("#[args(test=1, kwargs=\"**\", args)]")).is_empty());
+        assert!(parse_arguments(&items("#[args(test, \"*\")]")).is_empty());
+        assert!(parse_arguments(&items("#[args(test, \"*\", kwargs=\"**\")]")).is_empty());
+        assert!(parse_arguments(&items("#[args(\"/\", test)]")).is_empty());
+        assert!(parse_arguments(&items("#[args(test, \"*\", test2, \"/\")]")).is_empty());
+        assert!(parse_arguments(&items("#[args(test=\"1 +\")]")).is_empty());
     }
 
     #[test]
@@ -188,7 +200,7 @@ mod test {
         assert!(args == vec![Argument::Arg("test1".to_owned(), None),
                              Argument::Arg("test2".to_owned(), Some("None".to_owned())),
                              Argument::VarArgsSeparator,
-                             Argument::Kwarg("test3".to_owned(), "None".to_owned())]);
+                             Argument::Kwarg("test3".to_owned(), Some("None".to_owned()))]);
     }
 
     #[test]
@@ -198,7 +210,29 @@ mod test {
         assert!(args == vec![Argument::Arg("test1".to_owned(), None),
                              Argument::Arg("test2".to_owned(), Some("None".to_owned())),
                              Argument::VarArgs("args".to_owned()),
-                             Argument::Kwarg("test3".to_owned(), "None".to_owned()),
+                             Argument::Kwarg("test3".to_owned(), Some("None".to_owned())),
                              Argument::KeywordArgs("kwargs".to_owned())]);
     }
+
+    #[test]
+    fn test_kwargs_any_position() {
+        let args = parse_arguments(
+            &items("#[args(kwargs=\"**\", test1=1, args=\"*\", test2)]"));
+        assert!(args == vec![Argument::KeywordArgs("kwargs".to_owned()),
+                             Argument::Arg("test1".to_owned(), Some("1".to_owned())),
+                             Argument::VarArgs("args".to_owned()),
+                             Argument::Kwarg("test2".to_owned(), None)]);
+    }
+
+    #[test]
+    fn test_posonly_and_defaults() {
+        let args = parse_arguments(
+            &items("#[args(test1, \"/\", test2=1.5, \"*\", test3=\"vec![1, 2]\", test4)]"));
+        assert!(args == vec![Argument::Arg("test1".to_owned(), None),
+                             Argument::PosOnlySeparator,
+                             Argument::Arg("test2".to_owned(), Some("1.5".to_owned())),
+                             Argument::VarArgsSeparator,
+                             Argument::Kwarg("test3".to_owned(), Some("vec![1, 2]".to_owned())),
+                             Argument::Kwarg("test4".to_owned(), None)]);
+    }
 }
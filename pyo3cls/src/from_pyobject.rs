@@ -0,0 +1,166 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use syn;
+use quote::Tokens;
+
+use utils;
+
+
+/// Where the value of a named field is looked up.
+#[derive(Clone, Copy, PartialEq)]
+enum Source {
+    /// `getattr(obj, name)`
+    Attribute,
+    /// `obj[name]`
+    Item,
+}
+
+pub fn build_from_py_object(ast: &syn::DeriveInput) -> Tokens {
+    let cls = &ast.ident;
+    let source = container_source(&ast.attrs, Source::Attribute);
+
+    let body = match ast.body {
+        syn::Body::Struct(ref data) => {
+            let owner = cls.as_ref().to_owned();
+            let ctor = quote! { #cls };
+            impl_variant_data(&ctor, &owner, data, source)
+        },
+        syn::Body::Enum(ref variants) => {
+            let mut arms = Vec::new();
+            for variant in variants.iter() {
+                let vname = &variant.ident;
+                let name = vname.as_ref().to_owned();
+                let owner = format!("{}::{}", cls, vname);
+                let ctor = quote! { #cls::#vname };
+                let source = container_source(&variant.attrs, source);
+                let body = impl_variant_data(&ctor, &owner, &variant.data, source);
+                arms.push(quote! {
+                    match (|| -> _pyo3::PyResult<Self> { #body })() {
+                        Ok(value) => return Ok(value),
+                        Err(err) => _errors.push((#name, err)),
+                    }
+                });
+            }
+            let owner = cls.as_ref().to_owned();
+            quote! {
+                let mut _errors = Vec::new();
+                #(#arms)*
+                Err(_pyo3::derive_utils::no_matching_variant(_pyo3::PyObjectWithToken::py(_obj), #owner, _errors))
+            }
+        },
+    };
+
+    let mut generics = ast.generics.clone();
+    let lifetime = match ast.generics.lifetimes.first() {
+        Some(def) => def.lifetime.clone(),
+        None => {
+            generics.lifetimes.insert(0, syn::LifetimeDef::new("'source"));
+            syn::Lifetime::new("'source")
+        }
+    };
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    let mut predicates: Vec<Tokens> = ast.generics.where_clause.predicates.iter()
+        .map(|pred| quote! { #pred }).collect();
+    for param in ast.generics.ty_params.iter() {
+        let ident = &param.ident;
+        predicates.push(quote! { #ident: _pyo3::FromPyObject<#lifetime> });
+    }
+    let where_clause = if predicates.is_empty() {
+        quote! {}
+    } else {
+        quote! { where #(#predicates),* }
+    };
+
+    let dummy_const = syn::Ident::new(format!("_IMPL_PYO3_FROM_PY_OBJECT_{}", cls));
+
+    quote! {
+        #[allow(non_upper_case_globals, unused_attributes,
+                unused_qualifications, unused_variables, non_camel_case_types)]
+        const #dummy_const: () = {
+            extern crate pyo3 as _pyo3;
+
+            impl #impl_generics _pyo3::FromPyObject<#lifetime> for #cls #ty_generics #where_clause {
+                fn extract(_obj: &#lifetime _pyo3::PyObjectRef) -> _pyo3::PyResult<Self> {
+                    #body
+                }
+            }
+        };
+    }
+}
+
+/// Generates the extraction of a struct or an enum variant, evaluating to `PyResult<Self>`.
+fn impl_variant_data(ctor: &Tokens, owner: &str,
+                     data: &syn::VariantData, source: Source) -> Tokens {
+    match *data {
+        syn::VariantData::Struct(ref fields) => {
+            let mut values = Vec::new();
+            for field in fields.iter() {
+                let ident = field.ident.as_ref().unwrap();
+                let (source, name) = field_source(field, source);
+                let value = match source {
+                    Source::Attribute => quote! {
+                        _pyo3::derive_utils::extract_attr(_obj, #owner, #name)?
+                    },
+                    Source::Item => quote! {
+                        _pyo3::derive_utils::extract_item(_obj, #owner, #name)?
+                    },
+                };
+                values.push(quote! { #ident: #value });
+            }
+            quote! {
+                Ok(#ctor { #(#values),* })
+            }
+        },
+        syn::VariantData::Tuple(ref fields) if fields.len() == 1 => {
+            quote! {
+                Ok(#ctor(_pyo3::derive_utils::extract_newtype(_obj, #owner)?))
+            }
+        },
+        syn::VariantData::Tuple(ref fields) => {
+            let len = fields.len();
+            let values: Vec<Tokens> = (0..len).map(|idx| quote! {
+                _pyo3::derive_utils::extract_element(_seq, #owner, #idx)?
+            }).collect();
+            quote! {
+                let _seq = _pyo3::derive_utils::sequence(_obj, #owner, #len)?;
+                Ok(#ctor(#(#values),*))
+            }
+        },
+        syn::VariantData::Unit =>
+            panic!("#[derive(FromPyObject)] can not be used with unit structs or variants"),
+    }
+}
+
+/// Returns the default field source set with `#[pyo3(item)]` or `#[pyo3(attribute)]`.
+fn container_source(attrs: &Vec<syn::Attribute>, default: Source) -> Source {
+    let mut source = default;
+    for (key, value) in utils::get_pyo3_options(attrs) {
+        match (key.as_ref(), value) {
+            ("item", None) => source = Source::Item,
+            ("attribute", None) => source = Source::Attribute,
+            ("item", Some(_)) | ("attribute", Some(_)) =>
+                panic!("#[pyo3({} = \"...\")] is only supported on fields", key),
+            _ => (),
+        }
+    }
+    source
+}
+
+/// Returns the source and the Python name of a named field.
+fn field_source(field: &syn::Field, default: Source) -> (Source, String) {
+    let mut source = default;
+    let mut name = field.ident.as_ref().unwrap().as_ref().to_owned();
+    for (key, value) in utils::get_pyo3_options(&field.attrs) {
+        match key.as_ref() {
+            "item" => source = Source::Item,
+            "attribute" => source = Source::Attribute,
+            _ => continue,
+        }
+        if let Some(value) = value {
+            name = value;
+        }
+    }
+    (source, name)
+}
@@ -14,6 +14,7 @@ use proc_macro::TokenStream;
 use quote::{Tokens, ToTokens};
 
 mod py_class;
+mod py_exception;
 mod py_impl;
 mod py_proto;
 mod py_method;
@@ -23,6 +24,8 @@ mod func;
 mod method;
 mod module;
 mod utils;
+mod from_pyobject;
+mod to_pyobject;
 
 
 #[proc_macro_attribute]
@@ -101,6 +104,25 @@ pub fn class(attr: TokenStream, input: TokenStream) -> TokenStream {
     TokenStream::from_str(s.as_str()).unwrap()
 }
 
+#[proc_macro_attribute]
+pub fn exception(attr: TokenStream, input: TokenStream) -> TokenStream {
+    // Construct a string representation of the type definition
+    let source = input.to_string();
+
+    // Parse the string representation into a syntax tree
+    let mut ast = syn::parse_derive_input(&source).unwrap();
+
+    // Build the output
+    let expanded = py_exception::build_py_exception(&mut ast, attr.to_string());
+
+    // Return the generated impl as a TokenStream
+    let mut tokens = Tokens::new();
+    ast.to_tokens(&mut tokens);
+    let s = String::from(tokens.as_str()) + expanded.as_str();
+
+    TokenStream::from_str(s.as_str()).unwrap()
+}
+
 #[proc_macro_attribute]
 pub fn methods(_: TokenStream, input: TokenStream) -> TokenStream {
     // Construct a string representation of the type definition
@@ -119,3 +141,45 @@ pub fn methods(_: TokenStream, input: TokenStream) -> TokenStream {
 
     TokenStream::from_str(s.as_str()).unwrap()
 }
+
+#[proc_macro_derive(FromPyObject, attributes(pyo3))]
+pub fn derive_from_py_object(input: TokenStream) -> TokenStream {
+    // Construct a string representation of the type definition
+    let source = input.to_string();
+
+    // Parse the string representation into a syntax tree
+    let ast = syn::parse_derive_input(&source).unwrap();
+
+    // Build the output
+    let expanded = from_pyobject::build_from_py_object(&ast);
+
+    TokenStream::from_str(expanded.as_str()).unwrap()
+}
+
+#[proc_macro_derive(ToPyObject, attributes(pyo3))]
+pub fn derive_to_py_object(input: TokenStream) -> TokenStream {
+    // Construct a string representation of the type definition
+    let source = input.to_string();
+
+    // Parse the string representation into a syntax tree
+    let ast = syn::parse_derive_input(&source).unwrap();
+
+    // Build the output
+    let expanded = to_pyobject::build_to_py_object(&ast, to_pyobject::Conversion::ToObject);
+
+    TokenStream::from_str(expanded.as_str()).unwrap()
+}
+
+#[proc_macro_derive(IntoPyObject, attributes(pyo3))]
+pub fn derive_into_py_object(input: TokenStream) -> TokenStream {
+    // Construct a string representation of the type definition
+    let source = input.to_string();
+
+    // Parse the string representation into a syntax tree
+    let ast = syn::parse_derive_input(&source).unwrap();
+
+    // Build the output
+    let expanded = to_pyobject::build_to_py_object(&ast, to_pyobject::Conversion::IntoObject);
+
+    TokenStream::from_str(expanded.as_str()).unwrap()
+}
@@ -1,7 +1,7 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
 use syn;
-use quote::{Tokens, Ident};
+use quote::Tokens;
 
 use args::{Argument, parse_arguments};
 use utils::for_err_msg;
@@ -124,7 +124,6 @@ impl<'a> FnSpec<'a> {
         for s in self.attrs.iter() {
             match *s {
                 Argument::VarArgs(_) => return true,
-                Argument::VarArgsSeparator => return true,
                 _ => (),
             }
         }
@@ -155,18 +154,12 @@ impl<'a> FnSpec<'a> {
     pub fn default_value(&self, name: &syn::Ident) -> Option<Tokens> {
         for s in self.attrs.iter() {
             match *s {
-                Argument::Arg(ref ident, ref opt) => {
+                Argument::Arg(ref ident, Some(ref val)) |
+                Argument::Kwarg(ref ident, Some(ref val)) => {
                     if ident.as_str() == name.as_ref() {
-                        if let &Some(ref val) = opt {
-                            let i = Ident::from(val.as_str());
-                            return Some(quote!(#i))
-                        }
-                    }
-                },
-                Argument::Kwarg(ref ident, ref opt) => {
-                    if ident.as_str() == name.as_ref() {
-                        let i = Ident::from(opt.as_str());
-                        return Some(quote!(#i))
+                        // already validated by parse_arguments()
+                        let expr = syn::parse_expr(val).unwrap();
+                        return Some(quote!(#expr))
                     }
                 },
                 _ => (),
@@ -188,6 +181,24 @@ impl<'a> FnSpec<'a> {
         }
         false
     }
+
+    pub fn is_pos_only(&self, name: &syn::Ident) -> bool {
+        if !self.attrs.contains(&Argument::PosOnlySeparator) {
+            return false
+        }
+        for s in self.attrs.iter() {
+            match *s {
+                Argument::Arg(ref ident, _) => {
+                    if ident.as_str() == name.as_ref() {
+                        return true
+                    }
+                },
+                Argument::PosOnlySeparator => return false,
+                _ => (),
+            }
+        }
+        false
+    }
 }
 
 pub fn is_ref<'a>(name: &'a syn::Ident, ty: &'a syn::Ty) -> bool {
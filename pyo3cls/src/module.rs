@@ -46,41 +46,44 @@ pub fn py3_init(fnname: &syn::Ident, name: &String, doc: syn::Lit) -> Tokens {
         pub unsafe extern "C" fn #cb_name() -> *mut ::pyo3::ffi::PyObject {
             extern crate pyo3;
             use std;
-            use pyo3::{IntoPyPointer, ObjectProtocol};
 
             // initialize pyo3
             pyo3::prepare_pyo3_library();
 
-            static mut MODULE_DEF: pyo3::ffi::PyModuleDef = pyo3::ffi::PyModuleDef_INIT;
-            // We can't convert &'static str to *const c_char within a static initializer,
-            // so we'll do it here in the module initialization:
-            MODULE_DEF.m_name = concat!(stringify!(#m_name), "\0").as_ptr() as *const _;
-
             #[cfg(py_sys_config = "WITH_THREAD")]
             pyo3::ffi::PyEval_InitThreads();
 
-            let _module = pyo3::ffi::PyModule_Create(&mut MODULE_DEF);
-            if _module.is_null() {
-                return _module;
-            }
-
-            let _pool = pyo3::GILPool::new();
-            let _py = pyo3::Python::assume_gil_acquired();
-            let _module = match _py.from_owned_ptr_or_err::<pyo3::PyModule>(_module) {
-                Ok(m) => m,
-                Err(e) => {
-                    pyo3::PyErr::from(e).restore(_py);
-                    return std::ptr::null_mut();
-                }
-            };
-            _module.add("__doc__", #doc).expect("Failed to add doc for module");
-            match #fnname(_py, _module) {
-                Ok(_) => _module.into_ptr(),
-                Err(e) => {
-                    e.restore(_py);
-                    std::ptr::null_mut()
+            // called by the import machinery for the module object of each interpreter
+            unsafe extern "C" fn _exec(_module: *mut pyo3::ffi::PyObject) -> std::os::raw::c_int {
+                let _pool = pyo3::GILPool::new();
+                let _py = pyo3::Python::assume_gil_acquired();
+                let _module = match _py.from_borrowed_ptr_or_err::<pyo3::PyModule>(_module) {
+                    Ok(m) => m,
+                    Err(e) => {
+                        pyo3::PyErr::from(e).restore(_py);
+                        return -1;
+                    }
+                };
+                _module.add("__doc__", #doc).expect("Failed to add doc for module");
+                match #fnname(_py, _module) {
+                    Ok(_) => 0,
+                    Err(e) => {
+                        e.restore(_py);
+                        -1
+                    }
                 }
             }
+
+            static mut MODULE_DEF: pyo3::ffi::PyModuleDef = pyo3::ffi::PyModuleDef_INIT;
+            static mut SLOTS: [pyo3::ffi::PyModuleDef_Slot; 2] = [
+                pyo3::ffi::PyModuleDef_Slot { slot: 0, value: 0 as *mut _ },
+                pyo3::ffi::PyModuleDef_Slot { slot: 0, value: 0 as *mut _ },
+            ];
+            // We can't convert &'static str to *const c_char within a static initializer,
+            // so we'll do it here in the module initialization:
+            MODULE_DEF.m_name = concat!(stringify!(#m_name), "\0").as_ptr() as *const _;
+
+            pyo3::PyModule::init_def(&mut MODULE_DEF, &mut SLOTS, _exec)
         }
     }
 }
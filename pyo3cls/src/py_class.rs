@@ -16,6 +16,7 @@ pub fn build_py_class(ast: &mut syn::DeriveInput, attr: String) -> Tokens {
     let doc = utils::get_doc(&ast.attrs, true);
     let mut token: Option<syn::Ident> = None;
     let mut descriptors = Vec::new();
+    let mut variants = Vec::new();
     match ast.body {
         syn::Body::Struct(syn::VariantData::Struct(ref mut fields)) => {
             for field in fields.iter_mut() {
@@ -30,10 +31,23 @@ pub fn build_py_class(ast: &mut syn::DeriveInput, attr: String) -> Tokens {
                 }
             }
         },
-        _ => panic!("#[class] can only be used with normal structs"),
+        syn::Body::Enum(ref enum_variants) => {
+            for variant in enum_variants.iter() {
+                match variant.data {
+                    syn::VariantData::Unit => variants.push(variant.ident.clone()),
+                    _ => panic!("#[class] can only be used with C-like enums"),
+                }
+            }
+        },
+        _ => panic!("#[class] can only be used with normal structs or C-like enums"),
     }
 
     let dummy_const = syn::Ident::new(format!("_IMPL_PYO3_CLS_{}", ast.ident));
+    let enum_tokens = if let syn::Body::Enum(_) = ast.body {
+        Some(impl_enum(&ast.ident, &params, &variants))
+    } else {
+        None
+    };
     let tokens = impl_class(&ast.ident, &base, token, doc, params, flags, descriptors);
 
     quote! {
@@ -44,6 +58,7 @@ pub fn build_py_class(ast: &mut syn::DeriveInput, attr: String) -> Tokens {
             extern crate pyo3 as _pyo3;
 
             #tokens
+            #enum_tokens
         };
     }
 }
@@ -251,9 +266,18 @@ fn impl_class(cls: &syn::Ident, base: &syn::Ident,
             };
 
             #[inline]
-            unsafe fn type_object() -> &'static mut _pyo3::ffi::PyTypeObject {
-                static mut TYPE_OBJECT: _pyo3::ffi::PyTypeObject = _pyo3::ffi::PyTypeObject_INIT;
-                &mut TYPE_OBJECT
+            fn type_object() -> *mut _pyo3::ffi::PyTypeObject {
+                let storage = Self::type_storage();
+                if !storage.is_ready() {
+                    <#cls as _pyo3::typeob::PyTypeObject>::init_type();
+                }
+                storage.get()
+            }
+
+            #[inline]
+            fn type_storage() -> &'static _pyo3::typeob::TypeStorage {
+                static TYPE_OBJECT: _pyo3::typeob::TypeStorage = _pyo3::typeob::TypeStorage::INIT;
+                &TYPE_OBJECT
             }
         }
 
@@ -262,9 +286,9 @@ fn impl_class(cls: &syn::Ident, base: &syn::Ident,
             fn init_type() {
                 static START: std::sync::Once = std::sync::ONCE_INIT;
                 START.call_once(|| {
-                    let ty = unsafe{<#cls as _pyo3::typeob::PyTypeInfo>::type_object()};
+                    let storage = <#cls as _pyo3::typeob::PyTypeInfo>::type_storage();
 
-                    if (ty.tp_flags & _pyo3::ffi::Py_TPFLAGS_READY) == 0 {
+                    if !storage.is_ready() {
                         let gil = _pyo3::Python::acquire_gil();
                         let py = gil.python();
 
@@ -282,6 +306,147 @@ fn impl_class(cls: &syn::Ident, base: &syn::Ident,
     }
 }
 
+/// Generates the class attributes, protocols and conversions of a C-like enum.
+///
+/// `PyObjectProtocol` and `PyNumberProtocol` are implemented here, so enums
+/// can not have their own `#[py::proto]` impls of these two traits.
+fn impl_enum(cls: &syn::Ident, params: &HashMap<&'static str, syn::Ident>,
+             variants: &Vec<syn::Ident>) -> Tokens {
+    let cls_name = match params.get("name") {
+        Some(name) => quote! { #name }.as_str().to_string(),
+        None => quote! { #cls }.as_str().to_string()
+    };
+
+    let attrs: Vec<Tokens> = variants.iter().map(|variant| {
+        let name = variant.as_ref();
+        quote! { (#name, variant(py, #cls::#variant)?) }
+    }).collect();
+    let names: Vec<Tokens> = variants.iter().map(|variant| {
+        let name = variant.as_ref();
+        quote! { #cls::#variant => #name }
+    }).collect();
+    let reprs: Vec<Tokens> = variants.iter().map(|variant| {
+        let repr = format!("{}.{}", cls_name, variant);
+        quote! { #cls::#variant => #repr }
+    }).collect();
+    let values: Vec<Tokens> = variants.iter().map(|variant| {
+        quote! { #cls::#variant => #cls::#variant as isize }
+    }).collect();
+    let copies: Vec<Tokens> = variants.iter().map(|variant| {
+        quote! { #cls::#variant => #cls::#variant }
+    }).collect();
+
+    quote! {
+        impl #cls {
+            fn __pyo3_enum_value(&self) -> isize {
+                match *self {
+                    #(#values),*
+                }
+            }
+        }
+
+        impl _pyo3::class::methods::PyClassAttrsProtocolImpl for #cls {
+            fn class_attrs(py: _pyo3::Python)
+                           -> _pyo3::PyResult<Vec<(&'static str, _pyo3::PyObject)>>
+            {
+                fn variant(py: _pyo3::Python, value: #cls) -> _pyo3::PyResult<_pyo3::PyObject> {
+                    unsafe {
+                        let ty = <#cls as _pyo3::typeob::PyTypeInfo>::type_object();
+                        let obj = _pyo3::PyRawObject::new(py, ty, ty)?;
+                        obj.init(|_| value)?;
+                        Ok(_pyo3::PyObject::from_owned_ptr(py, _pyo3::IntoPyPointer::into_ptr(obj)))
+                    }
+                }
+
+                Ok(vec![#(#attrs),*])
+            }
+        }
+
+        impl<'p> _pyo3::class::basic::PyObjectProtocol<'p> for #cls {
+            fn __repr__(&'p self)
+                        -> <#cls as _pyo3::class::basic::PyObjectReprProtocol<'p>>::Result
+            {
+                Ok(match *self {
+                    #(#reprs),*
+                })
+            }
+
+            fn __hash__(&'p self)
+                        -> <#cls as _pyo3::class::basic::PyObjectHashProtocol<'p>>::Result
+            {
+                Ok(self.__pyo3_enum_value())
+            }
+
+            fn __richcmp__(&'p self,
+                           other: <#cls as _pyo3::class::basic::PyObjectRichcmpProtocol<'p>>::Other,
+                           op: _pyo3::CompareOp)
+                           -> <#cls as _pyo3::class::basic::PyObjectRichcmpProtocol<'p>>::Result
+            {
+                let py = _pyo3::PyObjectWithToken::py(other);
+                let other = match <#cls as _pyo3::PyTryFrom>::try_from(other) {
+                    Ok(other) => other,
+                    Err(_) => return Ok(py.NotImplemented()),
+                };
+                let eq = self.__pyo3_enum_value() == other.__pyo3_enum_value();
+                match op {
+                    _pyo3::CompareOp::Eq => Ok(_pyo3::ToPyObject::to_object(&eq, py)),
+                    _pyo3::CompareOp::Ne => Ok(_pyo3::ToPyObject::to_object(&!eq, py)),
+                    _ => Ok(py.NotImplemented()),
+                }
+            }
+        }
+        impl<'p> _pyo3::class::basic::PyObjectReprProtocol<'p> for #cls {
+            type Success = &'static str;
+            type Result = _pyo3::PyResult<&'static str>;
+        }
+        impl<'p> _pyo3::class::basic::PyObjectHashProtocol<'p> for #cls {
+            type Result = _pyo3::PyResult<isize>;
+        }
+        impl<'p> _pyo3::class::basic::PyObjectRichcmpProtocol<'p> for #cls {
+            type Other = &'p _pyo3::PyObjectRef;
+            type Success = _pyo3::PyObject;
+            type Result = _pyo3::PyResult<_pyo3::PyObject>;
+        }
+
+        impl<'p> _pyo3::class::number::PyNumberProtocol<'p> for #cls {
+            fn __int__(&'p self)
+                       -> <#cls as _pyo3::class::number::PyNumberIntProtocol<'p>>::Result
+            {
+                Ok(self.__pyo3_enum_value())
+            }
+        }
+        impl<'p> _pyo3::class::number::PyNumberIntProtocol<'p> for #cls {
+            type Success = isize;
+            type Result = _pyo3::PyResult<isize>;
+        }
+
+        impl _pyo3::ToPyObject for #cls {
+            /// Returns the class attribute of the variant.
+            fn to_object(&self, py: _pyo3::Python) -> _pyo3::PyObject {
+                let name = match *self {
+                    #(#names),*
+                };
+                _pyo3::ObjectProtocol::getattr(py.get_type::<#cls>(), name)
+                    .expect("Enum variant is not set on the class").into()
+            }
+        }
+        impl _pyo3::IntoPyObject for #cls {
+            fn into_object(self, py: _pyo3::Python) -> _pyo3::PyObject {
+                _pyo3::ToPyObject::to_object(&self, py)
+            }
+        }
+
+        impl<'a> _pyo3::FromPyObject<'a> for #cls {
+            fn extract(ob: &'a _pyo3::PyObjectRef) -> _pyo3::PyResult<Self> {
+                let value = <#cls as _pyo3::PyTryFrom>::try_from(ob)?;
+                Ok(match *value {
+                    #(#copies),*
+                })
+            }
+        }
+    }
+}
+
 fn impl_descriptors(cls: &syn::Ty, descriptors: Vec<(syn::Field, Vec<FnType>)>) -> Tokens {
     let methods: Vec<Tokens> = descriptors.iter().flat_map(|&(ref field, ref fns)| {
         fns.iter().map(|desc| {
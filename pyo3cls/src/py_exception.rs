@@ -0,0 +1,135 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use syn;
+use quote::{Tokens, ToTokens};
+
+use utils;
+
+
+pub fn build_py_exception(ast: &mut syn::DeriveInput, attr: String) -> Tokens {
+    let cls = &ast.ident;
+    let (module, base) = parse_attribute(attr);
+    let module = match module {
+        Some(module) => module,
+        None => panic!("#[exception] requires a module name, e.g. #[exception(module = \"mymodule\")]"),
+    };
+    if !ast.generics.lifetimes.is_empty() || !ast.generics.ty_params.is_empty() {
+        panic!("#[exception] can not be used with generic structs");
+    }
+
+    let fields: Vec<&syn::Ident> = match ast.body {
+        syn::Body::Struct(syn::VariantData::Struct(ref fields)) =>
+            fields.iter().map(|field| field.ident.as_ref().unwrap()).collect(),
+        syn::Body::Struct(syn::VariantData::Unit) => Vec::new(),
+        _ => panic!("#[exception] can only be used with structs with named fields or unit structs"),
+    };
+    let items: Vec<Tokens> = fields.iter().map(|field| {
+        let name = field.as_ref();
+        quote! { (#name, _pyo3::IntoPyObject::into_object(err.#field, py)) }
+    }).collect();
+
+    let name = format!("{}.{}", module, cls);
+    let doc = utils::get_doc(&ast.attrs, false);
+    let dummy_const = syn::Ident::new(format!("_IMPL_PYO3_EXCEPTION_{}", cls));
+
+    quote! {
+        #[allow(non_upper_case_globals, unused_attributes,
+                unused_qualifications, unused_variables, non_camel_case_types)]
+        const #dummy_const: () = {
+            extern crate pyo3 as _pyo3;
+
+            impl _pyo3::typeob::PyTypeObject for #cls {
+                #[inline(always)]
+                fn init_type() {}
+
+                #[inline]
+                fn type_object() -> _pyo3::Py<_pyo3::PyType> {
+                    static mut TYPE_OBJECT: *mut _pyo3::ffi::PyTypeObject =
+                        0 as *mut _pyo3::ffi::PyTypeObject;
+
+                    unsafe {
+                        if TYPE_OBJECT.is_null() {
+                            let gil = _pyo3::Python::acquire_gil();
+                            let py = gil.python();
+
+                            TYPE_OBJECT = _pyo3::derive_utils::new_exception_type(
+                                py, #name, py.get_type::<#base>(), #doc);
+                        }
+                        _pyo3::Py::from_borrowed_ptr(
+                            TYPE_OBJECT as *const _ as *mut _pyo3::ffi::PyObject)
+                    }
+                }
+            }
+
+            impl ::std::convert::From<#cls> for _pyo3::PyErr {
+                fn from(err: #cls) -> _pyo3::PyErr {
+                    let gil = _pyo3::Python::acquire_gil();
+                    let py = gil.python();
+
+                    let message = _pyo3::derive_utils::ExceptionMessage::exception_message(&err);
+                    _pyo3::derive_utils::new_exception::<#cls>(
+                        py, message, vec![#(#items),*])
+                }
+            }
+        };
+    }
+}
+
+/// Parses `(module = "pkg.module", base = path::to::Exception)`.
+fn parse_attribute(attr: String) -> (Option<String>, syn::Ident) {
+    let mut module = None;
+    let mut base = syn::Ident::from("_pyo3::exc::Exception");
+
+    let tts = match syn::parse_token_trees(&attr) {
+        Ok(tts) => tts,
+        Err(_) => return (module, base),
+    };
+
+    let mut elems = Vec::new();
+    for tt in tts.iter() {
+        match tt {
+            &syn::TokenTree::Delimited(ref delimited) => {
+                let mut elem = Vec::new();
+                for tt in delimited.tts.iter() {
+                    match tt {
+                        &syn::TokenTree::Token(syn::Token::Comma) =>
+                            elems.push(::std::mem::replace(&mut elem, Vec::new())),
+                        _ => elem.push(tt.clone()),
+                    }
+                }
+                if !elem.is_empty() {
+                    elems.push(elem);
+                }
+            }
+            _ => println!("Wrong format: {:?}", attr),
+        }
+    }
+
+    for elem in elems {
+        if elem.len() < 3 || elem[1] != syn::TokenTree::Token(syn::Token::Eq) {
+            println!("Wrong format: {:?}", attr);
+            continue
+        }
+        match elem[0] {
+            syn::TokenTree::Token(syn::Token::Ident(ref ident)) if ident.as_ref() == "module" => {
+                match elem[2] {
+                    syn::TokenTree::Token(syn::Token::Literal(syn::Lit::Str(ref s, _)))
+                        if elem.len() == 3 => module = Some(s.clone()),
+                    _ => println!("Wrong 'module' format: {:?}", elem),
+                }
+            }
+            syn::TokenTree::Token(syn::Token::Ident(ref ident)) if ident.as_ref() == "base" => {
+                let mut m = String::new();
+                for el in elem[2..].iter() {
+                    let mut t = Tokens::new();
+                    el.to_tokens(&mut t);
+                    m += t.as_str().trim();
+                }
+                base = syn::Ident::from(m.as_str());
+            }
+            _ => println!("Unsupported parameter: {:?}", elem[0]),
+        }
+    }
+
+    (module, base)
+}
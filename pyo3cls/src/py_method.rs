@@ -368,6 +368,11 @@ pub fn impl_arg_params(spec: &FnSpec, body: Tokens) -> Tokens {
             } else {
                 syn::Ident::from("false")
             };
+            let posonly = if spec.is_pos_only(&arg.name) {
+                syn::Ident::from("true")
+            } else {
+                syn::Ident::from("false")
+            };
 
             let opt = if let Some(_) = arg.optional {
                 syn::Ident::from("true")
@@ -380,7 +385,7 @@ pub fn impl_arg_params(spec: &FnSpec, body: Tokens) -> Tokens {
             params.push(
                 quote! {
                     _pyo3::argparse::ParamDescription{
-                        name: #name, is_optional: #opt, kw_only: #kwonly}
+                        name: #name, is_optional: #opt, kw_only: #kwonly, pos_only: #posonly}
                 }
             );
         }
@@ -412,7 +417,7 @@ pub fn impl_arg_params(spec: &FnSpec, body: Tokens) -> Tokens {
         match _pyo3::argparse::parse_args(Some(_LOCATION), _PARAMS, &_args,
             _kwargs, #accept_args, #accept_kwargs, &mut _output)
         {
-            Ok(_) => {
+            Ok((_varargs, _varkwargs)) => {
                 let mut _iter = _output.iter();
 
                 #body
@@ -436,7 +441,7 @@ fn impl_arg_param(arg: &FnArg, spec: &FnSpec, body: &Tokens, idx: usize) -> Toke
 
     if spec.is_args(&name) {
         quote! {
-            match <#ty as _pyo3::FromPyObject>::extract(_args.as_ref())
+            match <#ty as _pyo3::FromPyObject>::extract(_varargs.as_ref())
             {
                 Ok(#arg_name) => {
                     #body
@@ -447,7 +452,7 @@ fn impl_arg_param(arg: &FnArg, spec: &FnSpec, body: &Tokens, idx: usize) -> Toke
     }
     else if spec.is_kwargs(&name) {
         quote! {{
-            let #arg_name = _kwargs;
+            let #arg_name = _varkwargs;
             #body
         }}
     }
@@ -0,0 +1,266 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use syn;
+use quote::Tokens;
+
+use utils;
+
+
+/// Conversion trait to implement.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Conversion {
+    /// `ToPyObject`, fields are converted by reference
+    ToObject,
+    /// `IntoPyObject`, fields are moved out of `self`
+    IntoObject,
+}
+
+/// Python representation of a struct.
+#[derive(Clone, Copy, PartialEq)]
+enum StructRepr {
+    /// `dict`
+    Dict,
+    /// `types.SimpleNamespace`
+    Namespace,
+    /// `collections.namedtuple`
+    NamedTuple,
+}
+
+/// Python representation of a unit-only enum.
+#[derive(Clone, Copy, PartialEq)]
+enum EnumRepr {
+    /// The variant name as `str`
+    Str,
+    /// The variant discriminant as `int`
+    Int,
+}
+
+pub fn build_to_py_object(ast: &syn::DeriveInput, conv: Conversion) -> Tokens {
+    let cls = &ast.ident;
+
+    let body = match ast.body {
+        syn::Body::Struct(ref data) => impl_struct(ast, data, conv),
+        syn::Body::Enum(ref variants) => impl_enum(ast, variants, conv),
+    };
+
+    let (trait_name, method, self_arg, bound) = match conv {
+        Conversion::ToObject => (
+            quote! { _pyo3::ToPyObject }, quote! { to_object }, quote! { &self },
+            quote! { _pyo3::ToPyObject }),
+        Conversion::IntoObject => (
+            quote! { _pyo3::IntoPyObject }, quote! { into_object }, quote! { self },
+            quote! { _pyo3::IntoPyObject }),
+    };
+
+    let (impl_generics, ty_generics, _) = ast.generics.split_for_impl();
+    let mut predicates: Vec<Tokens> = ast.generics.where_clause.predicates.iter()
+        .map(|pred| quote! { #pred }).collect();
+    for param in ast.generics.ty_params.iter() {
+        let ident = &param.ident;
+        predicates.push(quote! { #ident: #bound });
+    }
+    let where_clause = if predicates.is_empty() {
+        quote! {}
+    } else {
+        quote! { where #(#predicates),* }
+    };
+
+    let dummy_const = match conv {
+        Conversion::ToObject =>
+            syn::Ident::new(format!("_IMPL_PYO3_TO_PY_OBJECT_{}", cls)),
+        Conversion::IntoObject =>
+            syn::Ident::new(format!("_IMPL_PYO3_INTO_PY_OBJECT_{}", cls)),
+    };
+
+    quote! {
+        #[allow(non_upper_case_globals, unused_attributes,
+                unused_qualifications, unused_variables, non_camel_case_types)]
+        const #dummy_const: () = {
+            extern crate pyo3 as _pyo3;
+
+            impl #impl_generics #trait_name for #cls #ty_generics #where_clause {
+                fn #method(#self_arg, py: _pyo3::Python) -> _pyo3::PyObject {
+                    #body
+                }
+            }
+        };
+    }
+}
+
+fn impl_struct(ast: &syn::DeriveInput, data: &syn::VariantData, conv: Conversion) -> Tokens {
+    let cls = &ast.ident;
+    let convert = |member: Tokens| match conv {
+        Conversion::ToObject => quote! { _pyo3::ToPyObject::to_object(&self.#member, py) },
+        Conversion::IntoObject => quote! { _pyo3::IntoPyObject::into_object(self.#member, py) },
+    };
+
+    match *data {
+        syn::VariantData::Struct(ref fields) => {
+            let repr = struct_repr(&ast.attrs);
+            let names: Vec<String> = fields.iter().map(field_name).collect();
+            if repr == StructRepr::NamedTuple {
+                check_namedtuple_names(cls.as_ref(), &names);
+            }
+
+            let items: Vec<Tokens> = fields.iter().zip(&names).map(|(field, name)| {
+                let ident = field.ident.as_ref().unwrap();
+                let value = convert(quote! { #ident });
+                quote! { (#name, #value) }
+            }).collect();
+
+            match repr {
+                StructRepr::Dict => quote! {
+                    _pyo3::derive_utils::into_dict(py, vec![#(#items),*])
+                },
+                StructRepr::Namespace => quote! {
+                    _pyo3::derive_utils::into_namespace(py, vec![#(#items),*])
+                },
+                StructRepr::NamedTuple => {
+                    let name = cls.as_ref();
+                    quote! {
+                        _pyo3::derive_utils::into_namedtuple(
+                            py, concat!(module_path!(), "::", #name), #name, vec![#(#items),*])
+                    }
+                }
+            }
+        },
+        syn::VariantData::Tuple(ref fields) if fields.len() == 1 => {
+            let idx = syn::Ident::new("0");
+            convert(quote! { #idx })
+        },
+        syn::VariantData::Tuple(ref fields) => {
+            let items: Vec<Tokens> = (0..fields.len()).map(|idx| {
+                let idx = syn::Ident::new(idx.to_string());
+                convert(quote! { #idx })
+            }).collect();
+            quote! {
+                _pyo3::PyTuple::new(py, &[#(#items),*]).into()
+            }
+        },
+        syn::VariantData::Unit =>
+            panic!("#[derive(ToPyObject)] can not be used with unit structs"),
+    }
+}
+
+fn impl_enum(ast: &syn::DeriveInput, variants: &Vec<syn::Variant>, conv: Conversion) -> Tokens {
+    let cls = &ast.ident;
+    let repr = enum_repr(&ast.attrs);
+
+    let arms: Vec<Tokens> = variants.iter().map(|variant| {
+        match variant.data {
+            syn::VariantData::Unit => (),
+            _ => panic!("#[derive(ToPyObject)] only supports enums with unit variants"),
+        }
+        let vname = &variant.ident;
+        match repr {
+            EnumRepr::Str => {
+                let name = variant_name(variant);
+                quote! { #cls::#vname => _pyo3::ToPyObject::to_object(#name, py) }
+            },
+            EnumRepr::Int => quote! {
+                #cls::#vname => _pyo3::ToPyObject::to_object(&(#cls::#vname as isize), py)
+            },
+        }
+    }).collect();
+
+    let value = match conv {
+        Conversion::ToObject => quote! { *self },
+        Conversion::IntoObject => quote! { self },
+    };
+    quote! {
+        match #value {
+            #(#arms),*
+        }
+    }
+}
+
+fn struct_repr(attrs: &Vec<syn::Attribute>) -> StructRepr {
+    let mut repr = StructRepr::Dict;
+    for (key, _) in utils::get_pyo3_options(attrs) {
+        match key.as_ref() {
+            "dict" => repr = StructRepr::Dict,
+            "namespace" => repr = StructRepr::Namespace,
+            "namedtuple" => repr = StructRepr::NamedTuple,
+            _ => (),
+        }
+    }
+    repr
+}
+
+fn enum_repr(attrs: &Vec<syn::Attribute>) -> EnumRepr {
+    let mut repr = EnumRepr::Str;
+    for (key, _) in utils::get_pyo3_options(attrs) {
+        match key.as_ref() {
+            "str" => repr = EnumRepr::Str,
+            "int" => repr = EnumRepr::Int,
+            _ => (),
+        }
+    }
+    repr
+}
+
+/// Returns the Python name of a field, renamed with `#[pyo3(item = "...")]`
+/// or `#[pyo3(attribute = "...")]`.
+fn field_name(field: &syn::Field) -> String {
+    let mut name = field.ident.as_ref().unwrap().as_ref().to_owned();
+    for (key, value) in utils::get_pyo3_options(&field.attrs) {
+        match (key.as_ref(), value) {
+            ("item", Some(value)) | ("attribute", Some(value)) => name = value,
+            _ => (),
+        }
+    }
+    name
+}
+
+/// Python keywords, which are valid Rust identifiers but not valid `namedtuple` names.
+const PYTHON_KEYWORDS: &'static [&'static str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "exec", "finally", "for", "from",
+    "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass",
+    "print", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Panics if `collections.namedtuple` would reject the type name or a field name,
+/// the conversion itself can not fail.
+///
+/// Names must be identifiers which are not Python keywords, field names must not
+/// start with an underscore and must be unique.
+fn check_namedtuple_names(cls: &str, names: &[String]) {
+    let is_identifier = |name: &str| {
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(c) if c.is_alphabetic() || c == '_' =>
+                chars.all(|c| c.is_alphanumeric() || c == '_'),
+            _ => false,
+        }
+    };
+    let check = |what: &str, name: &str| {
+        if !is_identifier(name) || PYTHON_KEYWORDS.contains(&name) {
+            panic!("#[pyo3(namedtuple)]: {} `{}` of `{}` is not a valid Python identifier",
+                   what, name, cls);
+        }
+    };
+
+    check("type name", cls);
+    for (idx, name) in names.iter().enumerate() {
+        check("field name", name);
+        if name.starts_with('_') {
+            panic!("#[pyo3(namedtuple)]: field name `{}` of `{}` starts with an underscore",
+                   name, cls);
+        }
+        if names[..idx].contains(name) {
+            panic!("#[pyo3(namedtuple)]: duplicate field name `{}` in `{}`", name, cls);
+        }
+    }
+}
+
+/// Returns the Python name of a unit variant, renamed with `#[pyo3(name = "...")]`.
+fn variant_name(variant: &syn::Variant) -> String {
+    let mut name = variant.ident.as_ref().to_owned();
+    for (key, value) in utils::get_pyo3_options(&variant.attrs) {
+        if let ("name", Some(value)) = (key.as_ref(), value) {
+            name = value;
+        }
+    }
+    name
+}
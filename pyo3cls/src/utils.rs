@@ -43,3 +43,32 @@ pub fn get_doc(attrs: &Vec<syn::Attribute>, null_terminated: bool) -> syn::Lit {
         syn::Lit::Str(doc, syn::StrStyle::Cooked)
     }
 }
+
+/// Collects the options of all `#[pyo3(...)]` attributes.
+///
+/// `#[pyo3(item, attribute = "name")]` results in
+/// `[("item", None), ("attribute", Some("name"))]`.
+pub fn get_pyo3_options(attrs: &Vec<syn::Attribute>) -> Vec<(String, Option<String>)> {
+    let mut options = Vec::new();
+
+    for attr in attrs.iter() {
+        match attr.value {
+            syn::MetaItem::List(ref name, ref metas) if name.as_ref() == "pyo3" => {
+                for meta in metas.iter() {
+                    match *meta {
+                        syn::NestedMetaItem::MetaItem(syn::MetaItem::Word(ref ident)) => {
+                            options.push((ident.as_ref().to_owned(), None));
+                        }
+                        syn::NestedMetaItem::MetaItem(
+                            syn::MetaItem::NameValue(ref ident, syn::Lit::Str(ref s, _))) => {
+                            options.push((ident.as_ref().to_owned(), Some(s.clone())));
+                        }
+                        _ => panic!("Unsupported #[pyo3(...)] option: {}", for_err_msg(meta)),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    options
+}
@@ -4,9 +4,10 @@
 
 //! Python argument parsing
 use ffi;
-use err::PyResult;
-use python::Python;
+use err::{PyErr, PyResult};
+use python::{Python, ToPyPointer};
 use conversion::PyTryFrom;
+use instance::PyObjectWithToken;
 use objects::{PyObjectRef, PyTuple, PyDict, PyString, exc};
 
 #[derive(Debug)]
@@ -16,8 +17,10 @@ pub struct ParamDescription<'a> {
     pub name: &'a str,
     /// Whether the parameter is optional.
     pub is_optional: bool,
-    /// Whether the parameter is optional.
+    /// Whether the parameter can only be passed by keyword.
     pub kw_only: bool,
+    /// Whether the parameter can only be passed by position.
+    pub pos_only: bool,
 }
 
 /// Parse argument list
@@ -26,69 +29,126 @@ pub struct ParamDescription<'a> {
 /// * params: Declared parameters of the function
 /// * args:   Positional arguments
 /// * kwargs: Keyword arguments
+/// * accept_args: Whether extra positional arguments are collected (`*args`)
+/// * accept_kwargs: Whether extra keyword arguments are collected (`**kwargs`)
 /// * output: Output array that receives the arguments.
 ///           Must have same length as `params` and must be initialized to `None`.
+///
+/// Returns the extra positional and keyword arguments. Error messages follow
+/// the wording of CPython.
 pub fn parse_args<'p>(fname: Option<&str>, params: &[ParamDescription],
                       args: &'p PyTuple, kwargs: Option<&'p PyDict>,
                       accept_args: bool, accept_kwargs: bool,
-                      output: &mut[Option<&'p PyObjectRef>]) -> PyResult<()>
+                      output: &mut[Option<&'p PyObjectRef>])
+                      -> PyResult<(&'p PyTuple, Option<&'p PyDict>)>
 {
+    let py = args.py();
+    let fname = fname.unwrap_or("function");
+
+    // Assign positional arguments, in declaration order
     let nargs = args.len();
-    let nkeywords = kwargs.map_or(0, |d| d.len());
-    if !accept_args && (nargs + nkeywords > params.len()) {
-        return Err(exc::TypeError::new(
-            format!("{}{} takes at most {} argument{} ({} given)",
-                    fname.unwrap_or("function"),
-                    if fname.is_some() { "()" } else { "" },
-                    params.len(),
-                    if params.len() == 1 { "s" } else { "" },
-                    nargs + nkeywords
-            )));
+    let mut npositional = 0;
+    for (p, out) in params.iter().zip(output.iter_mut()) {
+        if !p.kw_only {
+            if npositional < nargs {
+                *out = Some(args.get_item(npositional));
+            }
+            npositional += 1;
+        }
     }
-    let mut used_keywords = 0;
-    // Iterate through the parameters and assign values to output:
-    for (i, (p, out)) in params.iter().zip(output).enumerate() {
-        match kwargs.and_then(|d| d.get_item(p.name)) {
-            Some(kwarg) => {
-                *out = Some(kwarg);
-                used_keywords += 1;
-                if i < nargs {
-                    return Err(exc::TypeError::new(
-                        format!("Argument given by name ('{}') and position ({})", p.name, i+1)));
-                }
-            },
-            None => {
-                if p.kw_only {
-                    if !p.is_optional {
+    if !accept_args && nargs > npositional {
+        return Err(too_many_positional(fname, params, nargs));
+    }
+    let varargs: &PyTuple = unsafe {
+        py.from_owned_ptr_or_err(ffi::PyTuple_GetSlice(
+            args.as_ptr(), npositional as ffi::Py_ssize_t, nargs as ffi::Py_ssize_t))?
+    };
+
+    // Assign keyword arguments
+    let mut varkwargs: Option<&PyDict> = None;
+    let mut posonly_names = Vec::new();
+    if let Some(kwargs) = kwargs {
+        for (key, value) in kwargs.iter() {
+            let name = match PyString::try_from(key) {
+                Ok(name) => name.to_string()?,
+                Err(_) => return Err(exc::TypeError::new(
+                    format!("{} keywords must be strings", fname))),
+            };
+            match params.iter().position(|p| p.name == name) {
+                Some(i) if !params[i].pos_only => {
+                    if output[i].is_some() {
                         return Err(exc::TypeError::new(
-                            format!("Required argument ('{}') is keyword only argument", p.name)));
+                            format!("{} got multiple values for argument '{}'", fname, name)));
                     }
-                    *out = None;
+                    output[i] = Some(value);
+                    continue
                 }
-                else if i < nargs {
-                    *out = Some(args.get_item(i));
-                } else {
-                    *out = None;
-                    if !p.is_optional {
-                        return Err(exc::TypeError::new(
-                            format!("Required argument ('{}') (pos {}) not found", p.name, i+1)));
-                    }
+                Some(_) if !accept_kwargs => {
+                    posonly_names.push(name.into_owned());
+                    continue
+                }
+                None if !accept_kwargs => {
+                    return Err(exc::TypeError::new(
+                        format!("{} got an unexpected keyword argument '{}'", fname, name)));
                 }
+                _ => (),
             }
+            varkwargs.get_or_insert_with(|| PyDict::new(py)).set_item(key, value)?;
         }
     }
-    if !accept_kwargs && used_keywords != nkeywords {
-        // check for extraneous keyword arguments
-        for item in kwargs.unwrap().items().iter() {
-            let item = PyTuple::try_from(item)?;
-            let key = PyString::try_from(item.get_item(0))?.to_string()?;
-            if !params.iter().any(|p| p.name == key) {
-                return Err(exc::TypeError::new(
-                    format!("'{}' is an invalid keyword argument for this function", key)));
-            }
-        }
+    if !posonly_names.is_empty() {
+        return Err(exc::TypeError::new(
+            format!("{} got some positional-only arguments passed as keyword arguments: '{}'",
+                    fname, posonly_names.join(", "))));
+    }
+
+    // Check for missing required arguments
+    let missing: Vec<&ParamDescription> = params.iter().zip(output.iter())
+        .filter(|&(p, out)| out.is_none() && !p.is_optional)
+        .map(|(p, _)| p)
+        .collect();
+    let positional: Vec<&str> = missing.iter()
+        .filter(|p| !p.kw_only).map(|p| p.name).collect();
+    if !positional.is_empty() {
+        return Err(missing_arguments(fname, "positional", &positional));
+    }
+    let kw_only: Vec<&str> = missing.iter()
+        .filter(|p| p.kw_only).map(|p| p.name).collect();
+    if !kw_only.is_empty() {
+        return Err(missing_arguments(fname, "keyword-only", &kw_only));
     }
-    Ok(())
+
+    Ok((varargs, varkwargs))
+}
+
+fn too_many_positional(fname: &str, params: &[ParamDescription], nargs: usize) -> PyErr {
+    let max = params.iter().filter(|p| !p.kw_only).count();
+    let min = params.iter().filter(|p| !p.kw_only && !p.is_optional).count();
+    let expected = if min == max {
+        format!("{}", max)
+    } else {
+        format!("from {} to {}", min, max)
+    };
+    exc::TypeError::new(
+        format!("{} takes {} positional argument{} but {} {} given",
+                fname, expected,
+                if max == 1 { "" } else { "s" },
+                nargs,
+                if nargs == 1 { "was" } else { "were" }))
+}
+
+fn missing_arguments(fname: &str, kind: &str, names: &[&str]) -> PyErr {
+    let quoted: Vec<String> = names.iter().map(|name| format!("'{}'", name)).collect();
+    let list = match quoted.len() {
+        1 => quoted[0].clone(),
+        2 => format!("{} and {}", quoted[0], quoted[1]),
+        n => format!("{}, and {}", quoted[..n-1].join(", "), quoted[n-1]),
+    };
+    exc::TypeError::new(
+        format!("{} missing {} required {} argument{}: {}",
+                fname, names.len(), kind,
+                if names.len() == 1 { "" } else { "s" },
+                list))
 }
 
 #[inline]
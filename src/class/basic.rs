@@ -111,7 +111,7 @@ pub trait PyObjectRichcmpProtocol<'p>: PyObjectProtocol<'p> {
 #[doc(hidden)]
 pub trait PyObjectProtocolImpl {
     fn methods() -> Vec<PyMethodDef>;
-    fn tp_as_object(type_object: &mut ffi::PyTypeObject);
+    fn tp_as_object(type_object: &mut ffi::PyTypeTemplate);
     fn nb_bool_fn() -> Option<ffi::inquiry>;
 }
 
@@ -119,7 +119,7 @@ impl<T> PyObjectProtocolImpl for T {
     default fn methods() -> Vec<PyMethodDef> {
         Vec::new()
     }
-    default fn tp_as_object(_type_object: &mut ffi::PyTypeObject) {
+    default fn tp_as_object(_type_object: &mut ffi::PyTypeTemplate) {
     }
     default fn nb_bool_fn() -> Option<ffi::inquiry> {
         None
@@ -142,7 +142,7 @@ impl<'p, T> PyObjectProtocolImpl for T where T: PyObjectProtocol<'p> {
         }
         methods
     }
-    fn tp_as_object(type_object: &mut ffi::PyTypeObject) {
+    fn tp_as_object(type_object: &mut ffi::PyTypeTemplate) {
         type_object.tp_str = Self::tp_str();
         type_object.tp_repr = Self::tp_repr();
         type_object.tp_hash = Self::tp_hash();
@@ -27,16 +27,16 @@ pub trait PyGCClearProtocol<'p>: PyGCProtocol<'p> {}
 
 #[doc(hidden)]
 pub trait PyGCProtocolImpl {
-    fn update_type_object(type_object: &mut ffi::PyTypeObject);
+    fn update_type_object(type_object: &mut ffi::PyTypeTemplate);
 }
 
 impl<'p, T> PyGCProtocolImpl for T {
-    default fn update_type_object(_type_object: &mut ffi::PyTypeObject) {}
+    default fn update_type_object(_type_object: &mut ffi::PyTypeTemplate) {}
 }
 
 impl<'p, T> PyGCProtocolImpl for T where T: PyGCProtocol<'p>
 {
-    fn update_type_object(type_object: &mut ffi::PyTypeObject) {
+    fn update_type_object(type_object: &mut ffi::PyTypeTemplate) {
         type_object.tp_traverse = Self::tp_traverse();
         type_object.tp_clear = Self::tp_clear();
     }
@@ -39,17 +39,17 @@ pub trait PyIterNextProtocol<'p>: PyIterProtocol<'p> {
 
 #[doc(hidden)]
 pub trait PyIterProtocolImpl {
-    fn tp_as_iter(typeob: &mut ffi::PyTypeObject);
+    fn tp_as_iter(typeob: &mut ffi::PyTypeTemplate);
 }
 
 impl<T> PyIterProtocolImpl for T {
     #[inline]
-    default fn tp_as_iter(_: &mut ffi::PyTypeObject) {}
+    default fn tp_as_iter(_: &mut ffi::PyTypeTemplate) {}
 }
 
 impl<'p, T> PyIterProtocolImpl for T where T: PyIterProtocol<'p> {
     #[inline]
-    fn tp_as_iter(typeob: &mut ffi::PyTypeObject) {
+    fn tp_as_iter(typeob: &mut ffi::PyTypeTemplate) {
         typeob.tp_iter = Self::tp_iter();
         typeob.tp_iternext = Self::tp_iternext();
     }
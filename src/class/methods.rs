@@ -4,6 +4,9 @@ use std;
 use std::ffi::CString;
 
 use ffi;
+use err::PyResult;
+use object::PyObject;
+use python::Python;
 
 static NO_PY_METHODS: &'static [PyMethodDefType] = &[];
 
@@ -145,3 +148,15 @@ impl<T> PyPropMethodsProtocolImpl for T {
         NO_PY_METHODS
     }
 }
+
+#[doc(hidden)]
+pub trait PyClassAttrsProtocolImpl {
+    /// Class attributes that are added to the type dict once the type object is ready.
+    fn class_attrs(py: Python) -> PyResult<Vec<(&'static str, PyObject)>>;
+}
+
+impl<T> PyClassAttrsProtocolImpl for T {
+    default fn class_attrs(_py: Python) -> PyResult<Vec<(&'static str, PyObject)>> {
+        Ok(Vec::new())
+    }
+}
@@ -6,6 +6,7 @@
 
 pub mod async;
 pub mod basic;
+#[cfg(not(Py_LIMITED_API))]
 pub mod buffer;
 pub mod context;
 pub mod descr;
@@ -19,6 +20,7 @@ pub mod sequence;
 pub use self::basic::PyObjectProtocol;
 pub use self::async::PyAsyncProtocol;
 pub use self::iter::PyIterProtocol;
+#[cfg(not(Py_LIMITED_API))]
 pub use self::buffer::PyBufferProtocol;
 pub use self::context::PyContextProtocol;
 pub use self::descr::PyDescrProtocol;
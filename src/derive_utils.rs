@@ -0,0 +1,362 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Functions used by the code generated by `#[derive(FromPyObject)]`,
+//! `#[derive(ToPyObject)]`, `#[derive(IntoPyObject)]` and `#[py::exception]`.
+//!
+//! Extraction errors raised while extracting a field are replaced by a `TypeError`
+//! naming the path of the failing field, e.g. ``failed to extract `Config.servers[2].port`: ...``,
+//! with the original error as its `__cause__`. The path is kept in an attribute of the
+//! `TypeError`, so nested derived types and sequences prepend their own segment and the
+//! outermost extraction reports the complete path. Other errors, i.e. `MemoryError`
+//! or `KeyboardInterrupt`, are passed on unchanged.
+
+use std::fmt::Display;
+
+use ffi;
+use pythonrun;
+use err::{self, PyErr, PyResult};
+use object::PyObject;
+use python::Python;
+use conversion::{FromPyObject, PyTryFrom, ToPyObject, IntoPyObject};
+use noargs::NoArgs;
+use instance::{AsPyRef, PyObjectWithToken};
+use objectprotocol::ObjectProtocol;
+use typeob::PyTypeObject;
+use objects::{PyObjectRef, PySequence, PyDict, PyTuple, PyType, exc};
+
+/// Attribute of the `TypeError` raised by derived code holding the path of the failing field
+/// below the outermost type and the reason, i.e. `(".servers[2].port", "invalid literal")`.
+const PATH_ATTR: &'static str = "__pyo3_path__";
+
+/// Checks whether `err` is raised by a failed extraction and gets a path.
+fn is_extraction_error(py: Python, err: &PyErr) -> bool {
+    err.is_instance::<exc::TypeError>(py) ||
+        err.is_instance::<exc::ValueError>(py) ||
+        err.is_instance::<exc::OverflowError>(py) ||
+        err.is_instance::<exc::LookupError>(py) ||
+        err.is_instance::<exc::AttributeError>(py)
+}
+
+/// Returns the path and reason carried by an error raised by derived code.
+fn carried_path(py: Python, err: &PyErr) -> Option<(String, String)> {
+    if !err.is_instance::<exc::TypeError>(py) {
+        return None
+    }
+    let value = err.clone_ref(py).into_object(py);
+    value.getattr(py, PATH_ATTR).and_then(|path| path.extract(py)).ok()
+}
+
+/// Returns the message of the exception instance of `err`.
+///
+/// The single string argument is used if there is one, `str()` of `KeyError`
+/// would wrap it in quotes.
+fn message(py: Python, err: &PyErr) -> String {
+    let value = err.clone_ref(py).into_object(py);
+    let value = value.as_ref(py);
+    if let Ok(args) = value.getattr("args") {
+        if let Ok((msg,)) = args.extract::<(String,)>() {
+            return msg
+        }
+    }
+    match value.str() {
+        Ok(s) => s.to_string_lossy().into_owned(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Creates the `TypeError` for a failed extraction of the field `path` of `owner`.
+fn new_path_error(py: Python, owner: &str, path: String, reason: String) -> PyErr {
+    let value = exc::TypeError::new(format!("failed to extract `{}{}`: {}", owner, path, reason))
+        .into_object(py);
+    match value.as_ref(py).setattr(PATH_ATTR, (path, reason)) {
+        Ok(()) => PyErr::from_instance(value.as_ref(py)),
+        Err(err) => err,
+    }
+}
+
+/// Creates the `TypeError` raised for a failed extraction, `err` is the error it replaces.
+///
+/// Errors created by this module pass on their `__cause__`, other errors become the cause,
+/// so the cause is always the error raised by the extraction of the innermost field.
+#[cfg(Py_3)]
+fn path_error(py: Python, owner: &str, path: String, reason: String,
+              err: PyErr, has_path: bool) -> PyErr {
+    let cause = if has_path { err.cause(py) } else { Some(err) };
+    let path_err = new_path_error(py, owner, path, reason);
+    match cause {
+        Some(cause) => err::chain_cause(py, path_err, cause),
+        None => path_err,
+    }
+}
+
+#[cfg(not(Py_3))]
+fn path_error(py: Python, owner: &str, path: String, reason: String,
+              _err: PyErr, _has_path: bool) -> PyErr {
+    new_path_error(py, owner, path, reason)
+}
+
+/// Prefixes the path carried by `err` with `owner` followed by `segment`.
+///
+/// Extraction errors that do not carry a path yet get `owner` + `segment` as their path,
+/// other errors are returned unchanged.
+pub fn with_path(py: Python, err: PyErr, owner: &str, segment: &str) -> PyErr {
+    if let Some((path, reason)) = carried_path(py, &err) {
+        return path_error(py, owner, format!("{}{}", segment, path), reason, err, true)
+    }
+    if !is_extraction_error(py, &err) {
+        return err
+    }
+    let reason = message(py, &err);
+    path_error(py, owner, segment.to_owned(), reason, err, false)
+}
+
+/// Prefixes the path carried by `err` with the sequence index `index`.
+///
+/// Errors without a path are returned unchanged.
+pub fn with_index(py: Python, err: PyErr, index: usize) -> PyErr {
+    match carried_path(py, &err) {
+        Some((path, reason)) =>
+            path_error(py, "", format!("[{}]{}", index, path), reason, err, true),
+        None => err,
+    }
+}
+
+/// Extracts the attribute `name` of `obj`.
+pub fn extract_attr<'s, T>(obj: &'s PyObjectRef, owner: &str, name: &str) -> PyResult<T>
+    where T: FromPyObject<'s>
+{
+    obj.getattr(name)
+        .and_then(|value| value.extract())
+        .map_err(|err| with_path(obj.py(), err, owner, &format!(".{}", name)))
+}
+
+/// Extracts the item `key` of `obj`.
+pub fn extract_item<'s, T>(obj: &'s PyObjectRef, owner: &str, key: &str) -> PyResult<T>
+    where T: FromPyObject<'s>
+{
+    obj.get_item(key)
+        .and_then(|value| value.extract())
+        .map_err(|err| with_path(obj.py(), err, owner, &format!(".{}", key)))
+}
+
+/// Extracts `obj` as the only field of a newtype struct or variant.
+pub fn extract_newtype<'s, T>(obj: &'s PyObjectRef, owner: &str) -> PyResult<T>
+    where T: FromPyObject<'s>
+{
+    obj.extract().map_err(|err| with_path(obj.py(), err, owner, ""))
+}
+
+/// Casts `obj` to a sequence of exactly `len` items.
+pub fn sequence<'s>(obj: &'s PyObjectRef, owner: &str, len: usize) -> PyResult<&'s PySequence> {
+    let seq = PySequence::try_from(obj)
+        .map_err(|_| with_path(obj.py(), exc::TypeError::new("expected a sequence"), owner, ""))?;
+    let actual = seq.len()
+        .map_err(|err| with_path(obj.py(), err, owner, ""))?;
+    if actual as usize != len {
+        return Err(with_path(obj.py(), exc::ValueError::new(
+            format!("expected a sequence of length {}, got {}", len, actual)), owner, ""))
+    }
+    Ok(seq)
+}
+
+/// Extracts the item at `index` of `seq`.
+pub fn extract_element<'s, T>(seq: &'s PySequence, owner: &str, index: usize) -> PyResult<T>
+    where T: FromPyObject<'s>
+{
+    seq.get_item(index as isize)
+        .and_then(|value| value.extract())
+        .map_err(|err| with_path(seq.py(), err, owner, &format!("[{}]", index)))
+}
+
+/// Creates the error raised when no variant of the enum `owner` could be extracted.
+///
+/// An error that is not raised by a failed extraction is returned unchanged.
+pub fn no_matching_variant(py: Python, owner: &str, errors: Vec<(&str, PyErr)>) -> PyErr {
+    if let Some(idx) = errors.iter().position(|&(_, ref err)| !is_extraction_error(py, err)) {
+        return errors.into_iter().nth(idx).unwrap().1
+    }
+    let reasons: Vec<String> = errors.iter()
+        .map(|&(variant, ref err)| format!("{}: {}", variant, message(py, err)))
+        .collect();
+
+    new_path_error(py, owner, String::new(),
+                   format!("no variant matched ({})", reasons.join("; ")))
+}
+
+/// Creates a `dict` from the converted fields of a struct.
+pub fn into_dict(py: Python, items: Vec<(&str, PyObject)>) -> PyObject {
+    let dict = PyDict::new(py);
+    for (key, value) in items {
+        dict.set_item(key, value).expect("Failed to set dict item");
+    }
+    dict.into()
+}
+
+/// Creates a `types.SimpleNamespace` from the converted fields of a struct.
+///
+/// `types.SimpleNamespace` was added in Python 3.3, `#[pyo3(namespace)]` is not available
+/// on Python 2.
+#[cfg(Py_3)]
+pub fn into_namespace(py: Python, items: Vec<(&str, PyObject)>) -> PyObject {
+    let kwargs = PyDict::new(py);
+    for (key, value) in items {
+        kwargs.set_item(key, value).expect("Failed to set dict item");
+    }
+    py.import("types")
+        .and_then(|types| types.call("SimpleNamespace", NoArgs, kwargs))
+        .expect("Failed to create types.SimpleNamespace")
+        .into()
+}
+
+/// Creates an instance of the `collections.namedtuple` type `name`
+/// from the converted fields of a struct.
+///
+/// The type is created on first use in each interpreter and shared by all conversions
+/// of the Rust type identified by `key`. The field names are checked by the derive macro,
+/// `collections.namedtuple` only fails on invalid names.
+pub fn into_namedtuple(py: Python, key: &'static str,
+                       name: &str, items: Vec<(&str, PyObject)>) -> PyObject {
+    let types = unsafe { pythonrun::interpreter_cache() };
+    let ty = types.entry(key).or_insert_with(|| {
+        let fields: Vec<&str> = items.iter().map(|&(field, _)| field).collect();
+        py.import("collections")
+            .and_then(|collections| {
+                collections.call("namedtuple", (name, fields.join(" ")), NoArgs)
+            })
+            .expect("Failed to create namedtuple type")
+            .to_object(py)
+    }).clone_ref(py);
+
+    let values: Vec<PyObject> = items.into_iter().map(|(_, value)| value).collect();
+    ty.call(py, PyTuple::new(py, &values), NoArgs)
+        .expect("Failed to create namedtuple instance")
+}
+
+/// Creates the exception type `name` declared with `#[py::exception]`.
+///
+/// `name` must be qualified with the module name, i.e. `mymodule.MyError`.
+pub fn new_exception_type(py: Python, name: &str, base: &PyType, doc: &str)
+                          -> *mut ffi::PyTypeObject {
+    let dict = if doc.is_empty() {
+        None
+    } else {
+        let dict = PyDict::new(py);
+        dict.set_item("__doc__", doc).expect("Failed to set __doc__");
+        Some(dict.into())
+    };
+    let ty = PyErr::new_type(py, name, Some(base), dict);
+    if ty.is_null() {
+        PyErr::fetch(py).print(py);
+        panic!("Failed to create exception type {}", name);
+    }
+    ty
+}
+
+/// Message passed to the constructor of an exception declared with `#[py::exception]`.
+///
+/// Types implementing `Display` use their formatted value,
+/// other types create the exception without arguments.
+pub trait ExceptionMessage {
+    fn exception_message(&self) -> Option<String>;
+}
+
+impl<T> ExceptionMessage for T {
+    default fn exception_message(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T> ExceptionMessage for T where T: Display {
+    fn exception_message(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// Creates an instance of the exception type `T` and sets `fields` as its attributes.
+pub fn new_exception<T>(py: Python, message: Option<String>, fields: Vec<(&str, PyObject)>)
+                        -> PyErr where T: PyTypeObject
+{
+    let ty = py.get_type::<T>();
+    let value = match message {
+        Some(message) => ty.call1((message,)),
+        None => ty.call0(),
+    };
+    let value = match value {
+        Ok(value) => value,
+        Err(err) => return err,
+    };
+    for (name, field) in fields {
+        if let Err(err) = value.setattr(name, field) {
+            return err
+        }
+    }
+    PyErr::from_instance(value)
+}
+
+#[cfg(test)]
+mod test {
+    use python::Python;
+    use conversion::IntoPyObject;
+    use instance::AsPyRef;
+    use objects::{PyDict, exc};
+    use objectprotocol::ObjectProtocol;
+    use noargs::NoArgs;
+    use super::{extract_attr, extract_item, with_path, with_index};
+
+    #[test]
+    fn test_error_path() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let d = PyDict::new(py);
+        d.set_item("port", "x").unwrap();
+        let err = extract_item::<i32>(d.as_ref(), "Server", "port").unwrap_err();
+        let err = with_path(py, err, "Config", ".servers[2]");
+        assert!(err.is_instance::<exc::TypeError>(py));
+        let msg = err.into_object(py).as_ref(py).str().unwrap().to_string_lossy().into_owned();
+        assert!(msg.starts_with("failed to extract `Config.servers[2].port`: "), msg);
+
+        let err = extract_attr::<i32>(d.as_ref(), "Config", "port").unwrap_err();
+        assert!(err.is_instance::<exc::TypeError>(py));
+        let msg = err.clone_ref(py).into_object(py).as_ref(py).str().unwrap()
+            .to_string_lossy().into_owned();
+        assert!(msg.starts_with("failed to extract `Config.port`: "), msg);
+        #[cfg(Py_3)]
+        assert!(err.cause(py).unwrap().is_instance::<exc::AttributeError>(py));
+    }
+
+    #[test]
+    #[cfg(Py_3)]
+    fn test_error_path_cause() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let d = PyDict::new(py);
+        let err = extract_item::<i32>(d.as_ref(), "Server", "port").unwrap_err();
+        let err = with_path(py, err, "Config", ".servers[2]");
+        assert!(err.is_instance::<exc::TypeError>(py));
+        let msg = err.clone_ref(py).into_object(py).as_ref(py).str().unwrap()
+            .to_string_lossy().into_owned();
+        assert_eq!(msg, "failed to extract `Config.servers[2].port`: port");
+        assert!(err.cause(py).unwrap().is_instance::<exc::KeyError>(py));
+    }
+
+    #[test]
+    fn test_error_path_only_for_extraction_errors() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        // the path is not parsed from the message
+        let err = exc::ValueError::new("failed to extract `Other.x`: boom");
+        let err = with_path(py, err, "Config", ".port");
+        assert!(err.is_instance::<exc::TypeError>(py));
+        let msg = err.into_object(py).as_ref(py).str().unwrap().to_string_lossy().into_owned();
+        assert_eq!(msg, "failed to extract `Config.port`: failed to extract `Other.x`: boom");
+
+        let err = with_path(py, exc::MemoryError::new(NoArgs), "Config", ".port");
+        assert!(err.is_instance::<exc::MemoryError>(py));
+        let err = with_index(py, exc::TypeError::new("not a path"), 2);
+        let msg = err.into_object(py).as_ref(py).str().unwrap().to_string_lossy().into_owned();
+        assert_eq!(msg, "not a path");
+    }
+}
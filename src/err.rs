@@ -1,18 +1,22 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 use std;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::cell::UnsafeCell;
 use std::ffi::CString;
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_int};
 use std::error::Error;
 use libc;
 
 use ffi;
 use python::{ToPyPointer, IntoPyPointer, Python};
 use object::PyObject;
-use objects::{PyObjectRef, PyType, exc};
-use instance::Py;
+use objects::{PyObjectRef, PyType, PyTraceback, exc};
+use instance::{Py, AsPyRef};
+use objectprotocol::ObjectProtocol;
 use typeob::PyTypeObject;
 use conversion::{ToPyObject, IntoPyObject, ToBorrowedObject};
+use pythonrun;
 
 /// Defines a new exception type.
 ///
@@ -129,6 +133,14 @@ pub struct PyErr {
 
     /// The `PyTraceBack` object associated with the error.
     pub ptraceback: Option<PyObject>,
+
+    /// The `__cause__` of the exception instance for `Error::source()`,
+    /// `None` until the first call reads it.
+    pcause: UnsafeCell<Option<Option<Box<PyErr>>>>,
+
+    /// The exception instances this error is the (transitive) `__cause__` of,
+    /// `source()` stops when the chain loops back to one of them.
+    pchain: Vec<*mut ffi::PyObject>,
 }
 
 /// Represents the result of a Python call.
@@ -165,6 +177,8 @@ impl PyErr {
             ptype: ty,
             pvalue: PyErrValue::ToObject(Box::new(value)),
             ptraceback: None,
+            pcause: UnsafeCell::new(None),
+            pchain: Vec::new(),
         }
     }
 
@@ -179,6 +193,8 @@ impl PyErr {
             ptype: exc,
             pvalue: PyErrValue::ToObject(Box::new(args)),
             ptraceback: None,
+            pcause: UnsafeCell::new(None),
+            pchain: Vec::new(),
         }
     }
 
@@ -193,6 +209,8 @@ impl PyErr {
             ptype: ty,
             pvalue: value,
             ptraceback: None,
+            pcause: UnsafeCell::new(None),
+            pchain: Vec::new(),
         }
     }
 
@@ -210,12 +228,16 @@ impl PyErr {
                 ptype: unsafe { Py::from_borrowed_ptr( ffi::PyExceptionInstance_Class(ptr)) },
                 pvalue: PyErrValue::Value(obj.into()),
                 ptraceback: None,
+                pcause: UnsafeCell::new(None),
+                pchain: Vec::new(),
             }
         } else if unsafe { ffi::PyExceptionClass_Check(obj.as_ptr()) } != 0 {
             PyErr {
                 ptype: unsafe { Py::from_borrowed_ptr(ptr) },
                 pvalue: PyErrValue::None,
                 ptraceback: None,
+                pcause: UnsafeCell::new(None),
+                pchain: Vec::new(),
             }
         } else {
             PyErr {
@@ -223,10 +245,28 @@ impl PyErr {
                 pvalue: PyErrValue::ToObject(
                     Box::new("exceptions must derive from BaseException")),
                 ptraceback: None,
+                pcause: UnsafeCell::new(None),
+                pchain: Vec::new(),
             }
         }
     }
 
+    /// Creates an `OSError` from `err`, raised while accessing the file `filename`.
+    ///
+    /// On python 3 the exception type is the `OSError` subclass matching the raw
+    /// OS error code or the `io::ErrorKind` of `err`, e.g. `FileNotFoundError`.
+    /// The `errno`, `strerror` and `filename` attributes of the exception are set.
+    pub fn from_io_error(err: io::Error, filename: Option<&Path>) -> PyErr {
+        PyErr {
+            ptype: io_error_type(&err),
+            pvalue: PyErrValue::ToArgs(Box::new(IoErrorArguments {
+                err: err, filename: filename.map(|f| f.to_owned())})),
+            ptraceback: None,
+            pcause: UnsafeCell::new(None),
+            pchain: Vec::new(),
+        }
+    }
+
     /// Gets whether an error is present in the Python interpreter's global state.
     #[inline]
     pub fn occurred(_: Python) -> bool {
@@ -278,9 +318,8 @@ impl PyErr {
         // Note: must not panic to ensure all owned pointers get acquired correctly,
         // and because we mustn't panic in normalize().
 
-        let pvalue = if let Some(obj) =
-            PyObject::from_owned_ptr_or_opt(Python::assume_gil_acquired(), pvalue)
-        {
+        let py = Python::assume_gil_acquired();
+        let pvalue = if let Some(obj) = PyObject::from_owned_ptr_or_opt(py, pvalue) {
             PyErrValue::Value(obj)
         } else {
             PyErrValue::None
@@ -293,7 +332,9 @@ impl PyErr {
                 Py::from_owned_ptr(ptype)
             },
             pvalue: pvalue,
-            ptraceback: PyObject::from_owned_ptr_or_opt(Python::assume_gil_acquired(), ptraceback),
+            ptraceback: PyObject::from_owned_ptr_or_opt(py, ptraceback),
+            pcause: UnsafeCell::new(None),
+            pchain: Vec::new(),
         }
     }
 
@@ -344,7 +385,7 @@ impl PyErr {
     /// Helper function for normalizing the error by deconstructing and reconstructing the PyErr.
     /// Must not panic for safety in normalize()
     fn into_normalized(self, py: Python) -> PyErr {
-        let PyErr { ptype, pvalue, ptraceback } = self;
+        let PyErr { ptype, pvalue, ptraceback, .. } = self;
 
         let mut pvalue = match pvalue {
             PyErrValue::None => std::ptr::null_mut(),
@@ -376,7 +417,7 @@ impl PyErr {
     /// This is the opposite of `PyErr::fetch()`.
     #[inline]
     pub fn restore(self, py: Python) {
-        let PyErr { ptype, pvalue, ptraceback } = self;
+        let PyErr { ptype, pvalue, ptraceback, .. } = self;
 
         let pvalue = match pvalue {
             PyErrValue::None => std::ptr::null_mut(),
@@ -389,6 +430,102 @@ impl PyErr {
         }
     }
 
+    /// Returns the traceback of this error, if there is one.
+    pub fn traceback<'p>(&self, py: Python<'p>) -> Option<&'p PyTraceback> {
+        self.ptraceback.as_ref().map(|tb| unsafe {
+            py.from_owned_ptr(tb.clone_ref(py).into_ptr())
+        })
+    }
+
+    /// Sets `cause` as the `__cause__` of this error, like `raise self from cause`.
+    #[cfg(Py_3)]
+    pub fn with_cause(mut self, py: Python, mut cause: PyErr) -> PyErr {
+        cause.normalize(py);
+        self.normalize(py);
+        if let (&PyErrValue::Value(ref value), &PyErrValue::Value(ref cause_value)) =
+            (&self.pvalue, &cause.pvalue)
+        {
+            unsafe {
+                if let Some(ref tb) = cause.ptraceback {
+                    ffi::PyException_SetTraceback(cause_value.as_ptr(), tb.as_ptr());
+                }
+                // steals the reference to the cause
+                ffi::PyException_SetCause(value.as_ptr(), cause_value.clone_ref(py).into_ptr());
+            }
+        }
+        if let PyErrValue::Value(ref value) = self.pvalue {
+            cause.pchain = self.pchain.clone();
+            cause.pchain.push(value.as_ptr());
+        }
+        self.pcause = UnsafeCell::new(Some(Some(Box::new(cause))));
+        self
+    }
+
+    /// Returns the `__cause__` of this error, set by `raise ... from cause`.
+    #[cfg(Py_3)]
+    pub fn cause(&self, py: Python) -> Option<PyErr> {
+        let value = self.clone_ref(py).instance(py);
+        unsafe {
+            PyErr::from_chained(py, ffi::PyException_GetCause(value.as_ptr()))
+        }
+    }
+
+    /// Returns the `__context__` of this error, the exception that was being handled
+    /// when this error was raised.
+    #[cfg(Py_3)]
+    pub fn context(&self, py: Python) -> Option<PyErr> {
+        let value = self.clone_ref(py).instance(py);
+        unsafe {
+            PyErr::from_chained(py, ffi::PyException_GetContext(value.as_ptr()))
+        }
+    }
+
+    /// Creates a `PyErr` from an owned pointer to a chained exception instance,
+    /// which may be null.
+    #[cfg(Py_3)]
+    unsafe fn from_chained(py: Python, ptr: *mut ffi::PyObject) -> Option<PyErr> {
+        PyObject::from_owned_ptr_or_opt(py, ptr).map(|value| {
+            let mut err = PyErr::from_instance(value.as_ref(py));
+            err.ptraceback = PyObject::from_owned_ptr_or_opt(
+                py, ffi::PyException_GetTraceback(value.as_ptr()));
+            err
+        })
+    }
+
+    /// Reads the `__cause__` of the exception instance for `Error::source()`.
+    ///
+    /// Returns `None` if the chain loops back to this error or one it is the cause of.
+    #[cfg(Py_3)]
+    fn source_cause(&self, py: Python) -> Option<Box<PyErr>> {
+        let value = match self.pvalue {
+            PyErrValue::Value(ref value) => value.as_ptr(),
+            _ => return None,
+        };
+        unsafe {
+            if ffi::PyExceptionInstance_Check(value) == 0 {
+                return None
+            }
+            let mut cause = match PyErr::from_chained(py, ffi::PyException_GetCause(value)) {
+                Some(cause) => cause,
+                None => return None,
+            };
+            if let PyErrValue::Value(ref cause_value) = cause.pvalue {
+                let ptr = cause_value.as_ptr();
+                if ptr == value || self.pchain.contains(&ptr) {
+                    return None
+                }
+            }
+            cause.pchain = self.pchain.clone();
+            cause.pchain.push(value);
+            Some(Box::new(cause))
+        }
+    }
+
+    #[cfg(not(Py_3))]
+    fn source_cause(&self, _py: Python) -> Option<Box<PyErr>> {
+        None
+    }
+
     /// Issue a warning message.
     /// May return a PyErr if warnings-as-errors is enabled.
     pub fn warn(py: Python, category: &PyObjectRef, message: &str, stacklevel: i32) -> PyResult<()> {
@@ -399,6 +536,39 @@ impl PyErr {
         }
     }
 
+    /// Issue a warning message of category `T`, e.g. `exc::DeprecationWarning`.
+    /// May return a PyErr if warnings-as-errors is enabled.
+    pub fn warn_typed<T>(py: Python, message: &str, stacklevel: i32) -> PyResult<()>
+        where T: PyTypeObject
+    {
+        PyErr::warn(py, py.get_type::<T>().as_ref(), message, stacklevel)
+    }
+
+    /// Issue a warning message with explicit control over
+    /// the reported location, like `warnings.warn_explicit`.
+    ///
+    /// `module` defaults to `filename` stripped of its `.py` extension.
+    /// May return a PyErr if warnings-as-errors is enabled.
+    pub fn warn_explicit(py: Python, category: &PyObjectRef, message: &str, filename: &str,
+                         lineno: u32, module: Option<&str>) -> PyResult<()>
+    {
+        let message = CString::new(message)?;
+        let filename = CString::new(filename)?;
+        let module = match module {
+            Some(module) => Some(CString::new(module)?),
+            None => None,
+        };
+        let module_ptr = match module {
+            Some(ref module) => module.as_ptr(),
+            None => std::ptr::null(),
+        };
+        unsafe {
+            error_on_minusone(py, ffi::PyErr_WarnExplicit(
+                category.as_ptr(), message.as_ptr(), filename.as_ptr(),
+                lineno as c_int, module_ptr, std::ptr::null_mut()))
+        }
+    }
+
     pub fn clone_ref(&self, py: Python) -> PyErr {
         let v = match self.pvalue {
             PyErrValue::None => PyErrValue::None,
@@ -412,13 +582,119 @@ impl PyErr {
             ptype: self.ptype.clone_ref(py),
             pvalue: v,
             ptraceback: t,
+            pcause: UnsafeCell::new(None),
+            pchain: self.pchain.clone(),
         }
     }
 }
 
+/// Shows the type, the `repr()` of the value and the traceback of the error.
+///
+/// The GIL is not acquired, if the current thread does not hold it
+/// only the type is shown.
 impl std::fmt::Debug for PyErr {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        f.write_str(format!("PyErr {{ type: {:?} }}", self.ptype).as_str())
+        if !pythonrun::gil_is_acquired() {
+            return f.debug_struct("PyErr")
+                .field("type", &format_args!("{}", type_name_without_gil(&self.ptype)))
+                .finish()
+        }
+        let py = unsafe { Python::assume_gil_acquired() };
+
+        let value = self.clone_ref(py).instance(py);
+        let traceback = self.traceback(py).map(|tb| match tb.format() {
+            Ok(s) => s,
+            Err(_) => "<unformattable traceback>".to_owned(),
+        });
+        f.debug_struct("PyErr")
+            .field("type", &format_args!("{}", repr(self.ptype.as_ref(py))))
+            .field("value", &format_args!("{}", repr(value.as_ref(py))))
+            .field("traceback", &traceback)
+            .finish()
+    }
+}
+
+/// Renders the error as `TypeName: message`, like the last line of a Python traceback.
+///
+/// The GIL is not acquired, if the current thread does not hold it
+/// only `Python exception` is shown.
+impl std::fmt::Display for PyErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        if !pythonrun::gil_is_acquired() {
+            return f.write_str("Python exception")
+        }
+        let py = unsafe { Python::assume_gil_acquired() };
+
+        let value = self.clone_ref(py).instance(py);
+        let value = value.as_ref(py);
+        let type_name = exception_type_name(value.get_type());
+        match value.str() {
+            Ok(msg) => {
+                let msg = msg.to_string_lossy();
+                if msg.is_empty() {
+                    f.write_str(&type_name)
+                } else {
+                    write!(f, "{}: {}", type_name, msg)
+                }
+            }
+            Err(_) => write!(f, "{}: <exception str() failed>", type_name),
+        }
+    }
+}
+
+/// `source()` follows the `__cause__` of the exception.
+///
+/// The cause is read on the first call, which needs the GIL:
+/// without it `source()` returns `None` until a call made while holding the GIL.
+impl Error for PyErr {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        unsafe {
+            // the cache is written once, references handed out point into its box
+            if (*self.pcause.get()).is_none() && pythonrun::gil_is_acquired() {
+                let cause = self.source_cause(Python::assume_gil_acquired());
+                *self.pcause.get() = Some(cause);
+            }
+            match *self.pcause.get() {
+                Some(Some(ref cause)) => Some(&**cause as &(Error + 'static)),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Name of an exception type from its `tp_name`, which can be read without the GIL.
+#[cfg(not(Py_LIMITED_API))]
+fn type_name_without_gil(ty: &Py<PyType>) -> String {
+    unsafe {
+        let ty = ty.as_ptr() as *mut ffi::PyTypeObject;
+        std::ffi::CStr::from_ptr((*ty).tp_name).to_string_lossy().into_owned()
+    }
+}
+
+/// `tp_name` is not accessible with `Py_LIMITED_API`, only the pointer is shown.
+#[cfg(Py_LIMITED_API)]
+fn type_name_without_gil(ty: &Py<PyType>) -> String {
+    format!("{:?}", ty.as_ptr())
+}
+
+/// Name of an exception type as shown by tracebacks,
+/// qualified with the module name unless it is a builtin.
+fn exception_type_name(ty: &PyType) -> String {
+    let name = ty.getattr("__qualname__")
+        .or_else(|_| ty.getattr("__name__"))
+        .and_then(|name| name.extract::<String>())
+        .unwrap_or_else(|_| ty.name().into_owned());
+    match ty.getattr("__module__").and_then(|module| module.extract::<String>()) {
+        Ok(ref module) if module != "builtins" && module != "exceptions" && module != "__main__" =>
+            format!("{}.{}", module, name),
+        _ => name,
+    }
+}
+
+fn repr<T: ObjectProtocol>(obj: &T) -> String {
+    match obj.repr() {
+        Ok(s) => s.to_string_lossy().into_owned(),
+        Err(_) => "<unrepresentable object>".to_owned(),
     }
 }
 
@@ -489,52 +765,130 @@ macro_rules! impl_to_pyerr {
     }
 }
 
-#[cfg(Py_3)]
 /// Create `OSError` from `io::Error`
+///
+/// See `PyErr::from_io_error()`.
 impl std::convert::From<io::Error> for PyErr {
     fn from(err: io::Error) -> PyErr {
-        match err.kind() {
-            io::ErrorKind::BrokenPipe =>
-                PyErr::from_value::<exc::BrokenPipeError>(PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::ConnectionRefused =>
-                PyErr::from_value::<exc::ConnectionRefusedError>(
-                    PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::ConnectionAborted =>
-                PyErr::from_value::<exc::ConnectionAbortedError>(
-                    PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::ConnectionReset =>
-                PyErr::from_value::<exc::ConnectionResetError>(
-                    PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::Interrupted =>
-                PyErr::from_value::<exc::InterruptedError>(PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::NotFound =>
-                PyErr::from_value::<exc::FileNotFoundError>(PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::WouldBlock =>
-                PyErr::from_value::<exc::BlockingIOError>(PyErrValue::ToArgs(Box::new(err))),
-            io::ErrorKind::TimedOut =>
-                PyErr::from_value::<exc::TimeoutError>(PyErrValue::ToArgs(Box::new(err))),
-            _ =>
-                PyErr::from_value::<exc::OSError>(PyErrValue::ToArgs(Box::new(err))),
-        }
+        PyErr::from_io_error(err, None)
     }
 }
 
-#[cfg(not(Py_3))]
-/// Create `OSError` from `io::Error`
-impl std::convert::From<io::Error> for PyErr {
-    fn from(err: io::Error) -> PyErr {
-        PyErr::from_value::<exc::OSError>(PyErrValue::ToArgs(Box::new(err)))
+/// Extract `errno` and `strerror` from from `io::Error`
+impl PyErrArguments for io::Error {
+    fn arguments(&self, py: Python) -> PyObject {
+        io_error_arguments(py, self, None)
     }
 }
 
+struct IoErrorArguments {
+    err: io::Error,
+    filename: Option<PathBuf>,
+}
 
-/// Extract `errno` and `errdesc` from from `io::Error`
-impl PyErrArguments for io::Error {
+impl PyErrArguments for IoErrorArguments {
     fn arguments(&self, py: Python) -> PyObject {
-        (self.raw_os_error().unwrap_or(0), self.description()).to_object(py)
+        io_error_arguments(py, &self.err, self.filename.as_ref().map(|f| f.as_path()))
     }
 }
 
+/// Arguments for the `OSError` constructor, `(errno, strerror[, filename])`.
+///
+/// Errors without an OS error code use their message as the only argument,
+/// unless there is a filename.
+fn io_error_arguments(py: Python, err: &io::Error, filename: Option<&Path>) -> PyObject {
+    let msg = err.to_string();
+    match (err.raw_os_error(), filename) {
+        (Some(code), filename) => {
+            // `Display` of `io::Error` appends the error code to the OS message
+            let suffix = format!(" (os error {})", code);
+            let strerror = if msg.ends_with(&suffix) {
+                &msg[..msg.len() - suffix.len()]
+            } else {
+                &msg[..]
+            };
+            match filename {
+                Some(filename) => (code, strerror, filename_object(py, filename)).to_object(py),
+                None => (code, strerror).to_object(py),
+            }
+        }
+        (None, Some(filename)) => (py.None(), msg, filename_object(py, filename)).to_object(py),
+        (None, None) => msg.to_object(py),
+    }
+}
+
+#[cfg(all(Py_3, unix))]
+fn filename_object(py: Python, filename: &Path) -> PyObject {
+    use std::os::unix::ffi::OsStrExt;
+
+    // decode like `os.fsdecode()`, non utf-8 file names survive a round trip
+    let bytes = filename.as_os_str().as_bytes();
+    unsafe {
+        PyObject::from_owned_ptr_or_panic(py, ffi::PyUnicode_DecodeFSDefaultAndSize(
+            bytes.as_ptr() as *const c_char, bytes.len() as ffi::Py_ssize_t))
+    }
+}
+
+#[cfg(not(all(Py_3, unix)))]
+fn filename_object(py: Python, filename: &Path) -> PyObject {
+    filename.to_string_lossy().to_object(py)
+}
+
+#[cfg(Py_3)]
+fn io_error_type(err: &io::Error) -> Py<PyType> {
+    if let Some(ty) = err.raw_os_error().and_then(os_error_type) {
+        return ty
+    }
+    match err.kind() {
+        io::ErrorKind::BrokenPipe => exc::BrokenPipeError::type_object(),
+        io::ErrorKind::ConnectionRefused => exc::ConnectionRefusedError::type_object(),
+        io::ErrorKind::ConnectionAborted => exc::ConnectionAbortedError::type_object(),
+        io::ErrorKind::ConnectionReset => exc::ConnectionResetError::type_object(),
+        io::ErrorKind::Interrupted => exc::InterruptedError::type_object(),
+        io::ErrorKind::NotFound => exc::FileNotFoundError::type_object(),
+        io::ErrorKind::PermissionDenied => exc::PermissionError::type_object(),
+        io::ErrorKind::AlreadyExists => exc::FileExistsError::type_object(),
+        io::ErrorKind::WouldBlock => exc::BlockingIOError::type_object(),
+        io::ErrorKind::TimedOut => exc::TimeoutError::type_object(),
+        _ => exc::OSError::type_object(),
+    }
+}
+
+#[cfg(not(Py_3))]
+fn io_error_type(_err: &io::Error) -> Py<PyType> {
+    exc::OSError::type_object()
+}
+
+/// Maps an errno value to an `OSError` subclass, like `OSError.__new__` does.
+#[cfg(all(Py_3, unix))]
+fn os_error_type(code: i32) -> Option<Py<PyType>> {
+    let ty = match code {
+        libc::EAGAIN | libc::EALREADY | libc::EINPROGRESS =>
+            exc::BlockingIOError::type_object(),
+        libc::ECHILD => exc::ChildProcessError::type_object(),
+        libc::EPIPE | libc::ESHUTDOWN => exc::BrokenPipeError::type_object(),
+        libc::ECONNABORTED => exc::ConnectionAbortedError::type_object(),
+        libc::ECONNREFUSED => exc::ConnectionRefusedError::type_object(),
+        libc::ECONNRESET => exc::ConnectionResetError::type_object(),
+        libc::EEXIST => exc::FileExistsError::type_object(),
+        libc::ENOENT => exc::FileNotFoundError::type_object(),
+        libc::EISDIR => exc::IsADirectoryError::type_object(),
+        libc::ENOTDIR => exc::NotADirectoryError::type_object(),
+        libc::EINTR => exc::InterruptedError::type_object(),
+        libc::EACCES | libc::EPERM => exc::PermissionError::type_object(),
+        libc::ESRCH => exc::ProcessLookupError::type_object(),
+        libc::ETIMEDOUT => exc::TimeoutError::type_object(),
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// Windows error codes are not errno values, the `io::ErrorKind` is used instead.
+#[cfg(all(Py_3, not(unix)))]
+fn os_error_type(_code: i32) -> Option<Py<PyType>> {
+    None
+}
+
 impl<W: 'static + Send + std::fmt::Debug> std::convert::From<std::io::IntoInnerError<W>> for PyErr
 {
     fn from(err: std::io::IntoInnerError<W>) -> PyErr {
@@ -565,6 +919,19 @@ pub fn panic_after_error() -> ! {
     panic!("Python API called failed");
 }
 
+/// Sets `cause` as the `__cause__` of `err`.
+///
+/// Python 2 has no exception chaining, `err` is returned unchanged.
+#[cfg(Py_3)]
+pub fn chain_cause(py: Python, err: PyErr, cause: PyErr) -> PyErr {
+    err.with_cause(py, cause)
+}
+
+#[cfg(not(Py_3))]
+pub fn chain_cause(_py: Python, err: PyErr, _cause: PyErr) -> PyErr {
+    err
+}
+
 /// Returns Ok if the error code is not -1.
 #[inline]
 pub fn error_on_minusone(py: Python, result: libc::c_int) -> PyResult<()> {
@@ -577,8 +944,12 @@ pub fn error_on_minusone(py: Python, result: libc::c_int) -> PyResult<()> {
 
 #[cfg(test)]
 mod tests {
+    use std::io;
+    use std::path::Path;
     use ::{Python, PyErr};
     use objects::exc;
+    use instance::AsPyRef;
+    use objectprotocol::ObjectProtocol;
 
     #[test]
     fn set_typeerror() {
@@ -589,4 +960,149 @@ mod tests {
         assert!(PyErr::occurred(py));
         drop(PyErr::fetch(py));
     }
+
+    #[test]
+    #[cfg(all(Py_3, unix))]
+    fn io_error_subclass() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let err: PyErr = ::std::fs::File::open("/nonexistent/pyo3").unwrap_err().into();
+        assert!(err.is_instance::<exc::FileNotFoundError>(py));
+
+        let err: PyErr = io::Error::from_raw_os_error(::libc::EISDIR).into();
+        assert!(err.is_instance::<exc::IsADirectoryError>(py));
+
+        let err: PyErr = io::Error::new(io::ErrorKind::PermissionDenied, "denied").into();
+        assert!(err.is_instance::<exc::PermissionError>(py));
+        let value = err.instance(py);
+        assert!(value.getattr(py, "errno").unwrap().is_none());
+        assert_eq!(value.as_ref(py).str().unwrap().to_string_lossy(), "denied");
+
+        let err: PyErr = io::Error::new(io::ErrorKind::InvalidData, "bad data").into();
+        assert!(err.is_instance::<exc::OSError>(py));
+        assert!(!err.is_instance::<exc::FileNotFoundError>(py));
+    }
+
+    #[test]
+    #[cfg(all(Py_3, unix))]
+    fn io_error_attributes() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let path = Path::new("/nonexistent/pyo3");
+        let err = PyErr::from_io_error(io::Error::from_raw_os_error(::libc::ENOENT), Some(path));
+        let value = err.instance(py);
+        let value = value.as_ref(py);
+        assert_eq!(value.getattr("errno").unwrap().extract::<i32>().unwrap(), ::libc::ENOENT);
+        assert_eq!(value.getattr("strerror").unwrap().extract::<String>().unwrap(),
+                   "No such file or directory");
+        assert_eq!(value.getattr("filename").unwrap().extract::<String>().unwrap(),
+                   "/nonexistent/pyo3");
+    }
+
+    #[test]
+    #[cfg(Py_3)]
+    fn with_cause() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let cause = py.run("raise KeyError('low level')", None, None).unwrap_err();
+        let err = exc::RuntimeError::new("high level").with_cause(py, cause);
+        assert!(err.is_instance::<exc::RuntimeError>(py));
+
+        let cause = err.cause(py).expect("cause should be set");
+        assert!(cause.is_instance::<exc::KeyError>(py));
+        assert!(cause.traceback(py).is_some());
+        assert!(cause.cause(py).is_none());
+
+        let value = err.instance(py);
+        assert!(value.getattr(py, "__suppress_context__").unwrap().extract::<bool>(py).unwrap());
+    }
+
+    #[test]
+    #[cfg(Py_3)]
+    fn context() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let err = py.run("try:\n    {}['x']\nexcept KeyError:\n    raise ValueError('y')",
+                         None, None).unwrap_err();
+        assert!(err.is_instance::<exc::ValueError>(py));
+        assert!(err.cause(py).is_none());
+        let context = err.context(py).expect("context should be set");
+        assert!(context.is_instance::<exc::KeyError>(py));
+        let tb = err.traceback(py).unwrap().format().unwrap();
+        assert!(tb.starts_with("Traceback (most recent call last):\n"), tb);
+    }
+
+    #[test]
+    fn display_and_debug() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let err = exc::ValueError::new("invalid value");
+        assert_eq!(err.to_string(), "ValueError: invalid value");
+        let err: PyErr = exc::KeyError.into();
+        assert_eq!(err.to_string(), "KeyError");
+
+        let err = py.run("def fail():\n    raise TypeError('oops')\nfail()", None, None)
+            .unwrap_err();
+        let debug = format!("{:?}", err);
+        assert!(debug.contains("TypeError'>"), debug);
+        assert!(debug.contains("value: TypeError('oops'"), debug);
+        assert!(debug.contains("in fail"), debug);
+
+        py_exception!(mymodule, CustomError);
+        let err = CustomError::new("custom");
+        assert_eq!(err.to_string(), "mymodule.CustomError: custom");
+    }
+
+    #[test]
+    #[cfg(Py_3)]
+    fn error_source() {
+        use std::error::Error;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let err = py.run("raise ValueError('outer') from KeyError('inner')", None, None)
+            .unwrap_err();
+        let source = err.source().expect("source should be set");
+        assert_eq!(source.to_string(), "KeyError: 'inner'");
+        assert!(source.source().is_none());
+
+        let err = exc::RuntimeError::new("wrapped")
+            .with_cause(py, exc::ValueError::new("low level"));
+        assert_eq!(err.source().unwrap().to_string(), "ValueError: low level");
+
+        let boxed: Box<Error> = Box::new(err);
+        assert_eq!(boxed.to_string(), "RuntimeError: wrapped");
+
+        let err = py.run("e = ValueError('loop')\ne.__cause__ = e\nraise e", None, None)
+            .unwrap_err();
+        assert!(err.source().is_none());
+
+        let err = py.run("a = ValueError('a')\nb = KeyError('b')\nb.__cause__ = a\n\
+                          a.__cause__ = b\nraise a", None, None).unwrap_err();
+        let source = err.source().expect("source should be set");
+        assert_eq!(source.to_string(), "KeyError: 'b'");
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn format_without_gil() {
+        let _gil = Python::acquire_gil();
+
+        // `PyErr` is not `Send`, mark the GIL as released like `allow_threads` does
+        let err = exc::ValueError::new("invalid value");
+        let count = ::pythonrun::suspend_gil_count();
+        let (display, debug) = (err.to_string(), format!("{:?}", err));
+        ::pythonrun::resume_gil_count(count);
+        assert_eq!(display, "Python exception");
+        #[cfg(not(Py_LIMITED_API))]
+        assert_eq!(debug, "PyErr { type: ValueError }");
+        assert!(debug.starts_with("PyErr { type: "), debug);
+        assert_eq!(err.to_string(), "ValueError: invalid value");
+    }
 }
@@ -375,6 +375,10 @@ pub type newfunc =
 pub type allocfunc =
     unsafe extern "C" fn (arg1: *mut PyTypeObject, arg2: Py_ssize_t) -> *mut PyObject;
 
+/// Layout of the type object, used for the templates of `#[py::class]` types.
+#[doc(hidden)]
+pub type PyTypeTemplate = PyTypeObject;
+
 #[repr(C)]
 #[derive(Copy)]
 pub struct PyTypeObject {
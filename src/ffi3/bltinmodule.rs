@@ -1,7 +1,10 @@
 use ffi3::object::PyTypeObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyFilter_Type")]
     pub static mut PyFilter_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyMap_Type")]
     pub static mut PyMap_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyZip_Type")]
     pub static mut PyZip_Type: PyTypeObject;
 }
@@ -3,9 +3,13 @@ use ffi3::object::*;
 use ffi3::longobject::PyLongObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyBool_Type")]
     pub static mut PyBool_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="_PyPy_FalseStruct")]
     static mut _Py_FalseStruct: PyLongObject;
+    #[cfg_attr(PyPy, link_name="_PyPy_TrueStruct")]
     static mut _Py_TrueStruct: PyLongObject;
+    #[cfg_attr(PyPy, link_name="PyPyBool_FromLong")]
     pub fn PyBool_FromLong(arg1: c_long) -> *mut PyObject;
 }
 
@@ -3,7 +3,9 @@ use ffi3::object::*;
 use ffi3::pyport::Py_ssize_t;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_Type")]
     pub static mut PyByteArray_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyByteArrayIter_Type")]
     pub static mut PyByteArrayIter_Type: PyTypeObject;
 }
 
@@ -18,13 +20,19 @@ pub unsafe fn PyByteArray_CheckExact(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_FromObject")]
     pub fn PyByteArray_FromObject(o: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_Concat")]
     pub fn PyByteArray_Concat(a: *mut PyObject, b: *mut PyObject)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_FromStringAndSize")]
     pub fn PyByteArray_FromStringAndSize(string: *const c_char,
                                          len: Py_ssize_t) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_Size")]
     pub fn PyByteArray_Size(bytearray: *mut PyObject) -> Py_ssize_t;
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_AsString")]
     pub fn PyByteArray_AsString(bytearray: *mut PyObject) -> *mut c_char;
+    #[cfg_attr(PyPy, link_name="PyPyByteArray_Resize")]
     pub fn PyByteArray_Resize(bytearray: *mut PyObject, len: Py_ssize_t)
      -> c_int;
 }
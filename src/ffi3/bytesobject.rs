@@ -3,7 +3,9 @@ use ffi3::object::*;
 use ffi3::pyport::Py_ssize_t;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyBytes_Type")]
     pub static mut PyBytes_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytesIter_Type")]
     pub static mut PyBytesIter_Type: PyTypeObject;
 }
 
@@ -18,25 +20,36 @@ pub unsafe fn PyBytes_CheckExact(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyBytes_FromStringAndSize")]
     pub fn PyBytes_FromStringAndSize(arg1: *const c_char,
                                      arg2: Py_ssize_t) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_FromString")]
     pub fn PyBytes_FromString(arg1: *const c_char) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_FromObject")]
     pub fn PyBytes_FromObject(arg1: *mut PyObject) -> *mut PyObject;
     //pub fn PyBytes_FromFormatV(arg1: *const c_char, arg2: va_list)
     // -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_FromFormat")]
     pub fn PyBytes_FromFormat(arg1: *const c_char, ...)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_Size")]
     pub fn PyBytes_Size(arg1: *mut PyObject) -> Py_ssize_t;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_AsString")]
     pub fn PyBytes_AsString(arg1: *mut PyObject) -> *mut c_char;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_Repr")]
     pub fn PyBytes_Repr(arg1: *mut PyObject, arg2: c_int)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_Concat")]
     pub fn PyBytes_Concat(arg1: *mut *mut PyObject, arg2: *mut PyObject)
      -> ();
+    #[cfg_attr(PyPy, link_name="PyPyBytes_ConcatAndDel")]
     pub fn PyBytes_ConcatAndDel(arg1: *mut *mut PyObject, arg2: *mut PyObject)
      -> ();
+    #[cfg_attr(PyPy, link_name="PyPyBytes_DecodeEscape")]
     pub fn PyBytes_DecodeEscape(arg1: *const c_char, arg2: Py_ssize_t,
                                 arg3: *const c_char, arg4: Py_ssize_t,
                                 arg5: *const c_char) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyBytes_AsStringAndSize")]
     pub fn PyBytes_AsStringAndSize(obj: *mut PyObject,
                                    s: *mut *mut c_char,
                                    len: *mut Py_ssize_t) -> c_int;
@@ -3,6 +3,7 @@ use ffi3::object::PyObject;
 use ffi3::pystate::PyThreadState;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyEval_CallObjectWithKeywords")]
     pub fn PyEval_CallObjectWithKeywords(func: *mut PyObject,
                                          obj: *mut PyObject,
                                          kwargs: *mut PyObject)
@@ -15,47 +16,73 @@ pub unsafe fn PyEval_CallObject(func: *mut PyObject, arg: *mut PyObject) -> *mut
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyEval_CallFunction")]
     pub fn PyEval_CallFunction(obj: *mut PyObject,
                                format: *const c_char, ...)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_CallMethod")]
     pub fn PyEval_CallMethod(obj: *mut PyObject,
                              methodname: *const c_char,
                              format: *const c_char, ...)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetBuiltins")]
     pub fn PyEval_GetBuiltins() -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetGlobals")]
     pub fn PyEval_GetGlobals() -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetLocals")]
     pub fn PyEval_GetLocals() -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetFrame")]
     pub fn PyEval_GetFrame() -> *mut ::ffi3::PyFrameObject;
+    #[cfg_attr(PyPy, link_name="PyPy_AddPendingCall")]
     pub fn Py_AddPendingCall(func: Option<extern "C" fn(arg1: *mut c_void) -> c_int>,
                              arg: *mut c_void) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPy_MakePendingCalls")]
     pub fn Py_MakePendingCalls() -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPy_SetRecursionLimit")]
     pub fn Py_SetRecursionLimit(arg1: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_GetRecursionLimit")]
     pub fn Py_GetRecursionLimit() -> c_int;
+    #[cfg_attr(PyPy, link_name="_PyPy_CheckRecursiveCall")]
     fn _Py_CheckRecursiveCall(_where: *mut c_char) -> c_int;
+    #[cfg_attr(PyPy, link_name="_PyPy_CheckRecursionLimit")]
     static mut _Py_CheckRecursionLimit: c_int;
 }
 
 // TODO: Py_EnterRecursiveCall etc.
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetFuncName")]
     pub fn PyEval_GetFuncName(arg1: *mut PyObject) -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetFuncDesc")]
     pub fn PyEval_GetFuncDesc(arg1: *mut PyObject) -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPyEval_GetCallStats")]
     pub fn PyEval_GetCallStats(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_EvalFrame")]
     pub fn PyEval_EvalFrame(arg1: *mut ::ffi3::PyFrameObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_EvalFrameEx")]
     pub fn PyEval_EvalFrameEx(f: *mut ::ffi3::PyFrameObject, exc: c_int)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_SaveThread")]
     pub fn PyEval_SaveThread() -> *mut PyThreadState;
+    #[cfg_attr(PyPy, link_name="PyPyEval_RestoreThread")]
     pub fn PyEval_RestoreThread(arg1: *mut PyThreadState) -> ();
 }
 
 #[cfg(py_sys_config = "WITH_THREAD")]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyEval_ThreadsInitialized")]
     pub fn PyEval_ThreadsInitialized() -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyEval_InitThreads")]
     pub fn PyEval_InitThreads() -> ();
+    #[cfg_attr(PyPy, link_name="PyPyEval_AcquireLock")]
     pub fn PyEval_AcquireLock() -> ();
+    #[cfg_attr(PyPy, link_name="PyPyEval_ReleaseLock")]
     pub fn PyEval_ReleaseLock() -> ();
+    #[cfg_attr(PyPy, link_name="PyPyEval_AcquireThread")]
     pub fn PyEval_AcquireThread(tstate: *mut PyThreadState) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyEval_ReleaseThread")]
     pub fn PyEval_ReleaseThread(tstate: *mut PyThreadState) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyEval_ReInitThreads")]
     pub fn PyEval_ReInitThreads() -> ();
 }
 
@@ -65,8 +65,10 @@ pub const CO_FUTURE_GENERATOR_STOP : c_int = 0x8_0000;
 pub const CO_MAXBLOCKS: usize = 20;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyCode_Type")]
     pub static mut PyCode_Type: PyTypeObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyCode_New")]
     pub fn PyCode_New(arg1: c_int, arg2: c_int,
                       arg3: c_int, arg4: c_int,
                       arg5: c_int, arg6: *mut PyObject,
@@ -75,11 +77,14 @@ pub const CO_MAXBLOCKS: usize = 20;
                       arg11: *mut PyObject, arg12: *mut PyObject,
                       arg13: *mut PyObject, arg14: c_int,
                       arg15: *mut PyObject) -> *mut PyCodeObject;
+    #[cfg_attr(PyPy, link_name="PyPyCode_NewEmpty")]
     pub fn PyCode_NewEmpty(filename: *const c_char,
                            funcname: *const c_char,
                            firstlineno: c_int) -> *mut PyCodeObject;
+    #[cfg_attr(PyPy, link_name="PyPyCode_Addr2Line")]
     pub fn PyCode_Addr2Line(arg1: *mut PyCodeObject, arg2: c_int)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyCode_Optimize")]
     pub fn PyCode_Optimize(code: *mut PyObject, consts: *mut PyObject,
                            names: *mut PyObject, lnotab: *mut PyObject)
      -> *mut PyObject;
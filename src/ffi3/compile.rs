@@ -33,22 +33,28 @@ pub const FUTURE_GENERATOR_STOP   : &str = "generator_stop";
 
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyNode_Compile")]
     pub fn PyNode_Compile(arg1: *mut _node, arg2: *const c_char) -> *mut PyCodeObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyAST_CompileEx")]
     pub fn PyAST_CompileEx(_mod: *mut _mod,
                            filename: *const c_char,
                            flags: *mut PyCompilerFlags,
                            optimize: c_int, arena: *mut PyArena) -> *mut PyCodeObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyAST_CompileObject")]
     pub fn PyAST_CompileObject(_mod: *mut _mod,
                                filename: *mut PyObject,
                                flags: *mut PyCompilerFlags,
                                optimize: c_int, arena: *mut PyArena) -> *mut PyCodeObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyFuture_FromAST")]
     pub fn PyFuture_FromAST(_mod: *mut _mod, filename: *const c_char) -> *mut PyFutureFeatures;
 
+    #[cfg_attr(PyPy, link_name="PyPyFuture_FromASTObject")]
     pub fn PyFuture_FromASTObject(_mod: *mut _mod, filename: *mut PyObject) -> *mut PyFutureFeatures;
 
+    #[cfg_attr(PyPy, link_name="PyPyCompile_OpcodeStackEffect")]
     pub fn PyCompile_OpcodeStackEffect(
         opcode: c_int, oparg: c_int) -> c_int;
 }
@@ -2,6 +2,7 @@ use std::os::raw::{c_double, c_int};
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyComplex_Type")]
     pub static mut PyComplex_Type: PyTypeObject;
 }
 
@@ -16,9 +17,12 @@ pub unsafe fn PyComplex_CheckExact(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyComplex_FromDoubles")]
     pub fn PyComplex_FromDoubles(real: c_double,
                                  imag: c_double) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyComplex_RealAsDouble")]
     pub fn PyComplex_RealAsDouble(op: *mut PyObject) -> c_double;
+    #[cfg_attr(PyPy, link_name="PyPyComplex_ImagAsDouble")]
     pub fn PyComplex_ImagAsDouble(op: *mut PyObject) -> c_double;
 }
 
@@ -0,0 +1,341 @@
+use std::ptr;
+use std::ops::Deref;
+use std::os::raw::{c_char, c_int, c_uchar};
+use ffi3::object::*;
+use ffi3::pyport::Py_hash_t;
+use ffi3::pycapsule::PyCapsule_Import;
+use ffi3::pythonrun::PyErr_Print;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_CAPI {
+    pub DateType: *mut PyTypeObject,
+    pub DateTimeType: *mut PyTypeObject,
+    pub TimeType: *mut PyTypeObject,
+    pub DeltaType: *mut PyTypeObject,
+    pub TZInfoType: *mut PyTypeObject,
+    #[cfg(Py_3_7)]
+    pub TimeZone_UTC: *mut PyObject,
+
+    pub Date_FromDate: unsafe extern "C" fn(year: c_int, month: c_int, day: c_int,
+                                            cls: *mut PyTypeObject) -> *mut PyObject,
+    pub DateTime_FromDateAndTime: unsafe extern "C" fn(year: c_int, month: c_int, day: c_int,
+                                                       hour: c_int, minute: c_int,
+                                                       second: c_int, microsecond: c_int,
+                                                       tzinfo: *mut PyObject,
+                                                       cls: *mut PyTypeObject) -> *mut PyObject,
+    pub Time_FromTime: unsafe extern "C" fn(hour: c_int, minute: c_int,
+                                            second: c_int, microsecond: c_int,
+                                            tzinfo: *mut PyObject,
+                                            cls: *mut PyTypeObject) -> *mut PyObject,
+    pub Delta_FromDelta: unsafe extern "C" fn(days: c_int, seconds: c_int,
+                                              microseconds: c_int, normalize: c_int,
+                                              cls: *mut PyTypeObject) -> *mut PyObject,
+    #[cfg(Py_3_7)]
+    pub TimeZone_FromTimeZone: unsafe extern "C" fn(offset: *mut PyObject,
+                                                    name: *mut PyObject) -> *mut PyObject,
+
+    pub DateTime_FromTimestamp: unsafe extern "C" fn(cls: *mut PyObject, args: *mut PyObject,
+                                                     kwargs: *mut PyObject) -> *mut PyObject,
+    pub Date_FromTimestamp: unsafe extern "C" fn(cls: *mut PyObject,
+                                                 args: *mut PyObject) -> *mut PyObject,
+
+    #[cfg(Py_3_6)]
+    pub DateTime_FromDateAndTimeAndFold: unsafe extern "C" fn(year: c_int, month: c_int,
+                                                              day: c_int, hour: c_int,
+                                                              minute: c_int, second: c_int,
+                                                              microsecond: c_int,
+                                                              tzinfo: *mut PyObject,
+                                                              fold: c_int,
+                                                              cls: *mut PyTypeObject)
+                                                              -> *mut PyObject,
+    #[cfg(Py_3_6)]
+    pub Time_FromTimeAndFold: unsafe extern "C" fn(hour: c_int, minute: c_int,
+                                                   second: c_int, microsecond: c_int,
+                                                   tzinfo: *mut PyObject, fold: c_int,
+                                                   cls: *mut PyTypeObject) -> *mut PyObject,
+}
+
+pub const PyDateTime_CAPSULE_NAME: &'static [u8] = b"datetime.datetime_CAPI\0";
+
+pub const _PyDateTime_DATE_DATASIZE: usize = 4;
+pub const _PyDateTime_TIME_DATASIZE: usize = 6;
+pub const _PyDateTime_DATETIME_DATASIZE: usize = 10;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_Delta {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub days: c_int,
+    pub seconds: c_int,
+    pub microseconds: c_int,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_Date {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub hastzinfo: c_char,
+    pub data: [c_uchar; _PyDateTime_DATE_DATASIZE],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_Time {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub hastzinfo: c_char,
+    pub data: [c_uchar; _PyDateTime_TIME_DATASIZE],
+    #[cfg(Py_3_6)]
+    pub fold: c_uchar,
+    pub tzinfo: *mut PyObject,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_DateTime {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub hastzinfo: c_char,
+    pub data: [c_uchar; _PyDateTime_DATETIME_DATASIZE],
+    #[cfg(Py_3_6)]
+    pub fold: c_uchar,
+    pub tzinfo: *mut PyObject,
+}
+
+static mut PY_DATETIME_API_UNSAFE_CACHE: *const PyDateTime_CAPI = 0 as *const PyDateTime_CAPI;
+
+/// Lazily imported datetime C API.
+///
+/// Dereferencing `PyDateTimeAPI` imports the `datetime.datetime_CAPI` capsule
+/// on first use, so it must only be done while holding the GIL.
+pub struct PyDateTimeAPI {
+    __private_field: ()
+}
+pub static PyDateTimeAPI: PyDateTimeAPI = PyDateTimeAPI { __private_field: () };
+
+impl Deref for PyDateTimeAPI {
+    type Target = PyDateTime_CAPI;
+
+    fn deref(&self) -> &'static PyDateTime_CAPI {
+        unsafe {
+            let api = PyDateTime_IMPORT();
+            if api.is_null() {
+                PyErr_Print();
+                panic!("Failed to import the datetime C API");
+            }
+            &*api
+        }
+    }
+}
+
+/// Imports the datetime C API capsule, caching the result.
+///
+/// Must be called with the GIL held. Returns null and sets a Python error
+/// if the capsule could not be imported.
+#[inline]
+pub unsafe fn PyDateTime_IMPORT() -> *const PyDateTime_CAPI {
+    if PY_DATETIME_API_UNSAFE_CACHE.is_null() {
+        PY_DATETIME_API_UNSAFE_CACHE = PyCapsule_Import(
+            PyDateTime_CAPSULE_NAME.as_ptr() as *const c_char, 1) as *const PyDateTime_CAPI;
+    }
+    PY_DATETIME_API_UNSAFE_CACHE
+}
+
+// Type checks.
+
+#[inline(always)]
+pub unsafe fn PyDate_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, PyDateTimeAPI.DateType)
+}
+
+#[inline(always)]
+pub unsafe fn PyDate_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == PyDateTimeAPI.DateType) as c_int
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, PyDateTimeAPI.DateTimeType)
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == PyDateTimeAPI.DateTimeType) as c_int
+}
+
+#[inline(always)]
+pub unsafe fn PyTime_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, PyDateTimeAPI.TimeType)
+}
+
+#[inline(always)]
+pub unsafe fn PyTime_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == PyDateTimeAPI.TimeType) as c_int
+}
+
+#[inline(always)]
+pub unsafe fn PyDelta_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, PyDateTimeAPI.DeltaType)
+}
+
+#[inline(always)]
+pub unsafe fn PyDelta_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == PyDateTimeAPI.DeltaType) as c_int
+}
+
+#[inline(always)]
+pub unsafe fn PyTZInfo_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, PyDateTimeAPI.TZInfoType)
+}
+
+#[inline(always)]
+pub unsafe fn PyTZInfo_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == PyDateTimeAPI.TZInfoType) as c_int
+}
+
+// Accessors for date and datetime instances.
+
+#[inline(always)]
+pub unsafe fn PyDateTime_GET_YEAR(o: *mut PyObject) -> c_int {
+    let data = (*(o as *mut PyDateTime_Date)).data;
+    (c_int::from(data[0]) << 8) | c_int::from(data[1])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_GET_MONTH(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_Date)).data[2])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_GET_DAY(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_Date)).data[3])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DATE_GET_HOUR(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_DateTime)).data[4])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DATE_GET_MINUTE(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_DateTime)).data[5])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DATE_GET_SECOND(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_DateTime)).data[6])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DATE_GET_MICROSECOND(o: *mut PyObject) -> c_int {
+    let data = (*(o as *mut PyDateTime_DateTime)).data;
+    (c_int::from(data[7]) << 16) | (c_int::from(data[8]) << 8) | c_int::from(data[9])
+}
+
+#[cfg(Py_3_6)]
+#[inline(always)]
+pub unsafe fn PyDateTime_DATE_GET_FOLD(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_DateTime)).fold)
+}
+
+/// Returns a borrowed reference to the `tzinfo` of a datetime, or `Py_None`.
+#[inline(always)]
+pub unsafe fn PyDateTime_DATE_GET_TZINFO(o: *mut PyObject) -> *mut PyObject {
+    let dt = o as *mut PyDateTime_DateTime;
+    if (*dt).hastzinfo != 0 { (*dt).tzinfo } else { Py_None() }
+}
+
+// Accessors for time instances.
+
+#[inline(always)]
+pub unsafe fn PyDateTime_TIME_GET_HOUR(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_Time)).data[0])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_TIME_GET_MINUTE(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_Time)).data[1])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_TIME_GET_SECOND(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_Time)).data[2])
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_TIME_GET_MICROSECOND(o: *mut PyObject) -> c_int {
+    let data = (*(o as *mut PyDateTime_Time)).data;
+    (c_int::from(data[3]) << 16) | (c_int::from(data[4]) << 8) | c_int::from(data[5])
+}
+
+#[cfg(Py_3_6)]
+#[inline(always)]
+pub unsafe fn PyDateTime_TIME_GET_FOLD(o: *mut PyObject) -> c_int {
+    c_int::from((*(o as *mut PyDateTime_Time)).fold)
+}
+
+/// Returns a borrowed reference to the `tzinfo` of a time, or `Py_None`.
+#[inline(always)]
+pub unsafe fn PyDateTime_TIME_GET_TZINFO(o: *mut PyObject) -> *mut PyObject {
+    let t = o as *mut PyDateTime_Time;
+    if (*t).hastzinfo != 0 { (*t).tzinfo } else { Py_None() }
+}
+
+// Accessors for timedelta instances.
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DELTA_GET_DAYS(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Delta)).days
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DELTA_GET_SECONDS(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Delta)).seconds
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_DELTA_GET_MICROSECONDS(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Delta)).microseconds
+}
+
+// Constructors.
+
+#[inline(always)]
+pub unsafe fn PyDate_FromDate(year: c_int, month: c_int, day: c_int) -> *mut PyObject {
+    (PyDateTimeAPI.Date_FromDate)(year, month, day, PyDateTimeAPI.DateType)
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_FromDateAndTime(year: c_int, month: c_int, day: c_int,
+                                         hour: c_int, minute: c_int, second: c_int,
+                                         usecond: c_int) -> *mut PyObject {
+    (PyDateTimeAPI.DateTime_FromDateAndTime)(
+        year, month, day, hour, minute, second, usecond,
+        Py_None(), PyDateTimeAPI.DateTimeType)
+}
+
+#[inline(always)]
+pub unsafe fn PyTime_FromTime(hour: c_int, minute: c_int,
+                              second: c_int, usecond: c_int) -> *mut PyObject {
+    (PyDateTimeAPI.Time_FromTime)(
+        hour, minute, second, usecond, Py_None(), PyDateTimeAPI.TimeType)
+}
+
+#[inline(always)]
+pub unsafe fn PyDelta_FromDSU(days: c_int, seconds: c_int, useconds: c_int) -> *mut PyObject {
+    (PyDateTimeAPI.Delta_FromDelta)(days, seconds, useconds, 1, PyDateTimeAPI.DeltaType)
+}
+
+#[inline(always)]
+pub unsafe fn PyDateTime_FromTimestamp(args: *mut PyObject) -> *mut PyObject {
+    (PyDateTimeAPI.DateTime_FromTimestamp)(
+        PyDateTimeAPI.DateTimeType as *mut PyObject, args, ptr::null_mut())
+}
+
+#[inline(always)]
+pub unsafe fn PyDate_FromTimestamp(args: *mut PyObject) -> *mut PyObject {
+    (PyDateTimeAPI.Date_FromTimestamp)(PyDateTimeAPI.DateType as *mut PyObject, args)
+}
@@ -3,12 +3,19 @@ use ffi3::pyport::Py_ssize_t;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyDict_Type")]
     pub static mut PyDict_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyDictIterKey_Type")]
     pub static mut PyDictIterKey_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyDictIterValue_Type")]
     pub static mut PyDictIterValue_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyDictIterItem_Type")]
     pub static mut PyDictIterItem_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyDictKeys_Type")]
     pub static mut PyDictKeys_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyDictItems_Type")]
     pub static mut PyDictItems_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyDictValues_Type")]
     pub static mut PyDictValues_Type: PyTypeObject;
 }
 
@@ -43,25 +50,44 @@ pub unsafe fn PyDictViewSet_Check(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyDict_New")]
     pub fn PyDict_New() -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_GetItem")]
     pub fn PyDict_GetItem(mp: *mut PyObject, key: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_GetItemWithError")]
     pub fn PyDict_GetItemWithError(mp: *mut PyObject, key: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_SetItem")]
     pub fn PyDict_SetItem(mp: *mut PyObject, key: *mut PyObject, item: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_DelItem")]
     pub fn PyDict_DelItem(mp: *mut PyObject, key: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Clear")]
     pub fn PyDict_Clear(mp: *mut PyObject) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyDict_Next")]
     pub fn PyDict_Next(mp: *mut PyObject, pos: *mut Py_ssize_t,
                        key: *mut *mut PyObject, value: *mut *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Keys")]
     pub fn PyDict_Keys(mp: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Values")]
     pub fn PyDict_Values(mp: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Items")]
     pub fn PyDict_Items(mp: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Size")]
     pub fn PyDict_Size(mp: *mut PyObject) -> Py_ssize_t;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Copy")]
     pub fn PyDict_Copy(mp: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Contains")]
     pub fn PyDict_Contains(mp: *mut PyObject, key: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Update")]
     pub fn PyDict_Update(mp: *mut PyObject, other: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_Merge")]
     pub fn PyDict_Merge(mp: *mut PyObject, other: *mut PyObject, _override: c_int) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_MergeFromSeq2")]
     pub fn PyDict_MergeFromSeq2(d: *mut PyObject, seq2: *mut PyObject, _override: c_int) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_GetItemString")]
     pub fn PyDict_GetItemString(dp: *mut PyObject, key: *const c_char) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyDict_SetItemString")]
     pub fn PyDict_SetItemString(dp: *mut PyObject, key: *const c_char,
                                 item: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyDict_DelItemString")]
     pub fn PyDict_DelItemString(dp: *mut PyObject, key: *const c_char) -> c_int;
 }
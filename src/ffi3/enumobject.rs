@@ -1,7 +1,9 @@
 use ffi3::object::PyTypeObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyEnum_Type")]
     pub static mut PyEnum_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyReversed_Type")]
     pub static mut PyReversed_Type: PyTypeObject;
 }
 
@@ -2,8 +2,10 @@ use std::os::raw::c_int;
 use ffi3::object::PyObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyEval_EvalCode")]
     pub fn PyEval_EvalCode(arg1: *mut PyObject, arg2: *mut PyObject,
                            arg3: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyEval_EvalCodeEx")]
     pub fn PyEval_EvalCodeEx(co: *mut PyObject, globals: *mut PyObject,
                              locals: *mut PyObject, args: *mut *mut PyObject,
                              argc: c_int, kwds: *mut *mut PyObject,
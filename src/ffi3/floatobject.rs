@@ -2,6 +2,7 @@ use std::os::raw::{c_int, c_double};
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyFloat_Type")]
     pub static mut PyFloat_Type: PyTypeObject;
 }
 
@@ -16,11 +17,17 @@ pub unsafe fn PyFloat_CheckExact(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyFloat_GetMax")]
     pub fn PyFloat_GetMax() -> c_double;
+    #[cfg_attr(PyPy, link_name="PyPyFloat_GetMin")]
     pub fn PyFloat_GetMin() -> c_double;
+    #[cfg_attr(PyPy, link_name="PyPyFloat_GetInfo")]
     pub fn PyFloat_GetInfo() -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyFloat_FromString")]
     pub fn PyFloat_FromString(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyFloat_FromDouble")]
     pub fn PyFloat_FromDouble(arg1: c_double) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyFloat_AsDouble")]
     pub fn PyFloat_AsDouble(arg1: *mut PyObject) -> c_double;
 }
 
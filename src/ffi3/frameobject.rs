@@ -46,6 +46,7 @@ pub struct PyFrameObject {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyFrame_Type")]
     pub static mut PyFrame_Type: PyTypeObject;
 }
 
@@ -55,16 +56,24 @@ pub unsafe fn PyFrame_Check(op: *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyFrame_New")]
     pub fn PyFrame_New(tstate: *mut PyThreadState, code: *mut PyCodeObject,
         globals: *mut PyObject, locals: *mut PyObject) -> *mut PyFrameObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyFrame_BlockSetup")]
     pub fn PyFrame_BlockSetup(f: *mut PyFrameObject, _type: c_int, handler: c_int, level: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyFrame_BlockPop")]
     pub fn PyFrame_BlockPop(f: *mut PyFrameObject) -> *mut PyTryBlock;
 
+    #[cfg_attr(PyPy, link_name="PyPyFrame_LocalsToFast")]
     pub fn PyFrame_LocalsToFast(f: *mut PyFrameObject, clear: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyFrame_FastToLocalsWithError")]
     pub fn PyFrame_FastToLocalsWithError(f: *mut PyFrameObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyFrame_FastToLocals")]
     pub fn PyFrame_FastToLocals(f: *mut PyFrameObject) -> ();
     
+    #[cfg_attr(PyPy, link_name="PyPyFrame_ClearFreeList")]
     pub fn PyFrame_ClearFreeList() -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyFrame_GetLineNumber")]
     pub fn PyFrame_GetLineNumber(f: *mut PyFrameObject) -> c_int;
 }
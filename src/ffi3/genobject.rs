@@ -11,6 +11,8 @@ pub struct PyGenObject {
     #[cfg(py_sys_config="Py_TRACE_REFS")]
     pub _ob_prev: *mut PyObject,
     pub ob_refcnt: Py_ssize_t,
+    #[cfg(PyPy)]
+    pub ob_pypy_link: Py_ssize_t,
     pub ob_type: *mut PyTypeObject,
     pub gi_frame: *mut PyFrameObject,
     pub gi_running: c_int,
@@ -19,6 +21,7 @@ pub struct PyGenObject {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyGen_Type")]
     pub static mut PyGen_Type: PyTypeObject;
 }
 
@@ -33,11 +36,14 @@ pub unsafe fn PyGen_CheckExact(op: *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyGen_New")]
     pub fn PyGen_New(frame: *mut PyFrameObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyGen_NeedsFinalizing")]
     pub fn PyGen_NeedsFinalizing(op: *mut PyGenObject) -> c_int;
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyCoro_Type")]
     pub static mut PyCoro_Type: PyTypeObject;
 }
 
@@ -47,6 +53,7 @@ pub unsafe fn PyCoro_Check(op: *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="_PyPyCoroWrapper_Type")]
     pub static mut _PyCoroWrapper_Type: PyTypeObject;
 }
 
@@ -57,6 +64,7 @@ pub unsafe fn PyCoroWrapper_Check(op: *mut PyObject) -> c_int {
 
 #[cfg(Py_3_6)]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyAsyncGen_Type")]
     pub static mut PyAsyncGen_Type: PyTypeObject;
 }
 
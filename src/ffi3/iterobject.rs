@@ -2,10 +2,14 @@ use std::os::raw::c_int;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPySeqIter_Type")]
     pub static mut PySeqIter_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyCallIter_Type")]
     pub static mut PyCallIter_Type: PyTypeObject;
 
+    #[cfg_attr(PyPy, link_name="PyPySeqIter_New")]
     pub fn PySeqIter_New(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyCallIter_New")]
     pub fn PyCallIter_New(arg1: *mut PyObject, arg2: *mut PyObject)
      -> *mut PyObject;
 }
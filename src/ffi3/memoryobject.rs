@@ -3,6 +3,7 @@ use ffi3::pyport::Py_ssize_t;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyMemoryView_Type")]
     pub static mut PyMemoryView_Type: PyTypeObject;
 }
 
@@ -12,9 +13,12 @@ pub unsafe fn PyMemoryView_Check(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyMemoryView_FromObject")]
     pub fn PyMemoryView_FromObject(base: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyMemoryView_FromMemory")]
     pub fn PyMemoryView_FromMemory(mem: *mut c_char, size: Py_ssize_t,
                                    flags: c_int) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyMemoryView_GetContiguous")]
     pub fn PyMemoryView_GetContiguous(base: *mut PyObject,
                                       buffertype: c_int,
                                       order: c_char) -> *mut PyObject;
@@ -3,6 +3,7 @@ use std::{mem, ptr};
 use ffi3::object::{PyObject, PyTypeObject, Py_TYPE};
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_Type")]
     pub static mut PyCFunction_Type: PyTypeObject;
 }
 
@@ -30,9 +31,13 @@ pub type PyNoArgsFunction =
     unsafe extern "C" fn(slf: *mut PyObject) -> *mut PyObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_GetFunction")]
     pub fn PyCFunction_GetFunction(f: *mut PyObject) -> Option<PyCFunction>;
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_GetSelf")]
     pub fn PyCFunction_GetSelf(f: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_GetFlags")]
     pub fn PyCFunction_GetFlags(f: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_Call")]
     pub fn PyCFunction_Call(f: *mut PyObject,
                             args: *mut PyObject,
                             kwds: *mut PyObject) -> *mut PyObject;
@@ -64,6 +69,7 @@ pub unsafe fn PyCFunction_New(ml: *mut PyMethodDef, slf: *mut PyObject) -> *mut
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_NewEx")]
     pub fn PyCFunction_NewEx(arg1: *mut PyMethodDef, arg2: *mut PyObject,
                              arg3: *mut PyObject) -> *mut PyObject;
 }
@@ -92,6 +98,7 @@ pub const METH_COEXIST   : c_int = 0x0040;
 pub const METHOD_FASTCALL : c_int = 0x0080;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyCFunction_ClearFreeList")]
     pub fn PyCFunction_ClearFreeList() -> c_int;
 }
 
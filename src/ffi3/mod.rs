@@ -31,6 +31,7 @@ pub use self::methodobject::*;
 pub use self::moduleobject::*;
 pub use self::fileobject::*;
 pub use self::pycapsule::*;
+#[cfg(not(Py_LIMITED_API))] pub use self::datetime::*;
 pub use self::traceback::*;
 pub use self::sliceobject::*;
 pub use self::iterobject::*;
@@ -102,6 +103,7 @@ mod moduleobject; // TODO supports PEP-384 only; needs adjustment for Python 3.3
 // mod classobject; TODO excluded by PEP-384
 mod fileobject; // TODO supports PEP-384 only; needs adjustment for Python 3.3 and 3.5
 mod pycapsule; // TODO supports PEP-384 only; needs adjustment for Python 3.3 and 3.5
+#[cfg(not(Py_LIMITED_API))] mod datetime;
 mod traceback; // TODO supports PEP-384 only; needs adjustment for Python 3.3 and 3.5
 mod sliceobject; // TODO supports PEP-384 only; needs adjustment for Python 3.3 and 3.5
 // mod cellobject; TODO excluded by PEP-384
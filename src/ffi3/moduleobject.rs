@@ -4,6 +4,7 @@ use ffi3::object::*;
 use ffi3::methodobject::PyMethodDef;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyModule_Type")]
     pub static mut PyModule_Type: PyTypeObject;
 }
 
@@ -18,16 +19,27 @@ pub unsafe fn PyModule_CheckExact(op : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyModule_NewObject")]
     pub fn PyModule_NewObject(name: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyModule_New")]
     pub fn PyModule_New(name: *const c_char) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetDict")]
     pub fn PyModule_GetDict(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetNameObject")]
     pub fn PyModule_GetNameObject(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetName")]
     pub fn PyModule_GetName(arg1: *mut PyObject) -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetFilename")]
     pub fn PyModule_GetFilename(arg1: *mut PyObject) -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetFilenameObject")]
     pub fn PyModule_GetFilenameObject(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetDef")]
     pub fn PyModule_GetDef(arg1: *mut PyObject) -> *mut PyModuleDef;
+    #[cfg_attr(PyPy, link_name="PyPyModule_GetState")]
     pub fn PyModule_GetState(arg1: *mut PyObject) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyModuleDef_Init")]
     pub fn PyModuleDef_Init(arg1: *mut PyModuleDef) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyModuleDef_Type")]
     pub static mut PyModuleDef_Type: PyTypeObject;
 }
 
@@ -10,10 +10,12 @@ pub struct PyObject {
     #[cfg(py_sys_config="Py_TRACE_REFS")]
     _ob_prev: *mut PyObject,
     pub ob_refcnt: Py_ssize_t,
+    #[cfg(PyPy)]
+    pub ob_pypy_link: Py_ssize_t,
     pub ob_type: *mut PyTypeObject,
 }
 
-#[cfg(py_sys_config="Py_TRACE_REFS")]
+#[cfg(all(py_sys_config="Py_TRACE_REFS", not(PyPy)))]
 pub const PyObject_HEAD_INIT: PyObject = PyObject {
     _ob_next: ::std::ptr::null_mut(),
     _ob_prev: ::std::ptr::null_mut(),
@@ -21,7 +23,14 @@ pub const PyObject_HEAD_INIT: PyObject = PyObject {
     ob_type: ::std::ptr::null_mut()
 };
 
-#[cfg(not(py_sys_config="Py_TRACE_REFS"))]
+#[cfg(PyPy)]
+pub const PyObject_HEAD_INIT: PyObject = PyObject {
+    ob_refcnt: 1,
+    ob_pypy_link: 0,
+    ob_type: ::std::ptr::null_mut()
+};
+
+#[cfg(not(any(py_sys_config="Py_TRACE_REFS", PyPy)))]
 pub const PyObject_HEAD_INIT: PyObject = PyObject {
     ob_refcnt: 1,
     ob_type: ::std::ptr::null_mut()
@@ -208,12 +217,25 @@ pub type allocfunc =
 #[cfg(Py_LIMITED_API)]
 pub enum PyTypeObject { }
 
-#[cfg(not(Py_LIMITED_API))]
+// With Py_LIMITED_API the layout of the type object is not part of the stable ABI.
+// It is still used for the templates `typeob::create_type` converts into
+// a `PyType_Spec`, see `PyTypeTemplate`.
 mod typeobject {
     use ffi3::{self, object};
     use std::os::raw::{c_void, c_char, c_ulong, c_uint};
     use ffi3::pyport::Py_ssize_t;
 
+    #[cfg(not(Py_LIMITED_API))]
+    use ffi3::object::printfunc;
+
+    /// Templates never set `tp_print`.
+    #[cfg(Py_LIMITED_API)]
+    pub type printfunc = unsafe extern "C" fn();
+
+    /// The buffer protocol is not part of the limited API, templates never set `tp_as_buffer`.
+    #[cfg(Py_LIMITED_API)]
+    pub enum PyBufferProcs { }
+
     #[repr(C)]
     #[derive(Copy, Clone)]
     pub struct PyNumberMethods {
@@ -373,14 +395,17 @@ mod typeobject {
     };
     #[repr(C)]
     #[derive(Copy, Clone, Debug)]
+    #[cfg(not(Py_LIMITED_API))]
     pub struct PyBufferProcs {
         pub bf_getbuffer: Option<object::getbufferproc>,
         pub bf_releasebuffer: Option<object::releasebufferproc>,
     }
 
+    #[cfg(not(Py_LIMITED_API))]
     impl Default for PyBufferProcs {
         #[inline] fn default() -> Self { unsafe { ::std::mem::zeroed() } }
     }
+    #[cfg(not(Py_LIMITED_API))]
     pub const PyBufferProcs_INIT : PyBufferProcs = PyBufferProcs {
         bf_getbuffer: None,
         bf_releasebuffer: None,
@@ -394,7 +419,7 @@ mod typeobject {
         pub tp_basicsize: Py_ssize_t,
         pub tp_itemsize: Py_ssize_t,
         pub tp_dealloc: Option<object::destructor>,
-        pub tp_print: Option<object::printfunc>,
+        pub tp_print: Option<printfunc>,
         pub tp_getattr: Option<object::getattrfunc>,
         pub tp_setattr: Option<object::setattrfunc>,
         pub tp_as_async: *mut PyAsyncMethods,
@@ -419,7 +444,7 @@ mod typeobject {
         pub tp_methods: *mut ffi3::methodobject::PyMethodDef,
         pub tp_members: *mut ffi3::structmember::PyMemberDef,
         pub tp_getset: *mut ffi3::descrobject::PyGetSetDef,
-        pub tp_base: *mut PyTypeObject,
+        pub tp_base: *mut object::PyTypeObject,
         pub tp_dict: *mut ffi3::object::PyObject,
         pub tp_descr_get: Option<ffi3::object::descrgetfunc>,
         pub tp_descr_set: Option<ffi3::object::descrsetfunc>,
@@ -537,6 +562,7 @@ mod typeobject {
 
     #[repr(C)]
     #[derive(Copy, Clone)]
+    #[cfg(not(Py_LIMITED_API))]
     pub struct PyHeapTypeObject {
         pub ht_type: PyTypeObject,
         pub as_async: PyAsyncMethods,
@@ -550,20 +576,29 @@ mod typeobject {
         pub ht_cached_keys: *mut c_void,
     }
 
+    #[cfg(not(Py_LIMITED_API))]
     impl Default for PyHeapTypeObject {
         #[inline] fn default() -> Self { unsafe { ::std::mem::zeroed() } }
     }
 
     #[inline]
+    #[cfg(not(Py_LIMITED_API))]
     pub unsafe fn PyHeapType_GET_MEMBERS(etype: *mut PyHeapTypeObject) -> *mut ffi3::structmember::PyMemberDef {
         (etype as *mut c_char).offset(
             (*ffi3::object::Py_TYPE(etype as *mut ffi3::object::PyObject)).tp_basicsize as isize
         ) as *mut ffi3::structmember::PyMemberDef
     }
 }
-#[cfg(not(Py_LIMITED_API))]
+// with Py_LIMITED_API the opaque `PyTypeObject` shadows the struct of the glob import
 pub use self::typeobject::*;
 
+/// Layout of the type object, used for the templates of `#[py::class]` types.
+///
+/// With Py_LIMITED_API this is only valid for templates,
+/// type objects owned by the interpreter are opaque.
+#[doc(hidden)]
+pub use self::typeobject::PyTypeObject as PyTypeTemplate;
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct PyType_Slot {
@@ -590,15 +625,19 @@ impl Default for PyType_Spec {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyType_FromSpec")]
     pub fn PyType_FromSpec(arg1: *mut PyType_Spec) -> *mut PyObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyType_FromSpecWithBases")]
     pub fn PyType_FromSpecWithBases(arg1: *mut PyType_Spec, arg2: *mut PyObject)
                                     -> *mut PyObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyType_GetSlot")]
     pub fn PyType_GetSlot(arg1: *mut PyTypeObject, arg2: c_int) -> *mut c_void;
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyType_IsSubtype")]
     pub fn PyType_IsSubtype(a: *mut PyTypeObject, b: *mut PyTypeObject) -> c_int;
 }
 
@@ -609,12 +648,16 @@ pub unsafe fn PyObject_TypeCheck(ob: *mut PyObject, tp: *mut PyTypeObject) -> c_
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
     /// built-in 'type'
+    #[cfg_attr(PyPy, link_name="PyPyType_Type")]
     pub static mut PyType_Type: PyTypeObject;
     /// built-in 'object'
+    #[cfg_attr(PyPy, link_name="PyPyBaseObject_Type")]
     pub static mut PyBaseObject_Type: PyTypeObject;
     /// built-in 'super'
+    #[cfg_attr(PyPy, link_name="PyPySuper_Type")]
     pub static mut PySuper_Type: PyTypeObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyType_GetFlags")]
     pub fn PyType_GetFlags(arg1: *mut PyTypeObject) -> c_ulong;
 }
 
@@ -629,57 +672,91 @@ pub unsafe fn PyType_CheckExact(op: *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyType_Ready")]
     pub fn PyType_Ready(t: *mut PyTypeObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyType_GenericAlloc")]
     pub fn PyType_GenericAlloc(t: *mut PyTypeObject, nitems: Py_ssize_t) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyType_GenericNew")]
     pub fn PyType_GenericNew(t: *mut PyTypeObject, args: *mut PyObject,
                              kwds: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyType_ClearCache")]
     pub fn PyType_ClearCache() -> c_uint;
+    #[cfg_attr(PyPy, link_name="PyPyType_Modified")]
     pub fn PyType_Modified(t: *mut PyTypeObject);
 
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPyObject_Print")]
     pub fn PyObject_Print(o: *mut PyObject, fp: *mut ::libc::FILE, flags: c_int) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Repr")]
     pub fn PyObject_Repr(o: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Str")]
     pub fn PyObject_Str(o: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_ASCII")]
     pub fn PyObject_ASCII(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Bytes")]
     pub fn PyObject_Bytes(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_RichCompare")]
     pub fn PyObject_RichCompare(arg1: *mut PyObject, arg2: *mut PyObject,
                                 arg3: c_int) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_RichCompareBool")]
     pub fn PyObject_RichCompareBool(arg1: *mut PyObject, arg2: *mut PyObject,
                                     arg3: c_int) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_GetAttrString")]
     pub fn PyObject_GetAttrString(arg1: *mut PyObject,
                                   arg2: *const c_char) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_SetAttrString")]
     pub fn PyObject_SetAttrString(arg1: *mut PyObject,
                                   arg2: *const c_char,
                                   arg3: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_HasAttrString")]
     pub fn PyObject_HasAttrString(arg1: *mut PyObject, arg2: *const c_char) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_GetAttr")]
     pub fn PyObject_GetAttr(arg1: *mut PyObject, arg2: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_SetAttr")]
     pub fn PyObject_SetAttr(arg1: *mut PyObject, arg2: *mut PyObject,
                             arg3: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_HasAttr")]
     pub fn PyObject_HasAttr(arg1: *mut PyObject, arg2: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_SelfIter")]
     pub fn PyObject_SelfIter(arg1: *mut PyObject) -> *mut PyObject;
 
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="_PyPyObject_NextNotImplemented")]
     pub fn _PyObject_NextNotImplemented(arg1: *mut PyObject) -> *mut PyObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyObject_GenericGetAttr")]
     pub fn PyObject_GenericGetAttr(arg1: *mut PyObject, arg2: *mut PyObject)
                                    -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_GenericSetAttr")]
     pub fn PyObject_GenericSetAttr(arg1: *mut PyObject, arg2: *mut PyObject,
                                    arg3: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_GenericSetDict")]
     pub fn PyObject_GenericSetDict(arg1: *mut PyObject, arg2: *mut PyObject,
                                    arg3: *mut c_void) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Hash")]
     pub fn PyObject_Hash(arg1: *mut PyObject) -> Py_hash_t;
+    #[cfg_attr(PyPy, link_name="PyPyObject_HashNotImplemented")]
     pub fn PyObject_HashNotImplemented(arg1: *mut PyObject) -> Py_hash_t;
+    #[cfg_attr(PyPy, link_name="PyPyObject_IsTrue")]
     pub fn PyObject_IsTrue(arg1: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Not")]
     pub fn PyObject_Not(arg1: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyCallable_Check")]
     pub fn PyCallable_Check(arg1: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyObject_ClearWeakRefs")]
     pub fn PyObject_ClearWeakRefs(arg1: *mut PyObject) -> ();
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPyObject_CallFinalizer")]
     pub fn PyObject_CallFinalizer(arg1: *mut PyObject) -> ();
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPyObject_CallFinalizerFromDealloc")]
     pub fn PyObject_CallFinalizerFromDealloc(arg1: *mut PyObject) -> c_int;
 
+    #[cfg_attr(PyPy, link_name="PyPyObject_Dir")]
     pub fn PyObject_Dir(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPy_ReprEnter")]
     pub fn Py_ReprEnter(arg1: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPy_ReprLeave")]
     pub fn Py_ReprLeave(arg1: *mut PyObject) -> ();
 }
 
@@ -743,13 +820,15 @@ pub unsafe fn PyType_FastSubclass(t : *mut PyTypeObject, f : c_ulong) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="_PyPy_Dealloc")]
     pub fn _Py_Dealloc(arg1: *mut PyObject) -> ();
 }
 
 // Reference counting macros.
 #[inline(always)]
 pub unsafe fn Py_INCREF(op : *mut PyObject) {
-    if cfg!(py_sys_config="Py_REF_DEBUG") {
+    // the reference count of PyPy objects must only be changed by the exported functions
+    if cfg!(any(py_sys_config="Py_REF_DEBUG", PyPy)) {
         Py_IncRef(op)
     } else {
         (*op).ob_refcnt += 1
@@ -758,7 +837,7 @@ pub unsafe fn Py_INCREF(op : *mut PyObject) {
 
 #[inline(always)]
 pub unsafe fn Py_DECREF(op: *mut PyObject) {
-    if cfg!(py_sys_config="Py_REF_DEBUG") {
+    if cfg!(any(py_sys_config="Py_REF_DEBUG", PyPy)) {
         Py_DecRef(op)
     } else {
         (*op).ob_refcnt -= 1;
@@ -792,10 +871,14 @@ pub unsafe fn Py_XDECREF(op : *mut PyObject) {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPy_IncRef")]
     pub fn Py_IncRef(o: *mut PyObject);
+    #[cfg_attr(PyPy, link_name="PyPy_DecRef")]
     pub fn Py_DecRef(o: *mut PyObject);
 
+    #[cfg_attr(PyPy, link_name="_PyPy_NoneStruct")]
     static mut _Py_NoneStruct: PyObject;
+    #[cfg_attr(PyPy, link_name="_PyPy_NotImplementedStruct")]
     static mut _Py_NotImplementedStruct: PyObject;
 }
 
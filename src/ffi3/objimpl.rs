@@ -4,19 +4,29 @@ use ffi3::pyport::Py_ssize_t;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyObject_Malloc")]
     pub fn PyObject_Malloc(size: size_t) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Calloc")]
     pub fn PyObject_Calloc(nelem: size_t, elsize: size_t) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Realloc")]
     pub fn PyObject_Realloc(ptr: *mut c_void, new_size: size_t) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Free")]
     pub fn PyObject_Free(ptr: *mut c_void) -> ();
 
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="_PyPy_GetAllocatedBlocks")]
     pub fn _Py_GetAllocatedBlocks() -> Py_ssize_t;
+    #[cfg_attr(PyPy, link_name="PyPyObject_Init")]
     pub fn PyObject_Init(arg1: *mut PyObject, arg2: *mut PyTypeObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_InitVar")]
     pub fn PyObject_InitVar(arg1: *mut PyVarObject, arg2: *mut PyTypeObject,
                             arg3: Py_ssize_t) -> *mut PyVarObject;
+    #[cfg_attr(PyPy, link_name="_PyPyObject_New")]
     pub fn _PyObject_New(arg1: *mut PyTypeObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="_PyPyObject_NewVar")]
     pub fn _PyObject_NewVar(arg1: *mut PyTypeObject, arg2: Py_ssize_t) -> *mut PyVarObject;
 
+    #[cfg_attr(PyPy, link_name="PyPyGC_Collect")]
     pub fn PyGC_Collect() -> Py_ssize_t;
 }
 
@@ -35,7 +45,9 @@ impl Default for PyObjectArenaAllocator {
 }
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyObject_GetArenaAllocator")]
     pub fn PyObject_GetArenaAllocator(allocator: *mut PyObjectArenaAllocator) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyObject_SetArenaAllocator")]
     pub fn PyObject_SetArenaAllocator(allocator: *mut PyObjectArenaAllocator) -> ();
 }
 
@@ -58,16 +70,24 @@ pub unsafe fn PyObject_IS_GC(o : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="_PyPyObject_GC_Resize")]
     pub fn _PyObject_GC_Resize(arg1: *mut PyVarObject, arg2: Py_ssize_t) -> *mut PyVarObject;
 
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="_PyPyObject_GC_Malloc")]
     pub fn _PyObject_GC_Malloc(size: size_t) -> *mut PyObject;
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="_PyPyObject_GC_Calloc")]
     pub fn _PyObject_GC_Calloc(size: size_t) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="_PyPyObject_GC_New")]
     pub fn _PyObject_GC_New(arg1: *mut PyTypeObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="_PyPyObject_GC_NewVar")]
     pub fn _PyObject_GC_NewVar(arg1: *mut PyTypeObject, arg2: Py_ssize_t) -> *mut PyVarObject;
+    #[cfg_attr(PyPy, link_name="PyPyObject_GC_Track")]
     pub fn PyObject_GC_Track(arg1: *mut c_void) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyObject_GC_UnTrack")]
     pub fn PyObject_GC_UnTrack(arg1: *mut c_void) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyObject_GC_Del")]
     pub fn PyObject_GC_Del(arg1: *mut c_void) -> ();
 }
 
@@ -17,5 +17,6 @@ impl Default for PyHash_FuncDef {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyHash_GetFuncDef")]
     pub fn PyHash_GetFuncDef() -> *mut PyHash_FuncDef;
 }
\ No newline at end of file
@@ -3,19 +3,27 @@ use libc::size_t;
 
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyMem_RawMalloc")]
     pub fn PyMem_RawMalloc(size: size_t) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyMem_RawCalloc")]
     pub fn PyMem_RawCalloc(nelem: size_t, elsize: size_t)
      -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyMem_RawRealloc")]
     pub fn PyMem_RawRealloc(ptr: *mut c_void, new_size: size_t)
      -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyMem_RawFree")]
     pub fn PyMem_RawFree(ptr: *mut c_void) -> ();
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyMem_Malloc")]
     pub fn PyMem_Malloc(size: size_t) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyMem_Calloc")]
     pub fn PyMem_Calloc(nelem: size_t, elsize: size_t) -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyMem_Realloc")]
     pub fn PyMem_Realloc(ptr: *mut c_void, new_size: size_t)
      -> *mut c_void;
+    #[cfg_attr(PyPy, link_name="PyPyMem_Free")]
     pub fn PyMem_Free(ptr: *mut c_void) -> ();
 }
 
@@ -51,10 +59,13 @@ pub struct PyMemAllocatorEx {
 
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyMem_GetAllocator")]
     pub fn PyMem_GetAllocator(domain: PyMemAllocatorDomain,
                               allocator: *mut PyMemAllocatorEx) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyMem_SetAllocator")]
     pub fn PyMem_SetAllocator(domain: PyMemAllocatorDomain,
                               allocator: *mut PyMemAllocatorEx) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyMem_SetupDebugHooks")]
     pub fn PyMem_SetupDebugHooks() -> ();
 }
 
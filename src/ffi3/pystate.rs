@@ -9,24 +9,36 @@ pub enum PyInterpreterState { }
 pub enum PyThreadState { }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyInterpreterState_New")]
     pub fn PyInterpreterState_New() -> *mut PyInterpreterState;
+    #[cfg_attr(PyPy, link_name="PyPyInterpreterState_Clear")]
     pub fn PyInterpreterState_Clear(arg1: *mut PyInterpreterState) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyInterpreterState_Delete")]
     pub fn PyInterpreterState_Delete(arg1: *mut PyInterpreterState) -> ();
     //fn _PyState_AddModule(arg1: *mut PyObject,
     //                      arg2: *mut PyModuleDef) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyState_FindModule")]
     pub fn PyState_FindModule(arg1: *mut PyModuleDef) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_New")]
     pub fn PyThreadState_New(arg1: *mut PyInterpreterState)
      -> *mut PyThreadState;
     //fn _PyThreadState_Prealloc(arg1: *mut PyInterpreterState)
     // -> *mut PyThreadState;
     //fn _PyThreadState_Init(arg1: *mut PyThreadState) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_Clear")]
     pub fn PyThreadState_Clear(arg1: *mut PyThreadState) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_Delete")]
     pub fn PyThreadState_Delete(arg1: *mut PyThreadState) -> ();
     #[cfg(py_sys_config="WITH_THREAD")]
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_DeleteCurrent")]
     pub fn PyThreadState_DeleteCurrent() -> ();
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_Get")]
     pub fn PyThreadState_Get() -> *mut PyThreadState;
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_Swap")]
     pub fn PyThreadState_Swap(arg1: *mut PyThreadState) -> *mut PyThreadState;
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_GetDict")]
     pub fn PyThreadState_GetDict() -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyThreadState_SetAsyncExc")]
     pub fn PyThreadState_SetAsyncExc(arg1: c_long,
                                      arg2: *mut PyObject) -> c_int;
 }
@@ -39,8 +51,11 @@ pub enum PyGILState_STATE {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyGILState_Ensure")]
     pub fn PyGILState_Ensure() -> PyGILState_STATE;
+    #[cfg_attr(PyPy, link_name="PyPyGILState_Release")]
     pub fn PyGILState_Release(arg1: PyGILState_STATE) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyGILState_GetThisThreadState")]
     pub fn PyGILState_GetThisThreadState() -> *mut PyThreadState;
 }
 
@@ -49,3 +64,8 @@ pub unsafe fn PyThreadState_GET() -> *mut PyThreadState {
     PyThreadState_Get()
 }
 
+#[cfg(all(Py_3_9, not(PyPy)))]
+#[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    pub fn PyInterpreterState_Get() -> *mut PyInterpreterState;
+    pub fn PyThreadState_GetInterpreter(tstate: *mut PyThreadState) -> *mut PyInterpreterState;
+}
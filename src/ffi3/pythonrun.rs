@@ -1,6 +1,6 @@
 use std::os::raw::{c_char, c_int};
 use std::ptr;
-use libc::{wchar_t, FILE};
+use libc::{wchar_t, size_t, FILE};
 use ffi3::object::*;
 use ffi3::pystate::PyThreadState;
 #[cfg(not(Py_LIMITED_API))]
@@ -9,15 +9,25 @@ use ffi3::pyarena::PyArena;
 // TODO: PyCF_MASK etc. constants
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" { // TODO: these moved to pylifecycle.h
+    #[cfg_attr(PyPy, link_name="PyPy_SetProgramName")]
     pub fn Py_SetProgramName(arg1: *mut wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_GetProgramName")]
     pub fn Py_GetProgramName() -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_SetPythonHome")]
     pub fn Py_SetPythonHome(arg1: *mut wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_GetPythonHome")]
     pub fn Py_GetPythonHome() -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_Initialize")]
     pub fn Py_Initialize() -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_InitializeEx")]
     pub fn Py_InitializeEx(arg1: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_Finalize")]
     pub fn Py_Finalize() -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_IsInitialized")]
     pub fn Py_IsInitialized() -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPy_NewInterpreter")]
     pub fn Py_NewInterpreter() -> *mut PyThreadState;
+    #[cfg_attr(PyPy, link_name="PyPy_EndInterpreter")]
     pub fn Py_EndInterpreter(arg1: *mut PyThreadState) -> ();
 }
 
@@ -33,42 +43,52 @@ pub enum _mod {}
 
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyRun_SimpleStringFlags")]
     pub fn PyRun_SimpleStringFlags(arg1: *const c_char,
                                    arg2: *mut PyCompilerFlags)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyRun_AnyFileFlags")]
     pub fn PyRun_AnyFileFlags(arg1: *mut FILE, arg2: *const c_char,
                               arg3: *mut PyCompilerFlags) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyRun_AnyFileExFlags")]
     pub fn PyRun_AnyFileExFlags(fp: *mut FILE,
                                 filename: *const c_char,
                                 closeit: c_int,
                                 flags: *mut PyCompilerFlags) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyRun_SimpleFileExFlags")]
     pub fn PyRun_SimpleFileExFlags(fp: *mut FILE,
                                    filename: *const c_char,
                                    closeit: c_int,
                                    flags: *mut PyCompilerFlags)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyRun_InteractiveOneFlags")]
     pub fn PyRun_InteractiveOneFlags(fp: *mut FILE,
                                      filename: *const c_char,
                                      flags: *mut PyCompilerFlags)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyRun_InteractiveOneObject")]
     pub fn PyRun_InteractiveOneObject(fp: *mut FILE, filename: *mut PyObject,
                                       flags: *mut PyCompilerFlags)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyRun_InteractiveLoopFlags")]
     pub fn PyRun_InteractiveLoopFlags(fp: *mut FILE,
                                       filename: *const c_char,
                                       flags: *mut PyCompilerFlags)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyParser_ASTFromString")]
     pub fn PyParser_ASTFromString(s: *const c_char,
                                   filename: *const c_char,
                                   start: c_int,
                                   flags: *mut PyCompilerFlags,
                                   arena: *mut PyArena) -> *mut _mod;
+    #[cfg_attr(PyPy, link_name="PyPyParser_ASTFromStringObject")]
     pub fn PyParser_ASTFromStringObject(s: *const c_char,
                                         filename: *mut PyObject,
                                         start: c_int,
                                         flags: *mut PyCompilerFlags,
                                         arena: *mut PyArena)
      -> *mut _mod;
+    #[cfg_attr(PyPy, link_name="PyPyParser_ASTFromFile")]
     pub fn PyParser_ASTFromFile(fp: *mut FILE,
                                 filename: *const c_char,
                                 enc: *const c_char,
@@ -78,6 +98,7 @@ pub enum _mod {}
                                 flags: *mut PyCompilerFlags,
                                 errcode: *mut c_int,
                                 arena: *mut PyArena) -> *mut _mod;
+    #[cfg_attr(PyPy, link_name="PyPyParser_ASTFromFileObject")]
     pub fn PyParser_ASTFromFileObject(fp: *mut FILE, filename: *mut PyObject,
                                       enc: *const c_char,
                                       start: c_int,
@@ -104,10 +125,12 @@ pub unsafe fn PyParser_SimpleParseFile(fp: *mut FILE, s: *const c_char, b: c_int
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyParser_SimpleParseStringFlags")]
     pub fn PyParser_SimpleParseStringFlags(arg1: *const c_char,
                                            arg2: c_int,
                                            arg3: c_int)
      -> *mut _node;
+    #[cfg_attr(PyPy, link_name="PyPyParser_SimpleParseStringFlagsFilename")]
     pub fn PyParser_SimpleParseStringFlagsFilename(arg1:
                                                        *const c_char,
                                                    arg2:
@@ -116,21 +139,25 @@ pub unsafe fn PyParser_SimpleParseFile(fp: *mut FILE, s: *const c_char, b: c_int
                                                    arg4: c_int)
      -> *mut _node;
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPyParser_SimpleParseFileFlags")]
     pub fn PyParser_SimpleParseFileFlags(arg1: *mut FILE,
                                          arg2: *const c_char,
                                          arg3: c_int,
                                          arg4: c_int)
      -> *mut _node;
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPyRun_StringFlags")]
     pub fn PyRun_StringFlags(arg1: *const c_char, arg2: c_int,
                              arg3: *mut PyObject, arg4: *mut PyObject,
                              arg5: *mut PyCompilerFlags) -> *mut PyObject;
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPyRun_FileExFlags")]
     pub fn PyRun_FileExFlags(fp: *mut FILE, filename: *const c_char,
                              start: c_int, globals: *mut PyObject,
                              locals: *mut PyObject, closeit: c_int,
                              flags: *mut PyCompilerFlags) -> *mut PyObject;
     #[cfg(Py_LIMITED_API)]
+    #[cfg_attr(PyPy, link_name="PyPy_CompileString")]
     pub fn Py_CompileString(string: *const c_char,
                             p: *const c_char, s: c_int)
      -> *mut PyObject;
@@ -147,46 +174,68 @@ pub unsafe fn Py_CompileStringFlags(string: *const c_char, p: *const c_char, s:
 }
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPy_CompileStringExFlags")]
     pub fn Py_CompileStringExFlags(str: *const c_char,
                                    filename: *const c_char,
                                    start: c_int,
                                    flags: *mut PyCompilerFlags,
                                    optimize: c_int) -> *mut PyObject;
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPy_CompileStringObject")]
     pub fn Py_CompileStringObject(str: *const c_char,
                                   filename: *mut PyObject,
                                   start: c_int,
                                   flags: *mut PyCompilerFlags,
                                   optimize: c_int) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPy_SymtableString")]
     pub fn Py_SymtableString(str: *const c_char,
                              filename: *const c_char,
                              start: c_int) -> *mut symtable;
     #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name="PyPy_SymtableStringObject")]
     pub fn Py_SymtableStringObject(str: *const c_char,
                                    filename: *mut PyObject,
                                    start: c_int)
      -> *mut symtable;
 
+    #[cfg_attr(PyPy, link_name="PyPyErr_Print")]
     pub fn PyErr_Print() -> ();
+    #[cfg_attr(PyPy, link_name="PyPyErr_PrintEx")]
     pub fn PyErr_PrintEx(arg1: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyErr_Display")]
     pub fn PyErr_Display(arg1: *mut PyObject, arg2: *mut PyObject,
                          arg3: *mut PyObject) -> ();
 
     // TODO: these moved to pylifecycle.h
+    #[cfg_attr(PyPy, link_name="PyPy_AtExit")]
     pub fn Py_AtExit(func: Option<extern "C" fn() -> ()>)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPy_Exit")]
     pub fn Py_Exit(arg1: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_Main")]
     pub fn Py_Main(argc: c_int, argv: *mut *mut wchar_t)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPy_GetProgramFullPath")]
     pub fn Py_GetProgramFullPath() -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_GetPrefix")]
     pub fn Py_GetPrefix() -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_GetExecPrefix")]
     pub fn Py_GetExecPrefix() -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_GetPath")]
     pub fn Py_GetPath() -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_SetPath")]
     pub fn Py_SetPath(arg1: *const wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPy_DecodeLocale")]
+    pub fn Py_DecodeLocale(arg: *const c_char, size: *mut size_t) -> *mut wchar_t;
+    #[cfg_attr(PyPy, link_name="PyPy_GetVersion")]
     pub fn Py_GetVersion() -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPy_GetPlatform")]
     pub fn Py_GetPlatform() -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPy_GetCopyright")]
     pub fn Py_GetCopyright() -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPy_GetCompiler")]
     pub fn Py_GetCompiler() -> *const c_char;
+    #[cfg_attr(PyPy, link_name="PyPy_GetBuildInfo")]
     pub fn Py_GetBuildInfo() -> *const c_char;
 }
 
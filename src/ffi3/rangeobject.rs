@@ -2,8 +2,11 @@ use std::os::raw::c_int;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyRange_Type")]
     pub static mut PyRange_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyRangeIter_Type")]
     pub static mut PyRangeIter_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyLongRangeIter_Type")]
     pub static mut PyLongRangeIter_Type: PyTypeObject;
 }
 
@@ -3,8 +3,11 @@ use ffi3::pyport::Py_ssize_t;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPySet_Type")]
     pub static mut PySet_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyFrozenSet_Type")]
     pub static mut PyFrozenSet_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPySetIter_Type")]
     pub static mut PySetIter_Type: PyTypeObject;
 }
 
@@ -36,15 +39,23 @@ pub unsafe fn PyFrozenSet_Check(ob : *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPySet_New")]
     pub fn PySet_New(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyFrozenSet_New")]
     pub fn PyFrozenSet_New(arg1: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPySet_Size")]
     pub fn PySet_Size(anyset: *mut PyObject) -> Py_ssize_t;
+    #[cfg_attr(PyPy, link_name="PyPySet_Clear")]
     pub fn PySet_Clear(set: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySet_Contains")]
     pub fn PySet_Contains(anyset: *mut PyObject, key: *mut PyObject)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySet_Discard")]
     pub fn PySet_Discard(set: *mut PyObject, key: *mut PyObject)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySet_Add")]
     pub fn PySet_Add(set: *mut PyObject, key: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySet_Pop")]
     pub fn PySet_Pop(set: *mut PyObject) -> *mut PyObject;
 }
 
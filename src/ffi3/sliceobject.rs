@@ -3,6 +3,7 @@ use ffi3::pyport::Py_ssize_t;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="_PyPy_EllipsisObject")]
     static mut _Py_EllipsisObject: PyObject;
 }
 
@@ -12,7 +13,9 @@ pub unsafe fn Py_Ellipsis() -> *mut PyObject {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPySlice_Type")]
     pub static mut PySlice_Type: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyEllipsis_Type")]
     pub static mut PyEllipsis_Type: PyTypeObject;
 }
 
@@ -22,11 +25,14 @@ pub unsafe fn PySlice_Check(op: *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPySlice_New")]
     pub fn PySlice_New(start: *mut PyObject, stop: *mut PyObject,
                        step: *mut PyObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPySlice_GetIndices")]
     pub fn PySlice_GetIndices(r: *mut PyObject, length: Py_ssize_t,
                               start: *mut Py_ssize_t, stop: *mut Py_ssize_t,
                               step: *mut Py_ssize_t) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySlice_GetIndicesEx")]
     pub fn PySlice_GetIndicesEx(r: *mut PyObject, length: Py_ssize_t,
                                 start: *mut Py_ssize_t, stop: *mut Py_ssize_t,
                                 step: *mut Py_ssize_t,
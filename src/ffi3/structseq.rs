@@ -19,11 +19,15 @@ pub struct PyStructSequence_Desc {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyStructSequence_NewType")]
     pub fn PyStructSequence_NewType(desc: *mut PyStructSequence_Desc)
      -> *mut PyTypeObject;
+    #[cfg_attr(PyPy, link_name="PyPyStructSequence_New")]
     pub fn PyStructSequence_New(_type: *mut PyTypeObject) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyStructSequence_SetItem")]
     pub fn PyStructSequence_SetItem(arg1: *mut PyObject, arg2: Py_ssize_t,
                                     arg3: *mut PyObject) -> ();
+    #[cfg_attr(PyPy, link_name="PyPyStructSequence_GetItem")]
     pub fn PyStructSequence_GetItem(arg1: *mut PyObject, arg2: Py_ssize_t)
      -> *mut PyObject;
 }
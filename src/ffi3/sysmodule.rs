@@ -3,22 +3,37 @@ use libc::wchar_t;
 use ffi3::object::PyObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPySys_GetObject")]
     pub fn PySys_GetObject(arg1: *const c_char) -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPySys_SetObject")]
     pub fn PySys_SetObject(arg1: *const c_char, arg2: *mut PyObject)
      -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySys_SetArgv")]
     pub fn PySys_SetArgv(arg1: c_int, arg2: *mut *mut wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_SetArgvEx")]
     pub fn PySys_SetArgvEx(arg1: c_int, arg2: *mut *mut wchar_t,
                            arg3: c_int) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_SetPath")]
     pub fn PySys_SetPath(arg1: *const wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_WriteStdout")]
     pub fn PySys_WriteStdout(format: *const c_char, ...) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_WriteStderr")]
     pub fn PySys_WriteStderr(format: *const c_char, ...) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_FormatStdout")]
     pub fn PySys_FormatStdout(format: *const c_char, ...) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_FormatStderr")]
     pub fn PySys_FormatStderr(format: *const c_char, ...) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_ResetWarnOptions")]
     pub fn PySys_ResetWarnOptions() -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_AddWarnOption")]
     pub fn PySys_AddWarnOption(arg1: *const wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_AddWarnOptionUnicode")]
     pub fn PySys_AddWarnOptionUnicode(arg1: *mut PyObject) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_HasWarnOptions")]
     pub fn PySys_HasWarnOptions() -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPySys_AddXOption")]
     pub fn PySys_AddXOption(arg1: *const wchar_t) -> ();
+    #[cfg_attr(PyPy, link_name="PyPySys_GetXOptions")]
     pub fn PySys_GetXOptions() -> *mut PyObject;
 }
 
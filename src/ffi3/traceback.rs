@@ -2,8 +2,11 @@ use std::os::raw::c_int;
 use ffi3::object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyTraceBack_Here")]
     pub fn PyTraceBack_Here(arg1: *mut ::ffi3::PyFrameObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyTraceBack_Print")]
     pub fn PyTraceBack_Print(arg1: *mut PyObject, arg2: *mut PyObject) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyTraceBack_Type")]
     pub static mut PyTraceBack_Type: PyTypeObject;
 }
 
@@ -3,16 +3,20 @@ use ffi3::pyport::Py_ssize_t;
 use ffi3::object::PyObject;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyErr_WarnEx")]
     pub fn PyErr_WarnEx(category: *mut PyObject,
                         message: *const c_char,
                         stack_level: Py_ssize_t) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyErr_WarnFormat")]
     pub fn PyErr_WarnFormat(category: *mut PyObject, stack_level: Py_ssize_t,
                             format: *const c_char, ...)
      -> c_int;
     #[cfg(Py_3_6)]
+    #[cfg_attr(PyPy, link_name="PyPyErr_ResourceWarning")]
     pub fn PyErr_ResourceWarning(
         source: *mut PyObject, stack_level: Py_ssize_t,
         format: *const c_char, ...) -> c_int;
+    #[cfg_attr(PyPy, link_name="PyPyErr_WarnExplicit")]
     pub fn PyErr_WarnExplicit(category: *mut PyObject,
                               message: *const c_char,
                               filename: *const c_char,
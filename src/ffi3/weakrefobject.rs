@@ -4,9 +4,12 @@ use ffi3::object::*;
 pub enum PyWeakReference {}
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
-    static mut _PyWeakref_RefType: PyTypeObject;
-    static mut _PyWeakref_ProxyType: PyTypeObject;
-    static mut _PyWeakref_CallableProxyType: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="_PyPyWeakref_RefType")]
+    pub static mut _PyWeakref_RefType: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="_PyPyWeakref_ProxyType")]
+    pub static mut _PyWeakref_ProxyType: PyTypeObject;
+    #[cfg_attr(PyPy, link_name="_PyPyWeakref_CallableProxyType")]
+    pub static mut _PyWeakref_CallableProxyType: PyTypeObject;
 }
 
 #[inline(always)]
@@ -31,10 +34,13 @@ pub unsafe fn PyWeakref_Check(op: *mut PyObject) -> c_int {
 }
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    #[cfg_attr(PyPy, link_name="PyPyWeakref_NewRef")]
     pub fn PyWeakref_NewRef(ob: *mut PyObject, callback: *mut PyObject)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyWeakref_NewProxy")]
     pub fn PyWeakref_NewProxy(ob: *mut PyObject, callback: *mut PyObject)
      -> *mut PyObject;
+    #[cfg_attr(PyPy, link_name="PyPyWeakref_GetObject")]
     pub fn PyWeakref_GetObject(_ref: *mut PyObject) -> *mut PyObject;
 }
 
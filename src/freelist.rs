@@ -4,9 +4,12 @@
 use std;
 
 use ffi;
+use pythonrun;
 use err::PyResult;
 use python::Python;
-use typeob::{PyTypeInfo, PyObjectAlloc};
+use typeob::{PyTypeInfo, PyObjectAlloc, free_object};
+#[cfg(Py_3)]
+use typeob::call_finalizer_from_dealloc;
 
 /// Implementing this trait for custom class adds free allocation list to class.
 /// The performance improvement applies to types that are often created and deleted in a row,
@@ -75,7 +78,13 @@ impl<T> PyObjectAlloc<T> for T where T: PyObjectWithFreeList {
 
     unsafe fn alloc(_py: Python) -> PyResult<*mut ffi::PyObject> {
         let obj = if let Some(obj) = <T as PyObjectWithFreeList>::get_free_list().pop() {
-            ffi::PyObject_Init(obj, <T as PyTypeInfo>::type_object());
+            let ty = <T as PyTypeInfo>::type_object();
+            ffi::PyObject_Init(obj, ty);
+            // the object kept the reference to its type while it was in the free list,
+            // since python 3.8 `PyObject_Init` takes another one for heap types
+            if pythonrun::is_python3_at_least(8) && ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
+                ffi::Py_DECREF(ty as *mut ffi::PyObject);
+            }
             obj
         } else {
             ffi::PyType_GenericAlloc(<T as PyTypeInfo>::type_object(), 0)
@@ -88,28 +97,12 @@ impl<T> PyObjectAlloc<T> for T where T: PyObjectWithFreeList {
     unsafe fn dealloc(py: Python, obj: *mut ffi::PyObject) {
         Self::drop(py, obj);
 
-        if ffi::PyObject_CallFinalizerFromDealloc(obj) < 0 {
+        if call_finalizer_from_dealloc(obj) < 0 {
             return
         }
 
         if let Some(obj) = <T as PyObjectWithFreeList>::get_free_list().insert(obj) {
-            match (*T::type_object()).tp_free {
-                Some(free) => free(obj as *mut ::c_void),
-                None => {
-                    let ty = ffi::Py_TYPE(obj);
-                    if ffi::PyType_IS_GC(ty) != 0 {
-                        ffi::PyObject_GC_Del(obj as *mut ::c_void);
-                    } else {
-                        ffi::PyObject_Free(obj as *mut ::c_void);
-                    }
-
-                    // For heap types, PyType_GenericAlloc calls INCREF on the type objects,
-                    // so we need to call DECREF here:
-                    if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
-                        ffi::Py_DECREF(ty as *mut ffi::PyObject);
-                    }
-                }
-            }
+            free_object::<T>(obj)
         }
     }
 
@@ -118,23 +111,7 @@ impl<T> PyObjectAlloc<T> for T where T: PyObjectWithFreeList {
         Self::drop(py, obj);
 
         if let Some(obj) = <T as PyObjectWithFreeList>::get_free_list().insert(obj) {
-            match (*T::type_object()).tp_free {
-                Some(free) => free(obj as *mut ::c_void),
-                None => {
-                    let ty = ffi::Py_TYPE(obj);
-                    if ffi::PyType_IS_GC(ty) != 0 {
-                        ffi::PyObject_GC_Del(obj as *mut ::c_void);
-                    } else {
-                        ffi::PyObject_Free(obj as *mut ::c_void);
-                    }
-
-                    // For heap types, PyType_GenericAlloc calls INCREF on the type objects,
-                    // so we need to call DECREF here:
-                    if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
-                        ffi::Py_DECREF(ty as *mut ffi::PyObject);
-                    }
-                }
-            }
+            free_object::<T>(obj)
         }
     }
 }
@@ -107,6 +107,7 @@ impl<T> Py<T> {
     pub unsafe fn from_owned_ptr(ptr: *mut ffi::PyObject) -> Py<T> {
         debug_assert!(!ptr.is_null() && ffi::Py_REFCNT(ptr) > 0,
                       format!("REFCNT: {:?} - {:?}", ptr, ffi::Py_REFCNT(ptr)));
+        pythonrun::track_pointer(ptr);
         Py(ptr, std::marker::PhantomData)
     }
 
@@ -270,6 +271,7 @@ impl<T> IntoPyPointer for Py<T> {
     #[must_use]
     fn into_ptr(self) -> *mut ffi::PyObject {
         let ptr = self.0;
+        unsafe { pythonrun::untrack_pointer(ptr); }
         std::mem::forget(self);
         ptr
     }
@@ -291,6 +293,55 @@ impl<T> Drop for Py<T> {
 }
 
 
+/// Weak reference to a Python object with specified type information.
+///
+/// `WeakPy<T>` is the counterpart of `Py<T>` that does not keep the object alive,
+/// it can be used to observe Python objects from Rust caches.
+/// The type of the object has to support weak references,
+/// i.e. `#[py::class(weakref)]` for custom classes.
+#[derive(Debug)]
+pub struct WeakPy<T>(PyObject, std::marker::PhantomData<T>);
+
+// `WeakPy<T>` is thread-safe, because any python related operations require a Python<'p> token.
+unsafe impl<T> Send for WeakPy<T> {}
+unsafe impl<T> Sync for WeakPy<T> {}
+
+impl<T> WeakPy<T> {
+    /// Creates a weak reference to the object referenced by `obj`.
+    pub fn new(py: Python, obj: &Py<T>) -> PyResult<WeakPy<T>> {
+        unsafe {
+            let ptr = ffi::PyWeakref_NewRef(obj.as_ptr(), std::ptr::null_mut());
+            Ok(WeakPy(PyObject::from_owned_ptr_or_err(py, ptr)?, std::marker::PhantomData))
+        }
+    }
+
+    /// Returns a new `Py<T>` for the referenced object,
+    /// or `None` if the object no longer exists.
+    pub fn upgrade(&self, _py: Python) -> Option<Py<T>> {
+        unsafe {
+            let ptr = ffi::PyWeakref_GetObject(self.0.as_ptr());
+            if ptr.is_null() || ptr == ffi::Py_None() {
+                None
+            } else {
+                Some(Py::from_borrowed_ptr(ptr))
+            }
+        }
+    }
+
+    /// Clone self, Calls Py_INCREF() on the weak reference object.
+    #[inline]
+    pub fn clone_ref(&self, py: Python) -> WeakPy<T> {
+        WeakPy(self.0.clone_ref(py), std::marker::PhantomData)
+    }
+}
+
+impl<T> Py<T> {
+    /// Creates a `WeakPy<T>` that does not keep the object alive.
+    pub fn downgrade(&self, py: Python) -> PyResult<WeakPy<T>> {
+        WeakPy::new(py, self)
+    }
+}
+
 impl<T> std::convert::From<Py<T>> for PyObject {
     #[inline]
     fn from(ob: Py<T>) -> Self {
@@ -136,9 +136,10 @@
 //! ```
 
 extern crate libc;
-extern crate spin;
 extern crate pyo3cls;
 #[macro_use] extern crate log;
+#[cfg(feature = "num-complex")]
+extern crate num_complex;
 
 #[cfg(not(Py_3))]
 mod ffi2;
@@ -162,8 +163,13 @@ pub use object::PyObject;
 pub use noargs::NoArgs;
 pub use typeob::{PyTypeInfo, PyRawObject, PyObjectAlloc};
 pub use python::{Python, ToPyPointer, IntoPyPointer, IntoPyDictPointer};
-pub use pythonrun::{GILGuard, GILPool, prepare_freethreaded_python, prepare_pyo3_library};
-pub use instance::{PyToken, PyObjectWithToken, AsPyRef, Py, PyNativeType};
+pub use pythonrun::{GILGuard, GILPool, prepare_freethreaded_python, prepare_pyo3_library,
+                    finalize, append_inittab, ModuleInitFunc,
+                    DeferredDecrefStats, deferred_decref_stats,
+                    set_deferred_decref_high_water_mark, DEFAULT_DECREF_HIGH_WATER_MARK};
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+pub use pythonrun::{SubInterpreter, NotPython, InterpreterConfig};
+pub use instance::{PyToken, PyObjectWithToken, AsPyRef, Py, WeakPy, PyNativeType};
 pub use conversion::{FromPyObject, PyTryFrom, PyTryInto,
                      ToPyObject, ToBorrowedObject, IntoPyObject, IntoPyTuple};
 pub mod class;
@@ -171,7 +177,8 @@ pub use class::*;
 
 /// Procedural macros
 pub mod py {
-    pub use pyo3cls::{proto, class, methods};
+    pub use pyo3cls::{proto, class, methods, exception};
+    pub use pyo3cls::{FromPyObject, ToPyObject, IntoPyObject};
 
     #[cfg(Py_3)]
     pub use pyo3cls::mod3init as modinit;
@@ -204,8 +211,12 @@ pub mod callback;
 pub mod typeob;
 #[doc(hidden)]
 pub mod argparse;
+#[doc(hidden)]
+pub mod derive_utils;
+#[cfg(not(Py_LIMITED_API))]
 pub mod buffer;
 pub mod freelist;
+pub mod warnings;
 pub mod prelude;
 
 // re-export for simplicity
@@ -29,6 +29,7 @@ impl PyObject {
     pub unsafe fn from_owned_ptr(_py: Python, ptr: *mut ffi::PyObject) -> PyObject {
         debug_assert!(!ptr.is_null() && ffi::Py_REFCNT(ptr) > 0,
                       format!("REFCNT: {:?} - {:?}", ptr, ffi::Py_REFCNT(ptr)));
+        pythonrun::track_pointer(ptr);
         PyObject(ptr)
     }
 
@@ -78,6 +79,7 @@ impl PyObject {
         debug_assert!(!ptr.is_null() && ffi::Py_REFCNT(ptr) > 0,
                       format!("REFCNT: {:?} - {:?}", ptr, ffi::Py_REFCNT(ptr)));
         ffi::Py_INCREF(ptr);
+        pythonrun::track_pointer(ptr);
         PyObject(ptr)
     }
 
@@ -308,6 +310,7 @@ impl IntoPyPointer for PyObject {
     #[must_use]
     fn into_ptr(self) -> *mut ffi::PyObject {
         let ptr = self.0;
+        unsafe { pythonrun::untrack_pointer(ptr); }
         std::mem::forget(self);
         ptr
     }
@@ -0,0 +1,212 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_void;
+
+use ffi;
+use object::PyObject;
+use python::{Python, ToPyPointer};
+use err::{PyErr, PyResult};
+use instance::PyObjectWithToken;
+use objectprotocol::ObjectProtocol;
+use objects::exc;
+
+/// Represents a Python `PyCapsule` object.
+///
+/// Capsules are used to share C-level data, such as function tables,
+/// between extension modules without going through Python calls.
+pub struct PyCapsule(PyObject);
+
+pyobject_convert!(PyCapsule);
+pyobject_nativetype!(PyCapsule, PyCapsule_Type, PyCapsule_CheckExact);
+
+/// Value stored in capsules created by `PyCapsule::new`.
+///
+/// The value is the first field, so the capsule pointer
+/// is a valid `*mut T` for consumers written in C.
+#[repr(C)]
+struct CapsuleContents<T> {
+    value: T,
+    name: CString,
+}
+
+impl PyCapsule {
+    /// Creates a new capsule that owns `value`.
+    ///
+    /// `name` should be the full dotted path the capsule is reachable at,
+    /// e.g. `"pkg.module.attribute"`, so other modules can locate it
+    /// with `PyCapsule::import`. `value` is dropped when the capsule is destroyed.
+    pub fn new<'p, T: 'static + Send>(py: Python<'p>, value: T, name: &str)
+                                      -> PyResult<&'p PyCapsule>
+    {
+        let name = CString::new(name)?;
+        let name_ptr = name.as_ptr();
+        let contents = Box::into_raw(Box::new(CapsuleContents { value, name }));
+
+        unsafe {
+            let capsule = ffi::PyCapsule_New(
+                contents as *mut c_void, name_ptr, Some(capsule_destructor::<T>));
+            if capsule.is_null() {
+                drop(Box::from_raw(contents));
+            }
+            py.from_owned_ptr_or_err(capsule)
+        }
+    }
+
+    /// Imports the capsule stored at the dotted path `name`,
+    /// e.g. `"pkg.module.attribute"`.
+    ///
+    /// Raises `AttributeError` if the object is not a capsule
+    /// or the capsule was not created with the same name.
+    pub fn import<'p>(py: Python<'p>, name: &str) -> PyResult<&'p PyCapsule> {
+        let c_name = CString::new(name)?;
+        let (module, attr) = match name.rfind('.') {
+            Some(idx) => (&name[..idx], &name[idx+1..]),
+            None => return Err(exc::ValueError::new(
+                format!("Capsule name must be a dotted path: {}", name))),
+        };
+
+        let obj = py.import(module)?.getattr(attr)?;
+        unsafe {
+            if ffi::PyCapsule_IsValid(obj.as_ptr(), c_name.as_ptr()) == 0 {
+                return Err(exc::AttributeError::new(
+                    format!("PyCapsule_Import \"{}\" is not valid", name)))
+            }
+            Ok(py.from_borrowed_ptr(obj.as_ptr()))
+        }
+    }
+
+    /// Returns the name of the capsule, if it has one.
+    pub fn name(&self) -> Option<&CStr> {
+        unsafe {
+            let name = ffi::PyCapsule_GetName(self.as_ptr());
+            if name.is_null() {
+                ffi::PyErr_Clear();
+                None
+            } else {
+                Some(CStr::from_ptr(name))
+            }
+        }
+    }
+
+    /// Returns the raw pointer stored in the capsule.
+    pub fn pointer(&self) -> PyResult<*mut c_void> {
+        unsafe {
+            let name = ffi::PyCapsule_GetName(self.as_ptr());
+            let ptr = ffi::PyCapsule_GetPointer(self.as_ptr(), name);
+            if ptr.is_null() {
+                Err(PyErr::fetch(self.py()))
+            } else {
+                Ok(ptr)
+            }
+        }
+    }
+
+    /// Returns a reference to the value stored in the capsule.
+    ///
+    /// `name` is the name the caller expects the capsule to have.
+    /// Raises `ValueError` if the capsule has a different name.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that capsules named `name` actually store a `T`.
+    /// Capsule names are the only type information available, so they should
+    /// be unique to the stored type.
+    pub unsafe fn reference<T>(&self, name: &str) -> PyResult<&T> {
+        let name = CString::new(name)?;
+        let ptr = ffi::PyCapsule_GetPointer(self.as_ptr(), name.as_ptr());
+        if ptr.is_null() {
+            Err(PyErr::fetch(self.py()))
+        } else {
+            Ok(&*(ptr as *const T))
+        }
+    }
+}
+
+unsafe extern "C" fn capsule_destructor<T>(capsule: *mut ffi::PyObject) {
+    let name = ffi::PyCapsule_GetName(capsule);
+    let ptr = ffi::PyCapsule_GetPointer(capsule, name);
+    drop(Box::from_raw(ptr as *mut CapsuleContents<T>));
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use python::Python;
+    use conversion::ToPyObject;
+    use objectprotocol::ObjectProtocol;
+    use objects::{PyModule, exc};
+    use super::PyCapsule;
+
+    #[repr(C)]
+    struct VTable {
+        add: fn(i32, i32) -> i32,
+    }
+
+    fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[test]
+    fn test_capsule_reference() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let capsule = PyCapsule::new(py, VTable { add }, "builtins.vtable").unwrap();
+        assert_eq!(capsule.name().unwrap().to_str().unwrap(), "builtins.vtable");
+        let vtable = unsafe { capsule.reference::<VTable>("builtins.vtable").unwrap() };
+        assert_eq!((vtable.add)(1, 2), 3);
+
+        let err = unsafe { capsule.reference::<VTable>("builtins.other").err().unwrap() };
+        assert!(err.is_instance::<exc::ValueError>(py));
+    }
+
+    #[test]
+    fn test_capsule_import() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let module = PyModule::new(py, "capsule_test").unwrap();
+        let capsule = PyCapsule::new(py, 42u64, "capsule_test.value").unwrap();
+        module.add("value", capsule).unwrap();
+        module.add("wrong", PyCapsule::new(py, 42u64, "other.value").unwrap()).unwrap();
+        module.add("number", 42).unwrap();
+        let sys_modules = py.import("sys").unwrap().get("modules").unwrap();
+        sys_modules.set_item("capsule_test", module).unwrap();
+
+        let imported = PyCapsule::import(py, "capsule_test.value").unwrap();
+        assert_eq!(unsafe { *imported.reference::<u64>("capsule_test.value").unwrap() }, 42);
+
+        let err = PyCapsule::import(py, "capsule_test.wrong").unwrap_err();
+        assert!(err.is_instance::<exc::AttributeError>(py));
+        let err = PyCapsule::import(py, "capsule_test.number").unwrap_err();
+        assert!(err.is_instance::<exc::AttributeError>(py));
+        let err = PyCapsule::import(py, "capsule_test.missing").unwrap_err();
+        assert!(err.is_instance::<exc::AttributeError>(py));
+    }
+
+    #[test]
+    fn test_capsule_drop() {
+        struct Counter(Arc<AtomicUsize>);
+
+        impl Drop for Counter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+            let capsule = PyCapsule::new(py, Counter(drops.clone()), "test.counter")
+                .unwrap().to_object(py);
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+            drop(capsule);
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+}
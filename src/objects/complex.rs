@@ -0,0 +1,292 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use std::ops;
+use std::os::raw::c_double;
+
+use ffi;
+use object::PyObject;
+use python::{ToPyPointer, Python};
+use err::PyResult;
+use instance::PyObjectWithToken;
+use objects::PyFloat;
+#[cfg(feature = "num-complex")]
+use err::PyErr;
+#[cfg(feature = "num-complex")]
+use objects::PyObjectRef;
+#[cfg(feature = "num-complex")]
+use conversion::{ToPyObject, IntoPyObject};
+
+/// Represents a Python `complex` object.
+///
+/// With the `num-complex` feature enabled, you can usually avoid directly
+/// working with this type by using [`ToPyObject`](trait.ToPyObject.html)
+/// and [extract](struct.PyObject.html#method.extract)
+/// with `num_complex::Complex<f32>`/`num_complex::Complex<f64>`.
+pub struct PyComplex(PyObject);
+
+pyobject_convert!(PyComplex);
+pyobject_nativetype!(PyComplex, PyComplex_Type, PyComplex_Check);
+
+
+impl PyComplex {
+    /// Creates a new Python `complex` object from the real and imaginary parts.
+    pub fn from_doubles(py: Python, real: c_double, imag: c_double) -> &PyComplex {
+        unsafe {
+            py.from_owned_ptr(ffi::PyComplex_FromDoubles(real, imag))
+        }
+    }
+
+    /// Returns the real part of the complex number.
+    pub fn real(&self) -> c_double {
+        unsafe { ffi::PyComplex_RealAsDouble(self.as_ptr()) }
+    }
+
+    /// Returns the imaginary part of the complex number.
+    pub fn imag(&self) -> c_double {
+        unsafe { ffi::PyComplex_ImagAsDouble(self.as_ptr()) }
+    }
+
+    /// Returns the absolute value of the complex number.
+    /// This is equivalent to the Python expression `abs(self)`.
+    ///
+    /// Fails with `OverflowError` if the result does not fit into a float.
+    pub fn abs(&self) -> PyResult<c_double> {
+        unsafe {
+            let val = self.py().from_owned_ptr_or_err::<PyFloat>(
+                ffi::PyNumber_Absolute(self.as_ptr()))?;
+            Ok(val.value())
+        }
+    }
+
+    /// Returns `self` raised to the power of `other`.
+    /// This is equivalent to the Python expression `self ** other`.
+    pub fn pow<'py>(&'py self, other: &'py PyComplex) -> PyResult<&'py PyComplex> {
+        unsafe {
+            self.py().from_owned_ptr_or_err(
+                ffi::PyNumber_Power(self.as_ptr(), other.as_ptr(), ffi::Py_None()))
+        }
+    }
+}
+
+macro_rules! complex_binop(
+    ($(#[$attr: meta])* $trait: ident, $fn: ident, $checked: ident, $ffi: ident) => {
+        impl PyComplex {
+            $(#[$attr])*
+            pub fn $checked<'py>(&'py self, other: &'py PyComplex) -> PyResult<&'py PyComplex> {
+                unsafe {
+                    self.py().from_owned_ptr_or_err(ffi::$ffi(self.as_ptr(), other.as_ptr()))
+                }
+            }
+        }
+
+        /// Panics if the Python operation raises an exception,
+        /// e.g. `ZeroDivisionError` for division.
+        /// Use the corresponding `checked_*` method to handle the error instead.
+        impl<'py> ops::$trait for &'py PyComplex {
+            type Output = &'py PyComplex;
+
+            fn $fn(self, other: &'py PyComplex) -> &'py PyComplex {
+                unsafe {
+                    self.py().from_owned_ptr(ffi::$ffi(self.as_ptr(), other.as_ptr()))
+                }
+            }
+        }
+    }
+);
+
+complex_binop!(
+    /// Returns `self + other`, or the Python exception raised by the addition.
+    Add, add, checked_add, PyNumber_Add);
+complex_binop!(
+    /// Returns `self - other`, or the Python exception raised by the subtraction.
+    Sub, sub, checked_sub, PyNumber_Subtract);
+complex_binop!(
+    /// Returns `self * other`, or the Python exception raised by the multiplication.
+    Mul, mul, checked_mul, PyNumber_Multiply);
+complex_binop!(
+    /// Returns `self / other`, or the Python exception raised by the division,
+    /// e.g. `ZeroDivisionError` if `other` is zero.
+    Div, div, checked_div, PyNumber_TrueDivide);
+
+impl<'py> ops::Neg for &'py PyComplex {
+    type Output = &'py PyComplex;
+
+    fn neg(self) -> &'py PyComplex {
+        unsafe {
+            self.py().from_owned_ptr(ffi::PyNumber_Negative(self.as_ptr()))
+        }
+    }
+}
+
+/// Reads the real and imaginary parts of `obj`.
+///
+/// Objects that are not `complex` instances are converted with `float()`
+/// and get an imaginary part of zero.
+#[cfg(feature = "num-complex")]
+fn complex_parts(obj: &PyObjectRef) -> PyResult<(c_double, c_double)> {
+    unsafe {
+        if ffi::PyComplex_Check(obj.as_ptr()) != 0 {
+            Ok((ffi::PyComplex_RealAsDouble(obj.as_ptr()),
+                ffi::PyComplex_ImagAsDouble(obj.as_ptr())))
+        } else {
+            let real = ffi::PyFloat_AsDouble(obj.as_ptr());
+            #[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+            {
+                if real == -1.0 && PyErr::occurred(obj.py()) {
+                    Err(PyErr::fetch(obj.py()))
+                } else {
+                    Ok((real, 0.0))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "num-complex")]
+macro_rules! complex_conversion(
+    ($float: ty) => {
+        impl ToPyObject for ::num_complex::Complex<$float> {
+            #[cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
+            fn to_object(&self, py: Python) -> PyObject {
+                PyComplex::from_doubles(py, self.re as c_double, self.im as c_double).into()
+            }
+        }
+
+        impl IntoPyObject for ::num_complex::Complex<$float> {
+            #[cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
+            fn into_object(self, py: Python) -> PyObject {
+                PyComplex::from_doubles(py, self.re as c_double, self.im as c_double).into()
+            }
+        }
+
+        pyobject_extract!(obj to ::num_complex::Complex<$float> => {
+            let (re, im) = complex_parts(obj)?;
+            Ok(::num_complex::Complex::new(re as $float, im as $float))
+        });
+    }
+);
+
+#[cfg(feature = "num-complex")]
+complex_conversion!(f32);
+#[cfg(feature = "num-complex")]
+complex_conversion!(f64);
+
+
+#[cfg(test)]
+mod test {
+    use python::Python;
+    use super::PyComplex;
+
+    #[test]
+    #[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+    fn test_from_doubles() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let complex = PyComplex::from_doubles(py, 3.0, 1.2);
+        assert_eq!(complex.real(), 3.0);
+        assert_eq!(complex.imag(), 1.2);
+    }
+
+    macro_rules! assert_approx_eq(
+        ($left: expr, $right: expr) => (
+            assert!(($left - $right).abs() < 1e-12, "{} != {}", $left, $right)
+        )
+    );
+
+    #[test]
+    fn test_arithmetic() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let l = PyComplex::from_doubles(py, 3.0, 1.2);
+        let r = PyComplex::from_doubles(py, 1.0, 2.6);
+
+        let res = l + r;
+        assert_approx_eq!(res.real(), 4.0);
+        assert_approx_eq!(res.imag(), 3.8);
+
+        let res = l - r;
+        assert_approx_eq!(res.real(), 2.0);
+        assert_approx_eq!(res.imag(), -1.4);
+
+        let res = l * r;
+        assert_approx_eq!(res.real(), -0.12);
+        assert_approx_eq!(res.imag(), 9.0);
+
+        let res = l / r;
+        assert_approx_eq!(res.real(), 0.788_659_793_814_432_9);
+        assert_approx_eq!(res.imag(), -0.850_515_463_917_525_7);
+
+        let res = -l;
+        assert_approx_eq!(res.real(), -3.0);
+        assert_approx_eq!(res.imag(), -1.2);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+    fn test_abs() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let val = PyComplex::from_doubles(py, 3.0, 4.0);
+        assert_eq!(val.abs().unwrap(), 5.0);
+
+        let big = PyComplex::from_doubles(py, 1.7e308, 1.7e308);
+        assert!(big.abs().is_err());
+    }
+
+    #[test]
+    fn test_checked_div() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let l = PyComplex::from_doubles(py, 3.0, 1.2);
+        let r = PyComplex::from_doubles(py, 1.0, 2.6);
+        let res = l.checked_div(r).unwrap();
+        assert_approx_eq!(res.real(), 0.788_659_793_814_432_9);
+        assert_approx_eq!(res.imag(), -0.850_515_463_917_525_7);
+
+        let zero = PyComplex::from_doubles(py, 0.0, 0.0);
+        assert!(l.checked_div(zero).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+    fn test_pow() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let l = PyComplex::from_doubles(py, 0.0, 1.0);
+        let r = PyComplex::from_doubles(py, 2.0, 0.0);
+        let res = l.pow(r).unwrap();
+        assert_eq!(res.real(), -1.0);
+        assert_eq!(res.imag(), 0.0);
+
+        let zero = PyComplex::from_doubles(py, 0.0, 0.0);
+        let neg = PyComplex::from_doubles(py, -1.0, 0.0);
+        assert!(zero.pow(neg).is_err());
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn test_num_complex_round_trip() {
+        use num_complex::Complex;
+        use conversion::ToPyObject;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let val = Complex::new(3.0f64, 1.2);
+        let obj = val.to_object(py);
+        assert_eq!(obj.extract::<Complex<f64>>(py).unwrap(), val);
+        assert_eq!(obj.extract::<Complex<f32>>(py).unwrap(), Complex::new(3.0f32, 1.2));
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn test_num_complex_from_float() {
+        use num_complex::Complex;
+        use conversion::ToPyObject;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = 2.5f64.to_object(py);
+        assert_eq!(obj.extract::<Complex<f64>>(py).unwrap(), Complex::new(2.5, 0.0));
+        assert!("foo".to_object(py).extract::<Complex<f64>>(py).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Wrappers for the types of the `datetime` module.
+//!
+//! The datetime C API is imported on first use of any of these types.
+
+use std::os::raw::c_int;
+
+use ffi;
+use ffi::PyDateTimeAPI;
+use object::PyObject;
+use python::{Python, ToPyPointer};
+use instance::PyObjectWithToken;
+use conversion::ToPyObject;
+use err::PyResult;
+
+/// Access to the date fields of `PyDate` and `PyDateTime`.
+pub trait PyDateAccess {
+    fn get_year(&self) -> i32;
+    fn get_month(&self) -> u8;
+    fn get_day(&self) -> u8;
+}
+
+/// Access to the time fields of `PyTime` and `PyDateTime`.
+pub trait PyTimeAccess {
+    fn get_hour(&self) -> u8;
+    fn get_minute(&self) -> u8;
+    fn get_second(&self) -> u8;
+    fn get_microsecond(&self) -> u32;
+    #[cfg(Py_3_6)]
+    fn get_fold(&self) -> u8;
+}
+
+/// Represents a Python `datetime.date` object.
+pub struct PyDate(PyObject);
+
+pyobject_convert!(PyDate);
+pyobject_nativetype!(PyDate, *PyDateTimeAPI.DateType, PyDate_Check);
+
+impl PyDate {
+    /// Creates a new `datetime.date`.
+    /// Returns a `ValueError` if the date is out of range.
+    pub fn new(py: Python, year: i32, month: u8, day: u8) -> PyResult<&PyDate> {
+        unsafe {
+            py.from_owned_ptr_or_err(
+                (PyDateTimeAPI.Date_FromDate)(
+                    year as c_int, c_int::from(month), c_int::from(day),
+                    PyDateTimeAPI.DateType))
+        }
+    }
+
+    /// Creates a new `datetime.date` from a POSIX timestamp.
+    /// This is equivalent to `datetime.date.fromtimestamp(timestamp)`.
+    pub fn from_timestamp(py: Python, timestamp: f64) -> PyResult<&PyDate> {
+        let args = (timestamp,).to_object(py);
+        unsafe {
+            py.from_owned_ptr_or_err(ffi::PyDate_FromTimestamp(args.as_ptr()))
+        }
+    }
+}
+
+impl PyDateAccess for PyDate {
+    fn get_year(&self) -> i32 {
+        unsafe { ffi::PyDateTime_GET_YEAR(self.as_ptr()) as i32 }
+    }
+
+    fn get_month(&self) -> u8 {
+        unsafe { ffi::PyDateTime_GET_MONTH(self.as_ptr()) as u8 }
+    }
+
+    fn get_day(&self) -> u8 {
+        unsafe { ffi::PyDateTime_GET_DAY(self.as_ptr()) as u8 }
+    }
+}
+
+/// Represents a Python `datetime.datetime` object.
+pub struct PyDateTime(PyObject);
+
+pyobject_convert!(PyDateTime);
+pyobject_nativetype!(PyDateTime, *PyDateTimeAPI.DateTimeType, PyDateTime_Check);
+
+impl PyDateTime {
+    /// Creates a new `datetime.datetime`.
+    /// Returns a `ValueError` if any of the fields is out of range.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn new<'p>(py: Python<'p>, year: i32, month: u8, day: u8,
+                   hour: u8, minute: u8, second: u8, microsecond: u32,
+                   tzinfo: Option<&PyTzInfo>) -> PyResult<&'p PyDateTime>
+    {
+        unsafe {
+            py.from_owned_ptr_or_err(
+                (PyDateTimeAPI.DateTime_FromDateAndTime)(
+                    year as c_int, c_int::from(month), c_int::from(day),
+                    c_int::from(hour), c_int::from(minute), c_int::from(second),
+                    microsecond as c_int, tzinfo_ptr(tzinfo), PyDateTimeAPI.DateTimeType))
+        }
+    }
+
+    /// Creates a new `datetime.datetime` from a POSIX timestamp.
+    /// This is equivalent to `datetime.datetime.fromtimestamp(timestamp, tzinfo)`.
+    pub fn from_timestamp<'p>(py: Python<'p>, timestamp: f64, tzinfo: Option<&PyTzInfo>)
+                              -> PyResult<&'p PyDateTime>
+    {
+        let args = (timestamp, tzinfo).to_object(py);
+        unsafe {
+            py.from_owned_ptr_or_err(ffi::PyDateTime_FromTimestamp(args.as_ptr()))
+        }
+    }
+
+    /// Returns the `tzinfo` of this datetime, or `None` for a naive datetime.
+    pub fn get_tzinfo(&self) -> Option<&PyTzInfo> {
+        unsafe {
+            tzinfo_opt(self.py(), ffi::PyDateTime_DATE_GET_TZINFO(self.as_ptr()))
+        }
+    }
+}
+
+impl PyDateAccess for PyDateTime {
+    fn get_year(&self) -> i32 {
+        unsafe { ffi::PyDateTime_GET_YEAR(self.as_ptr()) as i32 }
+    }
+
+    fn get_month(&self) -> u8 {
+        unsafe { ffi::PyDateTime_GET_MONTH(self.as_ptr()) as u8 }
+    }
+
+    fn get_day(&self) -> u8 {
+        unsafe { ffi::PyDateTime_GET_DAY(self.as_ptr()) as u8 }
+    }
+}
+
+impl PyTimeAccess for PyDateTime {
+    fn get_hour(&self) -> u8 {
+        unsafe { ffi::PyDateTime_DATE_GET_HOUR(self.as_ptr()) as u8 }
+    }
+
+    fn get_minute(&self) -> u8 {
+        unsafe { ffi::PyDateTime_DATE_GET_MINUTE(self.as_ptr()) as u8 }
+    }
+
+    fn get_second(&self) -> u8 {
+        unsafe { ffi::PyDateTime_DATE_GET_SECOND(self.as_ptr()) as u8 }
+    }
+
+    fn get_microsecond(&self) -> u32 {
+        unsafe { ffi::PyDateTime_DATE_GET_MICROSECOND(self.as_ptr()) as u32 }
+    }
+
+    #[cfg(Py_3_6)]
+    fn get_fold(&self) -> u8 {
+        unsafe { ffi::PyDateTime_DATE_GET_FOLD(self.as_ptr()) as u8 }
+    }
+}
+
+/// Represents a Python `datetime.time` object.
+pub struct PyTime(PyObject);
+
+pyobject_convert!(PyTime);
+pyobject_nativetype!(PyTime, *PyDateTimeAPI.TimeType, PyTime_Check);
+
+impl PyTime {
+    /// Creates a new `datetime.time`.
+    /// Returns a `ValueError` if any of the fields is out of range.
+    pub fn new<'p>(py: Python<'p>, hour: u8, minute: u8, second: u8, microsecond: u32,
+                   tzinfo: Option<&PyTzInfo>) -> PyResult<&'p PyTime>
+    {
+        unsafe {
+            py.from_owned_ptr_or_err(
+                (PyDateTimeAPI.Time_FromTime)(
+                    c_int::from(hour), c_int::from(minute), c_int::from(second),
+                    microsecond as c_int, tzinfo_ptr(tzinfo), PyDateTimeAPI.TimeType))
+        }
+    }
+
+    /// Returns the `tzinfo` of this time, or `None` for a naive time.
+    pub fn get_tzinfo(&self) -> Option<&PyTzInfo> {
+        unsafe {
+            tzinfo_opt(self.py(), ffi::PyDateTime_TIME_GET_TZINFO(self.as_ptr()))
+        }
+    }
+}
+
+impl PyTimeAccess for PyTime {
+    fn get_hour(&self) -> u8 {
+        unsafe { ffi::PyDateTime_TIME_GET_HOUR(self.as_ptr()) as u8 }
+    }
+
+    fn get_minute(&self) -> u8 {
+        unsafe { ffi::PyDateTime_TIME_GET_MINUTE(self.as_ptr()) as u8 }
+    }
+
+    fn get_second(&self) -> u8 {
+        unsafe { ffi::PyDateTime_TIME_GET_SECOND(self.as_ptr()) as u8 }
+    }
+
+    fn get_microsecond(&self) -> u32 {
+        unsafe { ffi::PyDateTime_TIME_GET_MICROSECOND(self.as_ptr()) as u32 }
+    }
+
+    #[cfg(Py_3_6)]
+    fn get_fold(&self) -> u8 {
+        unsafe { ffi::PyDateTime_TIME_GET_FOLD(self.as_ptr()) as u8 }
+    }
+}
+
+/// Represents a Python `datetime.timedelta` object.
+pub struct PyDelta(PyObject);
+
+pyobject_convert!(PyDelta);
+pyobject_nativetype!(PyDelta, *PyDateTimeAPI.DeltaType, PyDelta_Check);
+
+impl PyDelta {
+    /// Creates a new `datetime.timedelta`.
+    ///
+    /// If `normalize` is `true`, `seconds` and `microseconds` are carried
+    /// over into `days` the way the Python constructor does it.
+    /// Returns an `OverflowError` if the result is out of range.
+    pub fn new(py: Python, days: i32, seconds: i32, microseconds: i32, normalize: bool)
+               -> PyResult<&PyDelta>
+    {
+        unsafe {
+            py.from_owned_ptr_or_err(
+                (PyDateTimeAPI.Delta_FromDelta)(
+                    days as c_int, seconds as c_int, microseconds as c_int,
+                    normalize as c_int, PyDateTimeAPI.DeltaType))
+        }
+    }
+
+    /// Number of days, between -999999999 and 999999999.
+    pub fn get_days(&self) -> i32 {
+        unsafe { ffi::PyDateTime_DELTA_GET_DAYS(self.as_ptr()) as i32 }
+    }
+
+    /// Number of seconds, between 0 and 86399.
+    pub fn get_seconds(&self) -> i32 {
+        unsafe { ffi::PyDateTime_DELTA_GET_SECONDS(self.as_ptr()) as i32 }
+    }
+
+    /// Number of microseconds, between 0 and 999999.
+    pub fn get_microseconds(&self) -> i32 {
+        unsafe { ffi::PyDateTime_DELTA_GET_MICROSECONDS(self.as_ptr()) as i32 }
+    }
+}
+
+/// Represents a Python `datetime.tzinfo` object.
+pub struct PyTzInfo(PyObject);
+
+pyobject_convert!(PyTzInfo);
+pyobject_nativetype!(PyTzInfo, *PyDateTimeAPI.TZInfoType, PyTZInfo_Check);
+
+fn tzinfo_ptr(tzinfo: Option<&PyTzInfo>) -> *mut ffi::PyObject {
+    match tzinfo {
+        Some(tzinfo) => tzinfo.as_ptr(),
+        None => unsafe { ffi::Py_None() },
+    }
+}
+
+unsafe fn tzinfo_opt(py: Python, ptr: *mut ffi::PyObject) -> Option<&PyTzInfo> {
+    if ptr.is_null() || ptr == ffi::Py_None() {
+        None
+    } else {
+        Some(py.from_borrowed_ptr(ptr))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use python::Python;
+    use conversion::PyTryFrom;
+    use objects::{PyDict, PyObjectRef};
+    use super::{PyDate, PyDateTime, PyTime, PyDelta, PyTzInfo, PyDateAccess, PyTimeAccess};
+
+    fn eval<'p>(py: Python<'p>, code: &str) -> &'p PyObjectRef {
+        let locals = PyDict::new(py);
+        locals.set_item("datetime", py.import("datetime").unwrap()).unwrap();
+        py.eval(code, None, Some(locals)).unwrap()
+    }
+
+    #[test]
+    fn test_date() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let date = PyDate::new(py, 2018, 1, 31).unwrap();
+        assert_eq!(date.get_year(), 2018);
+        assert_eq!(date.get_month(), 1);
+        assert_eq!(date.get_day(), 31);
+        assert!(PyDate::new(py, 2018, 2, 30).is_err());
+
+        let ob = eval(py, "datetime.date(1999, 12, 24)");
+        let date = PyDate::try_from(ob).unwrap();
+        assert_eq!((date.get_year(), date.get_month(), date.get_day()), (1999, 12, 24));
+        assert!(PyDateTime::try_from(ob).is_err());
+    }
+
+    #[test]
+    fn test_date_from_timestamp() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let date = PyDate::from_timestamp(py, 100.0).unwrap();
+        let expected = eval(py, "datetime.date.fromtimestamp(100)");
+        assert_eq!(date.get_year(), PyDate::try_from(expected).unwrap().get_year());
+    }
+
+    #[test]
+    fn test_datetime() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dt = PyDateTime::new(py, 2018, 1, 31, 12, 30, 15, 999_999, None).unwrap();
+        assert_eq!(dt.get_year(), 2018);
+        assert_eq!(dt.get_month(), 1);
+        assert_eq!(dt.get_day(), 31);
+        assert_eq!(dt.get_hour(), 12);
+        assert_eq!(dt.get_minute(), 30);
+        assert_eq!(dt.get_second(), 15);
+        assert_eq!(dt.get_microsecond(), 999_999);
+        assert!(dt.get_tzinfo().is_none());
+
+        // datetime is a subclass of date
+        let ob: &PyObjectRef = dt.into();
+        assert!(PyDate::try_from(ob).is_ok());
+        assert!(PyTime::try_from(ob).is_err());
+    }
+
+    #[test]
+    fn test_datetime_tzinfo() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let utc = PyTzInfo::try_from(eval(py, "datetime.timezone.utc")).unwrap();
+        let dt = PyDateTime::new(py, 2018, 1, 31, 0, 0, 0, 0, Some(utc)).unwrap();
+        assert!(dt.get_tzinfo().unwrap() == utc);
+
+        let dt = PyDateTime::from_timestamp(py, 0.0, Some(utc)).unwrap();
+        assert_eq!((dt.get_year(), dt.get_month(), dt.get_day()), (1970, 1, 1));
+        assert_eq!(dt.get_hour(), 0);
+    }
+
+    #[test]
+    fn test_time() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let time = PyTime::new(py, 23, 59, 58, 1, None).unwrap();
+        assert_eq!(time.get_hour(), 23);
+        assert_eq!(time.get_minute(), 59);
+        assert_eq!(time.get_second(), 58);
+        assert_eq!(time.get_microsecond(), 1);
+        assert!(time.get_tzinfo().is_none());
+        assert!(PyTime::new(py, 24, 0, 0, 0, None).is_err());
+    }
+
+    #[test]
+    fn test_delta() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let delta = PyDelta::new(py, 1, 86_401, -1, true).unwrap();
+        assert_eq!(delta.get_days(), 2);
+        assert_eq!(delta.get_seconds(), 0);
+        assert_eq!(delta.get_microseconds(), 999_999);
+
+        let ob = eval(py, "datetime.timedelta(days=-1)");
+        assert_eq!(PyDelta::try_from(ob).unwrap().get_days(), -1);
+    }
+}
@@ -5,10 +5,11 @@ use std::{mem, collections, hash, cmp};
 
 use ffi;
 use object::PyObject;
-use instance::{Py, PyObjectWithToken};
+use instance::{Py, PyObjectWithToken, AsPyRef};
 use python::{Python, ToPyPointer, IntoPyPointer, IntoPyDictPointer};
-use conversion::{ToPyObject, ToBorrowedObject, IntoPyObject};
-use objects::{PyObjectRef, PyList};
+use conversion::{ToPyObject, ToBorrowedObject, IntoPyObject, FromPyObject, PyTryFrom};
+use objects::{PyObjectRef, PyList, exc};
+use objectprotocol::ObjectProtocol;
 use err::{self, PyResult, PyErr};
 
 /// Represents a Python `dict`.
@@ -257,6 +258,71 @@ impl <K, V> IntoPyDictPointer for collections::BTreeMap<K, V>
     }
 }
 
+/// Raises a `TypeError` with `err` as its `__cause__`,
+/// adding the offending mapping key to the message.
+fn wrong_mapping_item(py: Python, what: &str, key: &PyObjectRef, err: PyErr) -> PyErr {
+    let msg = match err.clone_ref(py).into_object(py).as_ref(py).str() {
+        Ok(s) => format!("Failed to extract {} for key {:?}: {}", what, key, s.to_string_lossy()),
+        Err(_) => format!("Failed to extract {} for key {:?}", what, key),
+    };
+    err::chain_cause(py, exc::TypeError::new(msg), err)
+}
+
+/// Calls `f` for every `(key, value)` pair of `ob`.
+///
+/// `dict` instances are iterated directly, any other object is treated
+/// as a mapping and iterated through its `keys()` method.
+fn extract_mapping<'s, F>(ob: &'s PyObjectRef, mut f: F) -> PyResult<()>
+    where F: FnMut(&'s PyObjectRef, &'s PyObjectRef) -> PyResult<()>
+{
+    if let Ok(dict) = PyDict::try_from(ob) {
+        for (key, value) in dict.iter() {
+            f(key, value)?;
+        }
+    } else {
+        for key in ob.call_method0("keys")?.iter()? {
+            let key = key?;
+            f(key, ob.get_item(key)?)?;
+        }
+    }
+    Ok(())
+}
+
+impl<'source, K, V, S> FromPyObject<'source> for collections::HashMap<K, V, S>
+    where K: FromPyObject<'source> + hash::Hash + cmp::Eq,
+          V: FromPyObject<'source>,
+          S: hash::BuildHasher + Default
+{
+    fn extract(ob: &'source PyObjectRef) -> PyResult<Self> {
+        let py = ob.py();
+        let mut map = collections::HashMap::with_hasher(S::default());
+        extract_mapping(ob, |key, value| {
+            let k = key.extract().map_err(|e| wrong_mapping_item(py, "key", key, e))?;
+            let v = value.extract().map_err(|e| wrong_mapping_item(py, "value", key, e))?;
+            map.insert(k, v);
+            Ok(())
+        })?;
+        Ok(map)
+    }
+}
+
+impl<'source, K, V> FromPyObject<'source> for collections::BTreeMap<K, V>
+    where K: FromPyObject<'source> + cmp::Ord,
+          V: FromPyObject<'source>
+{
+    fn extract(ob: &'source PyObjectRef) -> PyResult<Self> {
+        let py = ob.py();
+        let mut map = collections::BTreeMap::new();
+        extract_mapping(ob, |key, value| {
+            let k = key.extract().map_err(|e| wrong_mapping_item(py, "key", key, e))?;
+            let v = value.extract().map_err(|e| wrong_mapping_item(py, "value", key, e))?;
+            map.insert(k, v);
+            Ok(())
+        })?;
+        Ok(map)
+    }
+}
+
 impl<K: ToPyObject, V: ToPyObject> IntoPyDictPointer for (K, V) {
     default fn into_dict_ptr(self, py: Python) -> *mut ffi::PyObject {
         let dict = PyDict::new(py);
@@ -300,7 +366,7 @@ mod test {
     use python::{Python, IntoPyDictPointer};
     use instance::AsPyRef;
     use conversion::{PyTryFrom, ToPyObject, IntoPyObject};
-    use objects::{PyDict, PyTuple};
+    use objects::{PyDict, PyTuple, exc};
     use {PyObject, ObjectProtocol};
 
     #[test]
@@ -657,4 +723,79 @@ mod test {
         assert!(py_map.len() == 1);
         assert!(py_map.get_item(1).unwrap().extract::<i32>().unwrap() == 1);
     }
+
+    #[test]
+    fn test_hashmap_from_python() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dict = PyDict::new(py);
+        dict.set_item(1, 10).unwrap();
+        dict.set_item(2, 20).unwrap();
+
+        let map: HashMap<i32, i32> = dict.extract().unwrap();
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&10), map.get(&1));
+        assert_eq!(Some(&20), map.get(&2));
+    }
+
+    #[test]
+    fn test_btreemap_from_python() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dict = PyDict::new(py);
+        dict.set_item(1, 10).unwrap();
+        dict.set_item(2, 20).unwrap();
+
+        let map: BTreeMap<i32, i32> = dict.extract().unwrap();
+        assert_eq!(vec![(1, 10), (2, 20)], map.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_hashmap_from_mapping() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let ob = py.eval("__import__('types').MappingProxyType({1: 10})", None, None).unwrap();
+        let map: HashMap<i32, i32> = ob.extract().unwrap();
+        assert_eq!(1, map.len());
+        assert_eq!(Some(&10), map.get(&1));
+    }
+
+    #[test]
+    fn test_hashmap_from_python_invalid_value() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dict = PyDict::new(py);
+        dict.set_item(7, "foo").unwrap();
+
+        let err = dict.extract::<HashMap<i32, i32>>().unwrap_err();
+        assert!(err.is_instance::<exc::TypeError>(py));
+        #[cfg(Py_3)]
+        assert!(err.cause(py).unwrap().is_instance::<exc::TypeError>(py));
+        let msg: String = err.into_object(py).as_ref(py).str().unwrap().extract().unwrap();
+        assert!(msg.starts_with("Failed to extract value for key 7"));
+    }
+
+    #[cfg(Py_3)]
+    #[test]
+    fn test_wrong_mapping_item_cause() {
+        use err::PyErr;
+        use super::wrong_mapping_item;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        // `UnicodeEncodeError` takes five constructor arguments,
+        // it must not be re-created from the message
+        let cause = py.eval("UnicodeEncodeError('utf-8', 'x', 0, 1, 'bad')", None, None).unwrap();
+        let key = 7.to_object(py);
+        let err = wrong_mapping_item(py, "key", key.as_ref(py), PyErr::from_instance(cause));
+        assert!(err.is_instance::<exc::TypeError>(py));
+        assert!(err.cause(py).unwrap().is_instance::<exc::UnicodeEncodeError>(py));
+        let msg: String = err.into_object(py).as_ref(py).str().unwrap().extract().unwrap();
+        assert!(msg.starts_with("Failed to extract key for key 7"));
+    }
 }
@@ -130,6 +130,19 @@ exc_type!(IOError, PyExc_IOError);
 #[cfg(target_os="windows")]
 exc_type!(WindowsError, PyExc_WindowsError);
 
+exc_type!(Warning, PyExc_Warning);
+exc_type!(UserWarning, PyExc_UserWarning);
+exc_type!(DeprecationWarning, PyExc_DeprecationWarning);
+exc_type!(PendingDeprecationWarning, PyExc_PendingDeprecationWarning);
+exc_type!(SyntaxWarning, PyExc_SyntaxWarning);
+exc_type!(RuntimeWarning, PyExc_RuntimeWarning);
+exc_type!(FutureWarning, PyExc_FutureWarning);
+exc_type!(ImportWarning, PyExc_ImportWarning);
+exc_type!(UnicodeWarning, PyExc_UnicodeWarning);
+exc_type!(BytesWarning, PyExc_BytesWarning);
+#[cfg(Py_3)]
+exc_type!(ResourceWarning, PyExc_ResourceWarning);
+
 
 impl UnicodeDecodeError {
 
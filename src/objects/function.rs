@@ -0,0 +1,213 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+
+use ffi;
+use object::PyObject;
+use python::{Python, ToPyPointer};
+use err::{PyErr, PyResult};
+use conversion::IntoPyObject;
+use objects::{PyTuple, PyDict};
+use pythonrun::GILPool;
+use argparse;
+use callback;
+
+/// Represents a Python built-in function object
+/// (`builtin_function_or_method`).
+pub struct PyCFunction(PyObject);
+
+pyobject_convert!(PyCFunction);
+pyobject_nativetype!(PyCFunction, PyCFunction_Type, PyCFunction_Check);
+
+/// Name of the capsule that owns the boxed closure of a `PyCFunction`.
+const CLOSURE_CAPSULE_NAME: &'static [u8] = b"pyo3-closure\0";
+
+impl PyCFunction {
+    /// Creates a new Python callable object that invokes the Rust closure `f`.
+    ///
+    /// The closure receives the positional arguments tuple and the optional
+    /// keyword arguments dictionary, and its return value is converted into
+    /// a Python object. The closure is owned by the returned function object
+    /// and dropped once Python releases it.
+    ///
+    /// ```rust
+    /// # extern crate pyo3;
+    /// # use pyo3::{Python, PyCFunction, PyTuple, PyDict, PyResult, ObjectProtocol};
+    /// # fn main() {
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let add_one = PyCFunction::new_closure(
+    ///     py, "add_one", "Adds one to the argument",
+    ///     |args: &PyTuple, _kwargs: Option<&PyDict>| -> PyResult<i32> {
+    ///         Ok(args.get_item(0).extract::<i32>()? + 1)
+    ///     }).unwrap();
+    /// # }
+    /// ```
+    pub fn new_closure<'p, F, R>(py: Python<'p>, name: &str, doc: &str, f: F)
+                                 -> PyResult<&'p PyCFunction>
+        where F: Fn(&PyTuple, Option<&PyDict>) -> PyResult<R> + Send + 'static,
+              R: IntoPyObject
+    {
+        let name = CString::new(name)?;
+        let doc = CString::new(doc)?;
+
+        unsafe {
+            let closure = Box::into_raw(Box::new(Closure {
+                f: f,
+                def: ffi::PyMethodDef {
+                    ml_name: name.as_ptr(),
+                    ml_meth: Some(::std::mem::transmute::<ffi::PyCFunctionWithKeywords,
+                                                          ffi::PyCFunction>(run_closure::<F, R>)),
+                    ml_flags: ffi::METH_VARARGS | ffi::METH_KEYWORDS,
+                    ml_doc: doc.as_ptr(),
+                },
+                _name: name,
+                _doc: doc,
+            }));
+            let capsule = ffi::PyCapsule_New(
+                closure as *mut c_void,
+                CLOSURE_CAPSULE_NAME.as_ptr() as *const c_char,
+                Some(drop_closure::<F>));
+            if capsule.is_null() {
+                drop(Box::from_raw(closure));
+                return Err(PyErr::fetch(py))
+            }
+
+            // the function object keeps the capsule alive,
+            // which owns the method definition
+            let func = ffi::PyCFunction_NewEx(&mut (*closure).def, capsule, ptr::null_mut());
+            ffi::Py_DECREF(capsule);
+            py.from_owned_ptr_or_err(func)
+        }
+    }
+}
+
+/// Closure of a `PyCFunction` together with its method definition,
+/// owned by the capsule passed as `self` to the function.
+struct Closure<F> {
+    f: F,
+    def: ffi::PyMethodDef,
+    _name: CString,
+    _doc: CString,
+}
+
+unsafe fn get_closure<'a, F>(capsule: *mut ffi::PyObject) -> &'a F {
+    &(*(ffi::PyCapsule_GetPointer(
+        capsule, CLOSURE_CAPSULE_NAME.as_ptr() as *const c_char) as *const Closure<F>)).f
+}
+
+unsafe extern "C" fn drop_closure<F>(capsule: *mut ffi::PyObject) {
+    let closure = ffi::PyCapsule_GetPointer(
+        capsule, CLOSURE_CAPSULE_NAME.as_ptr() as *const c_char) as *mut Closure<F>;
+    drop(Box::from_raw(closure));
+}
+
+unsafe extern "C" fn run_closure<F, R>(capsule: *mut ffi::PyObject,
+                                       args: *mut ffi::PyObject,
+                                       kwargs: *mut ffi::PyObject) -> *mut ffi::PyObject
+    where F: Fn(&PyTuple, Option<&PyDict>) -> PyResult<R> + Send + 'static,
+          R: IntoPyObject
+{
+    let _pool = GILPool::new();
+    let py = Python::assume_gil_acquired();
+    let args = py.from_borrowed_ptr::<PyTuple>(args);
+    let kwargs = argparse::get_kwargs(py, kwargs);
+
+    let result = get_closure::<F>(capsule)(args, kwargs);
+    callback::cb_convert(callback::PyObjectCallbackConverter, py, result)
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use python::Python;
+    use conversion::ToPyObject;
+    use objectprotocol::ObjectProtocol;
+    use objects::{PyTuple, PyDict, PyList, exc};
+    use err::PyResult;
+    use super::PyCFunction;
+
+    #[test]
+    fn test_call_closure() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let f = PyCFunction::new_closure(
+            py, "add", "Adds its arguments",
+            |args: &PyTuple, kwargs: Option<&PyDict>| -> PyResult<i32> {
+                let mut sum = 0;
+                for arg in args.iter() {
+                    sum += arg.extract::<i32>()?;
+                }
+                if let Some(kwargs) = kwargs {
+                    for (_, value) in kwargs.iter() {
+                        sum += value.extract::<i32>()?;
+                    }
+                }
+                Ok(sum)
+            }).unwrap();
+
+        let d = PyDict::new(py);
+        d.set_item("f", f).unwrap();
+        assert_eq!(py.eval("f(1, 2, c=3)", None, Some(d)).unwrap()
+                   .extract::<i32>().unwrap(), 6);
+        assert_eq!(py.eval("f.__name__", None, Some(d)).unwrap()
+                   .extract::<String>().unwrap(), "add");
+        assert_eq!(py.eval("f.__doc__", None, Some(d)).unwrap()
+                   .extract::<String>().unwrap(), "Adds its arguments");
+
+        let err = py.eval("f('a')", None, Some(d)).unwrap_err();
+        assert!(err.is_instance::<exc::TypeError>(py));
+    }
+
+    #[test]
+    fn test_closure_as_sort_key() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let key = PyCFunction::new_closure(
+            py, "key", "",
+            |args: &PyTuple, _kwargs: Option<&PyDict>| -> PyResult<i32> {
+                Ok(-args.get_item(0).extract::<i32>()?)
+            }).unwrap();
+
+        let list = PyList::new(py, &[3, 1, 2]);
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("key", key).unwrap();
+        let sorted = py.eval("sorted", None, None).unwrap()
+            .call((list,), kwargs).unwrap();
+        assert_eq!(sorted.extract::<Vec<i32>>().unwrap(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_closure_dropped() {
+        struct Counter(Arc<AtomicUsize>);
+
+        impl Drop for Counter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+
+            let counter = Counter(drops.clone());
+            let f = PyCFunction::new_closure(
+                py, "f", "",
+                move |_args: &PyTuple, _kwargs: Option<&PyDict>| -> PyResult<usize> {
+                    Ok(counter.0.load(Ordering::SeqCst))
+                }).unwrap().to_object(py);
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+            drop(f);
+        }
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+}
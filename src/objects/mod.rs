@@ -11,6 +11,11 @@ pub use self::tuple::PyTuple;
 pub use self::dict::PyDict;
 pub use self::list::PyList;
 pub use self::floatob::PyFloat;
+pub use self::complex::PyComplex;
+pub use self::function::PyCFunction;
+pub use self::capsule::PyCapsule;
+pub use self::weakref::{PyWeakRef, PyWeakProxy};
+pub use self::traceback::PyTraceback;
 pub use self::sequence::PySequence;
 pub use self::slice::{PySlice, PySliceIndices};
 pub use self::set::{PySet, PyFrozenSet};
@@ -22,6 +27,10 @@ pub use self::string::{PyBytes, PyString};
 #[cfg(not(Py_3))]
 pub use self::string2::{PyBytes, PyString};
 
+#[cfg(all(Py_3, not(Py_LIMITED_API)))]
+pub use self::datetime::{PyDate, PyDateTime, PyTime, PyDelta, PyTzInfo,
+                         PyDateAccess, PyTimeAccess};
+
 #[cfg(Py_3)]
 pub use self::num3::PyLong;
 #[cfg(Py_3)]
@@ -93,6 +102,10 @@ macro_rules! pyobject_nativetype(
     };
 
     ($name: ident, $typeobject: ident, $checkfunction: ident) => {
+        pyobject_nativetype!($name, $crate::ffi::$typeobject, $checkfunction);
+    };
+
+    ($name: ident, $typeobject: expr, $checkfunction: ident) => {
         pyobject_nativetype!($name);
 
         impl $crate::typeob::PyTypeInfo for $name {
@@ -104,8 +117,8 @@ macro_rules! pyobject_nativetype(
             const OFFSET: isize = 0;
 
             #[inline]
-            unsafe fn type_object() -> &'static mut $crate::ffi::PyTypeObject {
-                &mut $crate::ffi::$typeobject
+            fn type_object() -> *mut $crate::ffi::PyTypeObject {
+                unsafe { &mut $typeobject }
             }
 
             #[cfg_attr(feature = "cargo-clippy", allow(not_unsafe_ptr_arg_deref))]
@@ -211,6 +224,11 @@ mod bytearray;
 mod tuple;
 mod list;
 mod floatob;
+mod complex;
+mod function;
+mod capsule;
+mod weakref;
+mod traceback;
 mod sequence;
 mod slice;
 mod stringdata;
@@ -218,6 +236,9 @@ mod stringutils;
 mod set;
 pub mod exc;
 
+#[cfg(all(Py_3, not(Py_LIMITED_API)))]
+mod datetime;
+
 #[cfg(Py_3)]
 mod num3;
 
@@ -4,16 +4,20 @@
 
 use std;
 use std::os::raw::c_char;
+#[cfg(Py_3)]
+use std::os::raw::{c_int, c_void};
+#[cfg(Py_3)]
+use std::any::Any;
 use std::ffi::{CStr, CString};
 
 use ffi;
-use typeob::{PyTypeInfo, initialize_type};
+use typeob::{PyTypeInfo, PyTypeObject, initialize_type};
 use conversion::{ToPyObject, IntoPyTuple};
 use object::PyObject;
 use python::{Python, ToPyPointer, IntoPyDictPointer};
 use objects::{PyObjectRef, PyDict, PyType, exc};
 use objectprotocol::ObjectProtocol;
-use instance::PyObjectWithToken;
+use instance::{PyObjectWithToken, AsPyRef};
 use err::{PyResult, PyErr};
 
 
@@ -124,20 +128,107 @@ impl PyModule {
     /// and adds the type to this module.
     pub fn add_class<T>(&self) -> PyResult<()> where T: PyTypeInfo
     {
-        let ty = unsafe {
-            let ty = <T as PyTypeInfo>::type_object();
-
-            if ((*ty).tp_flags & ffi::Py_TPFLAGS_READY) != 0 {
-                PyType::new::<T>()
-            } else {
-                // automatically initialize the class
-                initialize_type::<T>(self.py(), Some(self.name()?))
-                    .expect(
-                        format!("An error occurred while initializing class {}", T::NAME).as_ref());
-                PyType::new::<T>()
+        if !<T as PyTypeInfo>::type_storage().is_ready() {
+            // automatically initialize the class
+            initialize_type::<T>(self.py(), Some(self.name()?))
+                .expect(
+                    format!("An error occurred while initializing class {}", T::NAME).as_ref());
+        }
+        <T as PyTypeInfo>::type_storage().check_interpreter(T::NAME)?;
+
+        self.setattr(T::NAME, PyType::new::<T>())
+    }
+
+    /// Adds an exception type declared with `py_exception!` or `#[py::exception]`
+    /// to the module, under the `__name__` of the type.
+    ///
+    /// Fails with `TypeError` if `T` is not an exception type.
+    pub fn add_exception<T>(&self) -> PyResult<()> where T: PyTypeObject
+    {
+        let ty = T::type_object();
+        let ty = ty.as_ref(self.py());
+        if unsafe { ffi::PyExceptionClass_Check(ty.as_ptr()) } == 0 {
+            return Err(exc::TypeError::new(
+                format!("{} is not an exception type", ty.name())))
+        }
+        let name: String = ty.getattr("__name__")?.extract()?;
+        self.setattr(name, ty)
+    }
+}
+
+/// State of a module created by `#[py::modinit]`, stored in the module object.
+#[cfg(Py_3)]
+type ModuleState = *mut Box<Any + Send>;
+
+#[cfg(Py_3)]
+impl PyModule {
+    /// Stores `state` in the module.
+    ///
+    /// Every interpreter importing a `#[py::modinit]` module gets its own module object,
+    /// the state is the place for data which must not be shared between them.
+    /// The state is dropped with the module object and can be set only once.
+    pub fn set_state<S>(&self, state: S) -> PyResult<()> where S: Any + Send {
+        unsafe {
+            let slot = self.state_slot()?;
+            if !(*slot).is_null() {
+                return Err(PyErr::new::<exc::RuntimeError, _>(
+                    format!("state of module {} is already set", self.name()?)))
+            }
+            *slot = Box::into_raw(Box::new(Box::new(state) as Box<Any + Send>));
+        }
+        Ok(())
+    }
+
+    /// Returns the state stored with `set_state`.
+    ///
+    /// Fails if no state is set or it is not of type `S`.
+    pub fn state<S>(&self) -> PyResult<&S> where S: Any + Send {
+        unsafe {
+            let slot = self.state_slot()?;
+            if (*slot).is_null() {
+                return Err(PyErr::new::<exc::RuntimeError, _>(
+                    format!("state of module {} is not set", self.name()?)))
+            }
+            match (**slot).downcast_ref::<S>() {
+                Some(state) => Ok(state),
+                None => Err(PyErr::new::<exc::TypeError, _>(
+                    format!("state of module {} has a different type", self.name()?))),
             }
-        };
+        }
+    }
+
+    unsafe fn state_slot(&self) -> PyResult<*mut ModuleState> {
+        let def = ffi::PyModule_GetDef(self.as_ptr());
+        if def.is_null() || (*def).m_free != Some(free_module_state as ffi::freefunc) {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                format!("module {} has no state, only #[py::modinit] modules have one",
+                        self.name()?)))
+        }
+        Ok(ffi::PyModule_GetState(self.as_ptr()) as *mut ModuleState)
+    }
+
+    /// Prepares the definition of a `#[py::modinit]` module for multi-phase initialization
+    /// (PEP 489), `exec` initializes the module object created for each interpreter.
+    #[doc(hidden)]
+    pub unsafe fn init_def(def: *mut ffi::PyModuleDef, slots: *mut [ffi::PyModuleDef_Slot; 2],
+                           exec: unsafe extern "C" fn(*mut ffi::PyObject) -> c_int)
+                           -> *mut ffi::PyObject
+    {
+        (*slots)[0] = ffi::PyModuleDef_Slot { slot: ffi::Py_mod_exec, value: exec as *mut c_void };
+        (*slots)[1] = ffi::PyModuleDef_Slot { slot: 0, value: std::ptr::null_mut() };
+        (*def).m_slots = (*slots).as_mut_ptr();
+        (*def).m_size = std::mem::size_of::<ModuleState>() as ffi::Py_ssize_t;
+        (*def).m_free = Some(free_module_state);
+        ffi::PyModuleDef_Init(def)
+    }
+}
 
-        self.setattr(T::NAME, ty)
+/// `m_free` of `#[py::modinit]` modules, drops the module state.
+#[cfg(Py_3)]
+unsafe extern "C" fn free_module_state(module: *mut c_void) {
+    let slot = ffi::PyModule_GetState(module as *mut ffi::PyObject) as *mut ModuleState;
+    if !slot.is_null() && !(*slot).is_null() {
+        drop(Box::from_raw(*slot));
+        *slot = std::ptr::null_mut();
     }
 }
@@ -2,7 +2,9 @@
 
 use std;
 
+#[cfg(not(Py_LIMITED_API))]
 use buffer;
+use derive_utils;
 use ffi::{self, Py_ssize_t};
 use err::{self, PyErr, PyResult, PyDowncastError};
 use object::PyObject;
@@ -216,6 +218,7 @@ impl<'a, T> FromPyObject<'a> for Vec<T> where T: FromPyObject<'a>
     }
 }
 
+#[cfg(not(Py_LIMITED_API))]
 impl <'source, T> FromPyObject<'source> for Vec<T>
     where for<'a> T: FromPyObject<'a> + buffer::Element + Copy
 {
@@ -239,8 +242,8 @@ fn extract_sequence<'s, T>(obj: &'s PyObjectRef) -> PyResult<Vec<T>> where T: Fr
 {
     let seq = PySequence::try_from(obj)?;
     let mut v = Vec::with_capacity(seq.len().unwrap_or(0) as usize);
-    for item in seq.iter()? {
-        v.push(item?.extract::<T>()?);
+    for (idx, item) in seq.iter()?.enumerate() {
+        v.push(item?.extract::<T>().map_err(|err| derive_utils::with_index(obj.py(), err, idx))?);
     }
     Ok(v)
 }
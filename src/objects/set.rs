@@ -5,7 +5,9 @@ use std::{hash, collections};
 use ffi;
 use python::{Python, ToPyPointer};
 use object::PyObject;
-use conversion::{ToPyObject, ToBorrowedObject};
+use objects::{PyObjectRef, exc};
+use objectprotocol::ObjectProtocol;
+use conversion::{ToPyObject, ToBorrowedObject, IntoPyObject, FromPyObject};
 use instance::{AsPyRef, Py, PyObjectWithToken};
 use err::{self, PyResult, PyErr};
 
@@ -114,6 +116,45 @@ impl<T> ToPyObject for collections::BTreeSet<T>
     }
 }
 
+/// Raises a `TypeError` with `err` as its `__cause__`,
+/// adding the offending set element to the message.
+fn wrong_set_element(py: Python, item: &PyObjectRef, err: PyErr) -> PyErr {
+    let msg = match err.clone_ref(py).into_object(py).as_ref(py).str() {
+        Ok(s) => format!("Failed to extract set element {:?}: {}", item, s.to_string_lossy()),
+        Err(_) => format!("Failed to extract set element {:?}", item),
+    };
+    err::chain_cause(py, exc::TypeError::new(msg), err)
+}
+
+impl<'source, T, S> FromPyObject<'source> for collections::HashSet<T, S>
+    where T: FromPyObject<'source> + hash::Hash + Eq,
+          S: hash::BuildHasher + Default
+{
+    fn extract(ob: &'source PyObjectRef) -> PyResult<Self> {
+        let py = ob.py();
+        let mut set = collections::HashSet::with_hasher(S::default());
+        for item in ob.iter()? {
+            let item = item?;
+            set.insert(item.extract().map_err(|e| wrong_set_element(py, item, e))?);
+        }
+        Ok(set)
+    }
+}
+
+impl<'source, T> FromPyObject<'source> for collections::BTreeSet<T>
+    where T: FromPyObject<'source> + Ord
+{
+    fn extract(ob: &'source PyObjectRef) -> PyResult<Self> {
+        let py = ob.py();
+        let mut set = collections::BTreeSet::new();
+        for item in ob.iter()? {
+            let item = item?;
+            set.insert(item.extract().map_err(|e| wrong_set_element(py, item, e))?);
+        }
+        Ok(set)
+    }
+}
+
 impl PyFrozenSet {
     /// Creates a new frozenset.
     ///
@@ -152,10 +193,11 @@ impl PyFrozenSet {
 
 #[cfg(test)]
 mod test {
-    use std::collections::{HashSet};
+    use std::collections::{HashSet, BTreeSet};
     use super::{PySet, PyFrozenSet};
     use python::Python;
-    use conversion::{ToPyObject, PyTryFrom};
+    use conversion::{ToPyObject, IntoPyObject, PyTryFrom};
+    use objects::{PyList, exc};
     use objectprotocol::ObjectProtocol;
     use instance::AsPyRef;
 
@@ -278,4 +320,40 @@ mod test {
             assert_eq!(1i32, el.unwrap().extract::<i32>().unwrap());
         }
     }
+
+    #[test]
+    fn test_hashset_from_python() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let ob = PySet::new(py, &[1, 2]);
+        let set: HashSet<i32> = ob.as_ref(py).extract().unwrap();
+        assert_eq!(2, set.len());
+        assert!(set.contains(&1));
+        assert!(set.contains(&2));
+    }
+
+    #[test]
+    fn test_btreeset_from_iterable() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let list = PyList::new(py, &[3, 1, 2, 1]);
+        let set: BTreeSet<i32> = list.extract().unwrap();
+        assert_eq!(vec![1, 2, 3], set.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_hashset_from_python_invalid_element() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let list = PyList::new(py, &["foo"]);
+        let err = list.extract::<HashSet<i32>>().unwrap_err();
+        assert!(err.is_instance::<exc::TypeError>(py));
+        #[cfg(Py_3)]
+        assert!(err.cause(py).unwrap().is_instance::<exc::TypeError>(py));
+        let msg: String = err.into_object(py).as_ref(py).str().unwrap().extract().unwrap();
+        assert!(msg.starts_with("Failed to extract set element 'foo'"));
+    }
 }
@@ -1,7 +1,9 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
 use std;
-use std::{mem, str};
+use std::str;
+#[cfg(not(Py_LIMITED_API))]
+use std::mem;
 use std::borrow::Cow;
 use std::os::raw::c_char;
 
@@ -56,6 +58,7 @@ impl PyString {
     }
 
     /// Gets the python string data in its underlying representation.
+    #[cfg(not(Py_LIMITED_API))]
     pub fn data(&self) -> PyStringData {
         // TODO: return the original representation instead
         // of forcing the UTF-8 representation to be created.
@@ -70,6 +73,25 @@ impl PyString {
         }
     }
 
+    /// Gets the python string data in its underlying representation.
+    ///
+    /// The UTF-8 representation is not cached by the stable ABI,
+    /// the data is kept alive by a `bytes` object owned by the current `GILPool`.
+    #[cfg(Py_LIMITED_API)]
+    pub fn data(&self) -> PyStringData {
+        unsafe {
+            let bytes = ffi::PyUnicode_AsUTF8String(self.0.as_ptr());
+            if bytes.is_null() {
+                PyErr::fetch(self.py()).print(self.py());
+                panic!("PyUnicode_AsUTF8String failed");
+            }
+            let bytes: &PyObjectRef = self.py().from_owned_ptr(bytes);
+            let data = ffi::PyBytes_AsString(bytes.as_ptr()) as *const u8;
+            let size = ffi::PyBytes_Size(bytes.as_ptr());
+            PyStringData::Utf8(std::slice::from_raw_parts(data, size as usize))
+        }
+    }
+
     /// Convert the `PyString` into a Rust string.
     ///
     /// Returns a `UnicodeDecodeError` if the input is not valid unicode
@@ -0,0 +1,46 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use object::PyObject;
+use python::ToPyPointer;
+use err::PyResult;
+use instance::PyObjectWithToken;
+use objectprotocol::ObjectProtocol;
+
+/// Represents a Python traceback object.
+pub struct PyTraceback(PyObject);
+
+pyobject_convert!(PyTraceback);
+pyobject_nativetype!(PyTraceback, PyTraceBack_Type, PyTraceBack_Check);
+
+
+impl PyTraceback {
+    /// Formats the traceback like the interpreter prints it,
+    /// starting with `Traceback (most recent call last):`.
+    pub fn format(&self) -> PyResult<String> {
+        let entries: Vec<String> = self.py().import("traceback")?
+            .call1("format_tb", (self,))?
+            .extract()?;
+        Ok(format!("Traceback (most recent call last):\n{}", entries.concat()))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use python::Python;
+    use objects::PyDict;
+
+    #[test]
+    fn test_format() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let d = PyDict::new(py);
+        let err = py.run("def fail():\n    raise ValueError('oops')\nfail()", None, Some(d))
+            .unwrap_err();
+        let traceback = err.traceback(py).expect("traceback should be set");
+        let s = traceback.format().unwrap();
+        assert!(s.starts_with("Traceback (most recent call last):\n"), s);
+        assert!(s.contains("in fail"), s);
+    }
+}
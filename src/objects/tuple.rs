@@ -1,13 +1,15 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
 use std;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
 use std::slice;
 
 use ffi::{self, Py_ssize_t};
 use err::{PyErr, PyResult};
-use instance::{Py, PyObjectWithToken, AsPyRef};
+use instance::{Py, PyObjectWithToken};
 use object::PyObject;
 use objects::PyObjectRef;
+use objectprotocol::ObjectProtocol;
 use python::{Python, ToPyPointer, IntoPyPointer};
 use conversion::{FromPyObject, ToPyObject, IntoPyTuple, IntoPyObject, PyTryFrom};
 use super::exc;
@@ -43,8 +45,12 @@ impl PyTuple {
     /// Gets the length of the tuple.
     pub fn len(&self) -> usize {
         unsafe {
+            #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+            let size = ffi::PyTuple_GET_SIZE(self.as_ptr());
+            #[cfg(any(Py_LIMITED_API, PyPy))]
+            let size = ffi::PyTuple_Size(self.as_ptr());
             // non-negative Py_ssize_t should always fit into Rust uint
-            ffi::PyTuple_GET_SIZE(self.as_ptr()) as usize
+            size as usize
         }
     }
 
@@ -64,8 +70,7 @@ impl PyTuple {
     /// Take a slice of the tuple pointed to by p from low and return it as a new tuple.
     pub fn split_from(&self, low: isize) -> Py<PyTuple> {
         unsafe {
-            let ptr = ffi::PyTuple_GetSlice(
-                self.as_ptr(), low, ffi::PyTuple_GET_SIZE(self.as_ptr()));
+            let ptr = ffi::PyTuple_GetSlice(self.as_ptr(), low, self.len() as Py_ssize_t);
             Py::from_owned_ptr_or_panic(ptr)
         }
     }
@@ -78,11 +83,16 @@ impl PyTuple {
         // It's quite inconsistent that this method takes `Python` when `len()` does not.
         assert!(index < self.len());
         unsafe {
-            self.py().from_borrowed_ptr(
-                ffi::PyTuple_GET_ITEM(self.as_ptr(), index as Py_ssize_t))
+            #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+            let item = ffi::PyTuple_GET_ITEM(self.as_ptr(), index as Py_ssize_t);
+            #[cfg(any(Py_LIMITED_API, PyPy))]
+            let item = ffi::PyTuple_GetItem(self.as_ptr(), index as Py_ssize_t);
+            self.py().from_borrowed_ptr(item)
         }
     }
 
+    /// Not available with `Py_LIMITED_API` or on PyPy, the tuple layout is not part of the stable ABI.
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
     pub fn as_slice(&self) -> &[PyObject] {
         // This is safe because PyObject has the same memory layout as *mut ffi::PyObject,
         // and because tuples are immutable.
@@ -98,15 +108,15 @@ impl PyTuple {
 
     /// Returns an iterator over the tuple items.
     pub fn iter(&self) -> PyTupleIterator {
-        PyTupleIterator{ py: self.py(), slice: self.as_slice(), index: 0 }
+        PyTupleIterator{ tuple: self, index: 0, length: self.len() }
     }
 }
 
 /// Used by `PyTuple::iter()`.
 pub struct PyTupleIterator<'a> {
-    py: Python<'a>,
-    slice: &'a [PyObject],
+    tuple: &'a PyTuple,
     index: usize,
+    length: usize,
 }
 
 impl<'a> Iterator for PyTupleIterator<'a> {
@@ -114,8 +124,8 @@ impl<'a> Iterator for PyTupleIterator<'a> {
 
     #[inline]
     fn next(&mut self) -> Option<&'a PyObjectRef> {
-        if self.index < self.slice.len() {
-            let item = self.slice[self.index].as_ref(self.py);
+        if self.index < self.length {
+            let item = self.tuple.get_item(self.index);
             self.index += 1;
             Some(item)
         } else {
@@ -195,10 +205,9 @@ macro_rules! tuple_conversion ({$length:expr,$(($refN:ident, $n:tt, $T:ident)),+
         fn extract(obj: &'s PyObjectRef) -> PyResult<Self>
         {
             let t = PyTuple::try_from(obj)?;
-            let slice = t.as_slice();
             if t.len() == $length {
                 Ok((
-                    $( try!(slice[$n].extract::<$T>(obj.py())), )+
+                    $( try!(t.get_item($n).extract::<$T>()), )+
                 ))
             } else {
                 Err(wrong_tuple_length(t, $length))
@@ -2,6 +2,7 @@
 //
 // based on Daniel Grunwald's https://github.com/dgrunwald/rust-cpython
 
+#[cfg(not(Py_LIMITED_API))]
 use std::ffi::CStr;
 use std::borrow::Cow;
 
@@ -11,6 +12,10 @@ use python::{Python, ToPyPointer};
 use err::{PyErr, PyResult};
 use instance::{Py, PyObjectWithToken};
 use typeob::{PyTypeInfo, PyTypeObject};
+#[cfg(Py_LIMITED_API)]
+use objectprotocol::ObjectProtocol;
+#[cfg(Py_LIMITED_API)]
+use objects::PyString;
 
 /// Represents a reference to a Python `type object`.
 pub struct PyType(PyObject);
@@ -43,12 +48,24 @@ impl PyType {
     }
 
     /// Gets the name of the PyType.
+    #[cfg(not(Py_LIMITED_API))]
     pub fn name(&self) -> Cow<str> {
         unsafe {
             CStr::from_ptr((*self.as_type_ptr()).tp_name).to_string_lossy()
         }
     }
 
+    /// Gets the name of the PyType.
+    ///
+    /// `tp_name` is not accessible with `Py_LIMITED_API`, `__name__` is used instead.
+    #[cfg(Py_LIMITED_API)]
+    pub fn name(&self) -> Cow<str> {
+        match self.getattr("__name__").and_then(|name| Ok(name.extract::<&PyString>()?)) {
+            Ok(name) => name.to_string_lossy(),
+            Err(_) => Cow::Borrowed("<unknown>"),
+        }
+    }
+
     /// Check whether `self` is subclass of type `T` like Python `issubclass` function
     pub fn is_subclass<T>(&self) -> PyResult<bool>
         where T: PyTypeObject
@@ -0,0 +1,180 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use std::ptr;
+
+use ffi;
+use object::PyObject;
+use python::{Python, ToPyPointer};
+use err::PyResult;
+use instance::PyObjectWithToken;
+use objects::PyObjectRef;
+
+/// Represents a Python `weakref.ref` object.
+pub struct PyWeakRef(PyObject);
+
+pyobject_convert!(PyWeakRef);
+pyobject_nativetype!(PyWeakRef, _PyWeakref_RefType, PyWeakref_CheckRef);
+
+/// Represents a Python `weakref.proxy` object.
+pub struct PyWeakProxy(PyObject);
+
+pyobject_convert!(PyWeakProxy);
+pyobject_nativetype!(PyWeakProxy, _PyWeakref_ProxyType, PyWeakref_CheckProxy);
+
+
+/// Returns the object `weakref` points to, or `None` if it was garbage collected.
+unsafe fn get_object(py: Python, weakref: *mut ffi::PyObject) -> Option<&PyObjectRef> {
+    let ptr = ffi::PyWeakref_GetObject(weakref);
+    if ptr.is_null() || ptr == ffi::Py_None() {
+        None
+    } else {
+        // keep the referent alive for the lifetime of the returned reference
+        ffi::Py_INCREF(ptr);
+        Some(py.from_owned_ptr(ptr))
+    }
+}
+
+fn callback_ptr(callback: Option<&PyObjectRef>) -> *mut ffi::PyObject {
+    match callback {
+        Some(callback) => callback.as_ptr(),
+        None => ptr::null_mut(),
+    }
+}
+
+impl PyWeakRef {
+    /// Creates a new weak reference to `obj`.
+    ///
+    /// If `callback` is given, it is called with the weak reference object
+    /// as its only argument when `obj` is about to be finalized.
+    /// Raises `TypeError` if the type of `obj` does not support weak references.
+    pub fn new<'p, T>(obj: &'p T, callback: Option<&PyObjectRef>) -> PyResult<&'p PyWeakRef>
+        where T: PyObjectWithToken + ToPyPointer
+    {
+        unsafe {
+            obj.py().from_owned_ptr_or_err(
+                ffi::PyWeakref_NewRef(obj.as_ptr(), callback_ptr(callback)))
+        }
+    }
+
+    /// Returns the referenced object, or `None` if it no longer exists.
+    pub fn upgrade(&self) -> Option<&PyObjectRef> {
+        unsafe { get_object(self.py(), self.as_ptr()) }
+    }
+}
+
+impl PyWeakProxy {
+    /// Creates a new weak proxy to `obj`.
+    ///
+    /// If `obj` is callable, the proxy is a `weakref.CallableProxyType` instance.
+    /// Raises `TypeError` if the type of `obj` does not support weak references.
+    pub fn new<'p, T>(obj: &'p T, callback: Option<&PyObjectRef>) -> PyResult<&'p PyWeakProxy>
+        where T: PyObjectWithToken + ToPyPointer
+    {
+        unsafe {
+            obj.py().from_owned_ptr_or_err(
+                ffi::PyWeakref_NewProxy(obj.as_ptr(), callback_ptr(callback)))
+        }
+    }
+
+    /// Returns the referenced object, or `None` if it no longer exists.
+    pub fn upgrade(&self) -> Option<&PyObjectRef> {
+        unsafe { get_object(self.py(), self.as_ptr()) }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use python::{Python, ToPyPointer};
+    use object::PyObject;
+    use instance::{AsPyRef, Py, WeakPy};
+    use objectprotocol::ObjectProtocol;
+    use objects::{PyObjectRef, PyDict, exc};
+    use pythonrun::GILPool;
+    use super::{PyWeakRef, PyWeakProxy};
+
+    fn new_object(py: Python) -> PyObject {
+        let _pool = GILPool::new();
+        py.eval("type('A', (), {})()", None, None).unwrap().into()
+    }
+
+    #[test]
+    fn test_weakref_upgrade() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj = new_object(py);
+        let wr: Py<PyWeakRef> = {
+            let _pool = GILPool::new();
+            let wr = PyWeakRef::new(obj.as_ref(py), None).unwrap();
+            assert_eq!(wr.upgrade().unwrap().as_ptr(), obj.as_ptr());
+            wr.into()
+        };
+
+        {
+            let _pool = GILPool::new();
+            drop(obj);
+        }
+        assert!(wr.as_ref(py).upgrade().is_none());
+    }
+
+    #[test]
+    fn test_weakref_callback() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj = new_object(py);
+        let d = PyDict::new(py);
+        py.run("called = []", None, Some(d)).unwrap();
+        let callback = py.eval("called.append", None, Some(d)).unwrap();
+        let _wr: Py<PyWeakRef> = {
+            let _pool = GILPool::new();
+            PyWeakRef::new(obj.as_ref(py), Some(callback)).unwrap().into()
+        };
+
+        {
+            let _pool = GILPool::new();
+            drop(obj);
+        }
+        assert_eq!(py.eval("len(called)", None, Some(d)).unwrap()
+                   .extract::<usize>().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_weakproxy() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj = new_object(py);
+        obj.as_ref(py).setattr("value", 42).unwrap();
+        let proxy = PyWeakProxy::new(obj.as_ref(py), None).unwrap();
+        assert_eq!(proxy.getattr("value").unwrap().extract::<i32>().unwrap(), 42);
+        assert_eq!(proxy.upgrade().unwrap().as_ptr(), obj.as_ptr());
+    }
+
+    #[test]
+    fn test_weakref_unsupported() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj = py.eval("1", None, None).unwrap();
+        let err = PyWeakRef::new(obj, None).unwrap_err();
+        assert!(err.is_instance::<exc::TypeError>(py));
+    }
+
+    #[test]
+    fn test_weak_py() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj: Py<PyObjectRef> = new_object(py).extract(py).unwrap();
+        let weak = WeakPy::new(py, &obj).unwrap();
+        assert_eq!(weak.upgrade(py).unwrap().as_ptr(), obj.as_ptr());
+
+        {
+            let _pool = GILPool::new();
+            drop(obj);
+        }
+        assert!(weak.upgrade(py).is_none());
+    }
+}
@@ -20,6 +20,6 @@ pub use python::{Python, ToPyPointer, IntoPyPointer};
 pub use err::{PyErr, PyErrValue, PyResult, PyDowncastError, PyErrArguments};
 pub use pythonrun::GILGuard;
 pub use typeob::PyRawObject;
-pub use instance::{PyToken, PyObjectWithToken, AsPyRef, Py, PyNativeType};
+pub use instance::{PyToken, PyObjectWithToken, AsPyRef, Py, WeakPy, PyNativeType};
 pub use conversion::{FromPyObject, PyTryFrom, PyTryInto,
                      ToPyObject, ToBorrowedObject, IntoPyObject, IntoPyTuple};
@@ -106,14 +106,43 @@ impl<'p> Python<'p> {
         GILGuard::acquire()
     }
 
+    /// Acquires the global interpreter lock for the duration of the closure `f`.
+    ///
+    /// Objects created in `f` are released when it returns, which is why the result
+    /// can not borrow from the `Python` token. Nested calls are allowed.
+    ///
+    /// ```
+    /// use pyo3::{Python, ObjectProtocol};
+    ///
+    /// let sum: i32 = Python::with_gil(|py| {
+    ///     py.eval("1 + 2", None, None).unwrap().extract().unwrap()
+    /// });
+    /// assert_eq!(sum, 3);
+    /// ```
+    #[inline]
+    pub fn with_gil<F, R>(f: F) -> R where F: for<'py> FnOnce(Python<'py>) -> R {
+        let gil = GILGuard::acquire();
+        f(gil.python())
+    }
+
+    /// Checks whether the current thread holds the GIL, i.e. a `GILGuard` or `GILPool` is alive.
+    ///
+    /// Returns `false` inside the closure of `allow_threads`.
+    #[inline]
+    pub fn is_gil_held() -> bool {
+        pythonrun::gil_is_acquired()
+    }
+
     /// Temporarily releases the `GIL`, thus allowing other Python threads to run.
     pub fn allow_threads<T, F>(self, f: F) -> T where F : Send + FnOnce() -> T {
         // The `Send` bound on the closure prevents the user from
         // transferring the `Python` token into the closure.
         unsafe {
+            let count = pythonrun::suspend_gil_count();
             let save = ffi::PyEval_SaveThread();
             let result = f();
             ffi::PyEval_RestoreThread(save);
+            pythonrun::resume_gil_count(count);
             result
         }
     }
@@ -157,8 +186,7 @@ impl<'p> Python<'p> {
                 .unwrap_or_else(|| ffi::PyModule_GetDict(mptr));
             let locals = locals.map(|l| l.as_ptr()).unwrap_or(globals);
 
-            let res_ptr = ffi::PyRun_StringFlags(code.as_ptr(),
-                start, globals, locals, ::std::ptr::null_mut());
+            let res_ptr = run_string(&code, start, globals, locals);
 
             self.from_owned_ptr_or_err(res_ptr)
         }
@@ -427,6 +455,24 @@ impl<'p> Python<'p> {
     }
 }
 
+#[cfg(not(Py_LIMITED_API))]
+unsafe fn run_string(code: &CString, start: c_int,
+                     globals: *mut ffi::PyObject, locals: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    ffi::PyRun_StringFlags(code.as_ptr(), start, globals, locals, ::std::ptr::null_mut())
+}
+
+#[cfg(Py_LIMITED_API)]
+unsafe fn run_string(code: &CString, start: c_int,
+                     globals: *mut ffi::PyObject, locals: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    let code = ffi::Py_CompileString(code.as_ptr(), "<string>\0".as_ptr() as *const _, start);
+    if code.is_null() {
+        return code
+    }
+    let res = ffi::PyEval_EvalCode(code, globals, locals);
+    ffi::Py_DECREF(code);
+    res
+}
+
 #[cfg(test)]
 mod test {
     use Python;
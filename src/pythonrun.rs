@@ -1,14 +1,119 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
-use std::{any, sync, rc, marker, mem};
-use spin;
+use std::{any, sync, rc, marker, mem, ptr, thread};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
 
 use ffi;
 use python::Python;
+use object::PyObject;
 use objects::PyObjectRef;
+#[cfg(Py_3)]
+use objects::PyDict;
+#[cfg(Py_3)]
+use objectprotocol::ObjectProtocol;
+#[cfg(Py_3)]
+use python::ToPyPointer;
+use err::{PyErr, PyResult};
+use objects::exc;
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+use std::env;
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+use std::ffi::{OsStr, OsString};
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+use std::os::raw::{c_int, c_void};
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+use std::path::{Path, PathBuf};
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+use libc::wchar_t;
 
 static START: sync::Once = sync::ONCE_INIT;
 static START_PYO3: sync::Once = sync::ONCE_INIT;
 
+thread_local! {
+    /// Number of `GILGuard`s and `GILPool`s alive on the current thread.
+    ///
+    /// It is non-zero while the current thread holds the GIL,
+    /// `Python::allow_threads` resets it for the duration of its closure.
+    static GIL_COUNT: Cell<usize> = Cell::new(0);
+
+    /// Release pool of the sub-interpreter the current thread runs in,
+    /// null for the main interpreter.
+    static CURRENT_POOL: Cell<*mut ReleasePool> = Cell::new(ptr::null_mut());
+}
+
+/// Checks whether the current thread holds the GIL,
+/// i.e. a `GILGuard` or a `GILPool` is alive on it.
+#[inline]
+pub(crate) fn gil_is_acquired() -> bool {
+    GIL_COUNT.with(|c| c.get() > 0)
+}
+
+/// Version of the running interpreter as `major * 100 + minor`, 0 until it is read.
+static PYTHON_VERSION: AtomicUsize = AtomicUsize::new(0);
+
+/// Checks whether the running interpreter is Python 3.`minor` or newer.
+///
+/// With the limited API the `Py_3_x` cfgs only describe the minimum supported version,
+/// so behavior that changed between versions has to be checked at runtime.
+/// `Py_GetVersion` can be called before Python is initialized.
+pub(crate) fn is_python3_at_least(minor: usize) -> bool {
+    let mut version = PYTHON_VERSION.load(Ordering::Relaxed);
+    if version == 0 {
+        let s = unsafe { ::std::ffi::CStr::from_ptr(ffi::Py_GetVersion()) }.to_string_lossy();
+        let mut parts = s.split(|c: char| !c.is_digit(10))
+            .map(|part| part.parse::<usize>().unwrap_or(0));
+        let major = parts.next().unwrap_or(0);
+        let minor = parts.next().unwrap_or(0);
+        version = major * 100 + minor;
+        PYTHON_VERSION.store(version, Ordering::Relaxed);
+    }
+    version >= 300 + minor
+}
+
+/// Increments the GIL count of the current thread, returns the previous count.
+#[inline]
+fn increment_gil_count() -> usize {
+    GIL_COUNT.with(|c| {
+        let count = c.get();
+        c.set(count + 1);
+        count
+    })
+}
+
+/// Decrements the GIL count of the current thread for a `GILGuard` or `GILPool`
+/// created when the count was `depth`.
+///
+/// In debug builds this panics if the guard is not the innermost one, dropping it
+/// would release the objects still referenced by the guards created after it.
+#[inline]
+fn decrement_gil_count(depth: usize, kind: &str) {
+    let count = GIL_COUNT.with(|c| {
+        let count = c.get();
+        c.set(count.saturating_sub(1));
+        count
+    });
+    if cfg!(debug_assertions) && count != depth + 1 && !thread::panicking() {
+        panic!("{} dropped out of order: it is at nesting level {} but the innermost \
+                GILGuard/GILPool is at level {}; GILGuards and GILPools must be dropped \
+                in the reverse order of their creation", kind, depth + 1, count);
+    }
+}
+
+/// Resets the GIL count of the current thread before the GIL is released,
+/// returns the count to pass to `resume_gil_count`.
+#[inline]
+pub(crate) fn suspend_gil_count() -> usize {
+    GIL_COUNT.with(|c| c.replace(0))
+}
+
+/// Restores the GIL count saved by `suspend_gil_count` after the GIL is reacquired.
+#[inline]
+pub(crate) fn resume_gil_count(count: usize) {
+    GIL_COUNT.with(|c| c.set(count))
+}
+
 /// Prepares the use of Python in a free-threaded context.
 ///
 /// If the Python interpreter is not already initialized, this function
@@ -43,28 +148,428 @@ pub fn prepare_freethreaded_python() {
         } else {
             // If Python isn't initialized yet, we expect that Python threading
             // isn't initialized either.
-            #[cfg(not(Py_3_7))]
-            assert_eq!(ffi::PyEval_ThreadsInitialized(), 0);
+            // Since Python 3.7 threading is always initialized.
+            if !is_python3_at_least(7) {
+                assert_eq!(ffi::PyEval_ThreadsInitialized(), 0);
+            }
             // Initialize Python.
             // We use Py_InitializeEx() with initsigs=0 to disable Python signal handling.
             // Signal handling depends on the notion of a 'main thread', which doesn't exist in this case.
             // Note that the 'main thread' notion in Python isn't documented properly;
             // and running Python without one is not officially supported.
             ffi::Py_InitializeEx(0);
-            ffi::PyEval_InitThreads();
-            // PyEval_InitThreads() will acquire the GIL,
-            // but we don't want to hold it at this point
-            // (it's not acquired in the other code paths)
-            // So immediately release the GIL:
-            let _thread_state = ffi::PyEval_SaveThread();
-            // Note that the PyThreadState returned by PyEval_SaveThread is also held in TLS by the Python runtime,
-            // and will be restored by PyGILState_Ensure.
+            init_threads();
         }
 
         prepare_pyo3_library();
     });
 }
 
+/// Module initialization function generated by `#[py::modinit(name)]`,
+/// `PyInit_name` on Python 3 and `initname` on Python 2.
+#[cfg(Py_3)]
+pub type ModuleInitFunc = unsafe extern "C" fn() -> *mut ffi::PyObject;
+
+/// Module initialization function generated by `#[py::modinit(name)]`,
+/// `PyInit_name` on Python 3 and `initname` on Python 2.
+#[cfg(not(Py_3))]
+pub type ModuleInitFunc = unsafe extern "C" fn();
+
+/// Makes the module `name` compiled into the application importable,
+/// `init` is its initialization function.
+///
+/// Before Python is initialized, the module is added to the builtin modules
+/// with `PyImport_AppendInittab` and initialized by its first import.
+/// Afterwards the builtin modules can not be changed anymore,
+/// so the module is initialized immediately and added to `sys.modules`.
+///
+/// ```ignore
+/// #[py::modinit(mymod)]
+/// fn init_mymod(py: Python, m: &PyModule) -> PyResult<()> {
+///     ...
+/// }
+///
+/// pyo3::append_inittab("mymod", PyInit_mymod).unwrap();
+/// Python::with_gil(|py| py.run("import mymod", None, None)).unwrap();
+/// ```
+///
+/// Fails with `ValueError` if `name` contains a nul byte and with `MemoryError`
+/// if the builtin modules table can not be extended.
+pub fn append_inittab(name: &str, init: ModuleInitFunc) -> PyResult<()> {
+    let c_name = CString::new(name)?;
+    unsafe {
+        if ffi::Py_IsInitialized() == 0 {
+            // the builtin modules table keeps the name, it is never freed
+            let c_name = c_name.into_raw();
+            if ffi::PyImport_AppendInittab(c_name, Some(init)) != 0 {
+                drop(CString::from_raw(c_name));
+                // the exception types are static objects, the error can be created
+                // before Python is initialized
+                return Err(PyErr::new::<exc::MemoryError, _>(
+                    format!("Failed to add {} to the builtin modules", name)))
+            }
+            Ok(())
+        } else {
+            Python::with_gil(|py| init_builtin_module(py, name, init))
+        }
+    }
+}
+
+#[cfg(Py_3)]
+unsafe fn init_builtin_module(py: Python, name: &str, init: ModuleInitFunc) -> PyResult<()> {
+    let ob = init();
+    if ob.is_null() {
+        return Err(PyErr::fetch(py))
+    }
+
+    let module = if ffi::PyObject_TypeCheck(ob, &mut ffi::PyModuleDef_Type) != 0 {
+        // multi-phase initialization, `ob` is the statically allocated module definition
+        let def = ob as *mut ffi::PyModuleDef;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("origin", "built-in")?;
+        let spec = py.import("importlib.machinery")?
+            .call("ModuleSpec", (name, py.None()), kwargs)?;
+        let module: &PyObjectRef = py.from_owned_ptr_or_err(
+            ffi::PyModule_FromDefAndSpec(def, spec.as_ptr()))?;
+        if ffi::PyModule_ExecDef(module.as_ptr(), def) != 0 {
+            return Err(PyErr::fetch(py))
+        }
+        module
+    } else {
+        py.from_owned_ptr(ob)
+    };
+    py.import("sys")?.get("modules")?.set_item(name, module)
+}
+
+#[cfg(not(Py_3))]
+unsafe fn init_builtin_module(py: Python, _name: &str, init: ModuleInitFunc) -> PyResult<()> {
+    // `Py_InitModule` adds the module to `sys.modules`
+    init();
+    if ffi::PyErr_Occurred().is_null() {
+        Ok(())
+    } else {
+        Err(PyErr::fetch(py))
+    }
+}
+
+/// Initializes Python threading after `Py_InitializeEx` and releases the GIL.
+unsafe fn init_threads() {
+    ffi::PyEval_InitThreads();
+    // PyEval_InitThreads() will acquire the GIL,
+    // but we don't want to hold it at this point
+    // (it's not acquired in the other code paths)
+    // So immediately release the GIL:
+    let _thread_state = ffi::PyEval_SaveThread();
+    // Note that the PyThreadState returned by PyEval_SaveThread is also held in TLS by the Python runtime,
+    // and will be restored by PyGILState_Ensure.
+}
+
+/// Configuration of the Python interpreter for applications embedding Python.
+///
+/// The configuration is applied by `initialize`, which replaces
+/// `prepare_freethreaded_python` and must be called before Python is used.
+///
+/// ```no_run
+/// use pyo3::{InterpreterConfig, Python};
+///
+/// InterpreterConfig::new()
+///     .program_name("service")
+///     .python_home("/opt/service/python")
+///     .add_path("/opt/service/python/lib/python3.6")
+///     .add_path("/opt/service/app")
+///     .argv(vec!["service", "--verbose"])
+///     .optimize(1)
+///     .ignore_environment(true)
+///     .initialize()
+///     .unwrap();
+///
+/// Python::with_gil(|py| py.run("import sys; print(sys.path)", None, None).unwrap());
+/// unsafe { pyo3::finalize() };
+/// ```
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+#[derive(Debug, Clone, Default)]
+pub struct InterpreterConfig {
+    program_name: Option<OsString>,
+    python_home: Option<PathBuf>,
+    path: Vec<PathBuf>,
+    argv: Vec<OsString>,
+    optimize: u8,
+    signals: bool,
+    ignore_environment: bool,
+    #[cfg(Py_3_7)]
+    utf8_mode: bool,
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl InterpreterConfig {
+    /// Creates the default configuration, which is the one
+    /// used by `prepare_freethreaded_python`.
+    pub fn new() -> InterpreterConfig {
+        InterpreterConfig::default()
+    }
+
+    /// Sets the program name, which is used to compute the default module search path,
+    /// like `Py_SetProgramName`.
+    pub fn program_name<S: AsRef<OsStr>>(mut self, name: S) -> InterpreterConfig {
+        self.program_name = Some(name.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the location of the standard library, like the `PYTHONHOME` environment variable.
+    pub fn python_home<P: AsRef<Path>>(mut self, home: P) -> InterpreterConfig {
+        self.python_home = Some(home.as_ref().to_owned());
+        self
+    }
+
+    /// Adds an entry to `sys.path`.
+    ///
+    /// If any entries are added, they replace the default module search path
+    /// and `sys.prefix` and `sys.exec_prefix` are empty, like with `Py_SetPath`.
+    /// The entries must include the standard library.
+    pub fn add_path<P: AsRef<Path>>(mut self, path: P) -> InterpreterConfig {
+        self.path.push(path.as_ref().to_owned());
+        self
+    }
+
+    /// Sets `sys.argv`, `sys.path` is not changed.
+    pub fn argv<I, S>(mut self, args: I) -> InterpreterConfig
+        where I: IntoIterator<Item=S>, S: AsRef<OsStr>
+    {
+        self.argv = args.into_iter().map(|arg| arg.as_ref().to_owned()).collect();
+        self
+    }
+
+    /// Sets the optimization level like the `-O` command line option,
+    /// `0` keeps asserts, `1` removes asserts and `2` also removes docstrings.
+    ///
+    /// # Panic
+    /// Panics if `level` is larger than `2`.
+    pub fn optimize(mut self, level: u8) -> InterpreterConfig {
+        assert!(level <= 2, "optimization level must be 0, 1 or 2, got {}", level);
+        self.optimize = level;
+        self
+    }
+
+    /// Installs the Python signal handlers, `SIGINT` raises `KeyboardInterrupt`.
+    ///
+    /// The handlers run on the thread which initializes Python, disabled by default.
+    pub fn signals(mut self, enable: bool) -> InterpreterConfig {
+        self.signals = enable;
+        self
+    }
+
+    /// Ignores all `PYTHON*` environment variables, like the `-E` command line option.
+    pub fn ignore_environment(mut self, ignore: bool) -> InterpreterConfig {
+        self.ignore_environment = ignore;
+        self
+    }
+
+    /// Enables the UTF-8 mode of [PEP 540](https://www.python.org/dev/peps/pep-0540/),
+    /// like the `-X utf8` command line option.
+    #[cfg(Py_3_7)]
+    pub fn utf8_mode(mut self, enable: bool) -> InterpreterConfig {
+        self.utf8_mode = enable;
+        self
+    }
+
+    /// Initializes the Python interpreter and Python threading with this configuration.
+    ///
+    /// Returns `ValueError` if a string or path contains a nul byte, if a `sys.path` entry
+    /// contains the path separator or if a string can not be decoded, and `RuntimeError`
+    /// if Python is already initialized. The configuration is not applied in these cases.
+    pub fn initialize(self) -> PyResult<()> {
+        // every string is converted before `START`, which is only used for the initialization
+        let path = if self.path.is_empty() {
+            None
+        } else {
+            Some(env::join_paths(&self.path).map_err(|err| {
+                PyErr::new::<exc::ValueError, _>(format!("Invalid sys.path entry: {}", err))
+            })?)
+        };
+        if unsafe { ffi::Py_IsInitialized() } != 0 {
+            return Err(PyErr::new::<exc::RuntimeError, _>("Python is already initialized"))
+        }
+        let mut strings = Some(unsafe { WideStrings::new(&self, path.as_ref())? });
+
+        let mut initialized = false;
+        START.call_once(|| unsafe {
+            if ffi::Py_IsInitialized() == 0 {
+                self.apply(strings.take().unwrap());
+                init_threads();
+                initialized = true;
+            }
+            prepare_pyo3_library();
+        });
+
+        if initialized {
+            Ok(())
+        } else {
+            Err(PyErr::new::<exc::RuntimeError, _>("Python is already initialized"))
+        }
+    }
+
+    unsafe fn apply(&self, mut strings: WideStrings) {
+        #[cfg(not(Py_3_7))]
+        assert_eq!(ffi::PyEval_ThreadsInitialized(), 0);
+
+        ffi::Py_OptimizeFlag = self.optimize as c_int;
+        ffi::Py_IgnoreEnvironmentFlag = self.ignore_environment as c_int;
+
+        // Python keeps the program name and home, they are never freed
+        if !strings.program_name.is_null() {
+            ffi::Py_SetProgramName(mem::replace(&mut strings.program_name, ptr::null_mut()));
+        }
+        if !strings.python_home.is_null() {
+            ffi::Py_SetPythonHome(mem::replace(&mut strings.python_home, ptr::null_mut()));
+        }
+        if !strings.path.is_null() {
+            ffi::Py_SetPath(strings.path);
+        }
+
+        ffi::Py_InitializeEx(self.signals as c_int);
+
+        if !strings.argv.is_empty() {
+            ffi::PySys_SetArgvEx(strings.argv.len() as c_int, strings.argv.as_mut_ptr(), 0);
+        }
+    }
+}
+
+/// Strings of an `InterpreterConfig` converted to wide strings allocated with
+/// `PyMem_RawMalloc`, the strings which are not null are freed when it is dropped.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+struct WideStrings {
+    program_name: *mut wchar_t,
+    python_home: *mut wchar_t,
+    path: *mut wchar_t,
+    argv: Vec<*mut wchar_t>,
+    /// UTF-8 mode to restore if the conversion fails
+    #[cfg(Py_3_7)]
+    utf8_mode: Option<c_int>,
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl WideStrings {
+    /// Converts the strings of `config`, `path` is the joined `sys.path`.
+    ///
+    /// The UTF-8 mode is read by `Py_DecodeLocale`, it is enabled first
+    /// and restored if a string can not be converted.
+    unsafe fn new(config: &InterpreterConfig, path: Option<&OsString>) -> PyResult<WideStrings> {
+        let mut strings = WideStrings {
+            program_name: ptr::null_mut(),
+            python_home: ptr::null_mut(),
+            path: ptr::null_mut(),
+            argv: Vec::with_capacity(config.argv.len()),
+            #[cfg(Py_3_7)]
+            utf8_mode: Some(ffi::Py_UTF8Mode),
+        };
+        #[cfg(Py_3_7)]
+        {
+            if config.utf8_mode {
+                ffi::Py_UTF8Mode = 1;
+            }
+        }
+
+        if let Some(ref name) = config.program_name {
+            strings.program_name = wide_string(name)?;
+        }
+        if let Some(ref home) = config.python_home {
+            strings.python_home = wide_string(home.as_os_str())?;
+        }
+        if let Some(path) = path {
+            strings.path = wide_string(path)?;
+        }
+        for arg in &config.argv {
+            strings.argv.push(wide_string(arg)?);
+        }
+
+        #[cfg(Py_3_7)]
+        {
+            // the strings are used, keep the mode they were decoded with
+            strings.utf8_mode = None;
+        }
+        Ok(strings)
+    }
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl Drop for WideStrings {
+    fn drop(&mut self) {
+        unsafe {
+            #[cfg(Py_3_7)]
+            {
+                if let Some(mode) = self.utf8_mode {
+                    ffi::Py_UTF8Mode = mode;
+                }
+            }
+            let strings = [self.program_name, self.python_home, self.path];
+            for s in strings.iter().chain(self.argv.iter()) {
+                if !s.is_null() {
+                    ffi::PyMem_RawFree(*s as *mut c_void);
+                }
+            }
+        }
+    }
+}
+
+/// Converts `s` to a nul terminated wide string allocated with `PyMem_RawMalloc`.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy)), windows))]
+unsafe fn wide_string(s: &OsStr) -> PyResult<*mut wchar_t> {
+    use std::os::windows::ffi::OsStrExt;
+
+    let wide: Vec<u16> = s.encode_wide().collect();
+    if wide.contains(&0) {
+        return Err(PyErr::new::<exc::ValueError, _>(format!("{:?} contains a nul byte", s)))
+    }
+    let ptr = ffi::PyMem_RawMalloc((wide.len() + 1) * mem::size_of::<wchar_t>()) as *mut wchar_t;
+    if ptr.is_null() {
+        return Err(PyErr::new::<exc::MemoryError, _>("Out of memory"))
+    }
+    ptr::copy_nonoverlapping(wide.as_ptr() as *const wchar_t, ptr, wide.len());
+    *ptr.offset(wide.len() as isize) = 0;
+    Ok(ptr)
+}
+
+/// Converts `s` to a nul terminated wide string allocated with `PyMem_RawMalloc`,
+/// it is decoded like Python decodes its command line arguments.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy)), not(windows)))]
+unsafe fn wide_string(s: &OsStr) -> PyResult<*mut wchar_t> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let bytes = CString::new(s.as_bytes()).map_err(|_| {
+        PyErr::new::<exc::ValueError, _>(format!("{:?} contains a nul byte", s))
+    })?;
+    let ptr = ffi::Py_DecodeLocale(bytes.as_ptr(), ptr::null_mut());
+    if ptr.is_null() {
+        return Err(PyErr::new::<exc::ValueError, _>(format!("Failed to decode {:?}", s)))
+    }
+    Ok(ptr)
+}
+
+/// Runs the `atexit` handlers and finalizes the Python interpreter.
+///
+/// Objects dropped without the GIL are released before.
+/// This function has no effect if Python is not initialized.
+///
+/// # Safety
+/// All Python objects must be dropped before, and Python can not be
+/// initialized or used again afterwards.
+/// It must be called from the thread which initialized Python,
+/// which must not hold the GIL.
+pub unsafe fn finalize() {
+    if ffi::Py_IsInitialized() == 0 {
+        return
+    }
+    assert!(!gil_is_acquired(), "finalize() called while the GIL is held");
+
+    // restores the thread state released by `init_threads`
+    ffi::PyGILState_Ensure();
+    if !POOL.is_null() {
+        (*POOL).cache.clear();
+    }
+    release_deferred();
+    ffi::Py_Finalize();
+}
+
 
 #[doc(hidden)]
 pub fn prepare_pyo3_library() {
@@ -89,7 +594,8 @@ pub fn prepare_pyo3_library() {
 pub struct GILGuard {
     owned: usize,
     borrowed: usize,
-    gstate: ffi::PyGILState_STATE,
+    depth: usize,
+    gstate: Option<ffi::PyGILState_STATE>,
     // hack to opt out of Send on stable rust, which doesn't
     // have negative impls
     no_send: marker::PhantomData<rc::Rc<()>>
@@ -98,11 +604,14 @@ pub struct GILGuard {
 /// The Drop implementation for `GILGuard` will release the GIL.
 impl Drop for GILGuard {
     fn drop(&mut self) {
+        decrement_gil_count(self.depth, "GILGuard");
         unsafe {
-            let pool: &'static mut ReleasePool = &mut *POOL;
+            let pool = current_pool();
             pool.drain(self.owned, self.borrowed, true);
 
-            ffi::PyGILState_Release(self.gstate);
+            if let Some(gstate) = self.gstate {
+                ffi::PyGILState_Release(gstate);
+            }
         }
     }
 }
@@ -112,9 +621,16 @@ impl Drop for GILGuard {
 struct ReleasePool {
     owned: Vec<*mut ffi::PyObject>,
     borrowed: Vec<*mut ffi::PyObject>,
-    pointers: *mut Vec<*mut ffi::PyObject>,
     obj: Vec<Box<any::Any>>,
-    p: spin::Mutex<*mut Vec<*mut ffi::PyObject>>,
+    /// Decrefs deferred in a sub-interpreter, the main interpreter uses the `DEFERRED` stack.
+    ///
+    /// The pool of a sub-interpreter is only used by the thread which entered it.
+    deferred: Vec<*mut ffi::PyObject>,
+    /// Objects cached for the interpreter, see `interpreter_cache`.
+    cache: HashMap<&'static str, PyObject>,
+    /// Number of `PyObject` and `Py<T>` handles created in a sub-interpreter for each object,
+    /// see `track_pointer`. Always empty for the main interpreter.
+    tracked: HashMap<*mut ffi::PyObject, usize>,
 }
 
 impl ReleasePool {
@@ -122,32 +638,45 @@ impl ReleasePool {
         ReleasePool {
             owned: Vec::with_capacity(256),
             borrowed: Vec::with_capacity(256),
-            pointers: Box::into_raw(Box::new(Vec::with_capacity(256))),
             obj: Vec::with_capacity(8),
-            p: spin::Mutex::new(Box::into_raw(Box::new(Vec::with_capacity(256)))),
+            deferred: Vec::new(),
+            cache: HashMap::new(),
+            tracked: HashMap::new(),
         }
     }
 
-    unsafe fn release_pointers(&mut self) {
-        let mut v = self.p.lock();
+    /// Forgets a handle of `obj` created in the sub-interpreter of the pool,
+    /// returns `false` if there is none.
+    fn untrack(&mut self, obj: *mut ffi::PyObject) -> bool {
+        let remaining = match self.tracked.get_mut(&obj) {
+            Some(count) => {
+                *count -= 1;
+                *count
+            },
+            None => return false,
+        };
+        if remaining == 0 {
+            self.tracked.remove(&obj);
+        }
+        true
+    }
 
-        // vec of pointers
-        let ptr = *v;
-        let vec: &'static mut Vec<*mut ffi::PyObject> = &mut *ptr;
-        if vec.is_empty() {
+    /// Releases the deferred decrefs of the interpreter the pool belongs to,
+    /// which must be the current interpreter.
+    unsafe fn release_deferred(&mut self) {
+        if self as *mut ReleasePool == POOL {
+            release_deferred();
             return
         }
-
-        // switch vectors
-        *v = self.pointers;
-        self.pointers = ptr;
-        drop(v);
-
-        // release py objects
-        for ptr in vec.iter_mut() {
-            ffi::Py_DECREF(*ptr);
+        // a decref may run `__del__`, which can queue new decrefs
+        while !self.deferred.is_empty() {
+            let batch = mem::replace(&mut self.deferred, Vec::new());
+            DEFERRED_PENDING.fetch_sub(batch.len(), Ordering::Relaxed);
+            RELEASED_TOTAL.fetch_add(batch.len(), Ordering::Relaxed);
+            for obj in batch {
+                ffi::Py_DECREF(obj);
+            }
         }
-        vec.set_len(0);
     }
 
     pub unsafe fn drain(&mut self, owned: usize, borrowed: usize, pointers: bool) {
@@ -165,19 +694,32 @@ impl ReleasePool {
         }
 
         if pointers {
-            self.release_pointers();
+            self.release_deferred();
         }
 
         self.obj.clear();
     }
 }
 
+/// Release pool of the main interpreter.
 static mut POOL: *mut ReleasePool = ::std::ptr::null_mut();
 
+/// Returns the release pool of the interpreter the current thread runs in.
+#[inline]
+unsafe fn current_pool() -> &'static mut ReleasePool {
+    let pool = CURRENT_POOL.with(|p| p.get());
+    if pool.is_null() {
+        &mut *POOL
+    } else {
+        &mut *pool
+    }
+}
+
 #[doc(hidden)]
 pub struct GILPool {
     owned: usize,
     borrowed: usize,
+    depth: usize,
     pointers: bool,
     no_send: marker::PhantomData<rc::Rc<()>>,
 }
@@ -185,9 +727,10 @@ pub struct GILPool {
 impl Default for GILPool {
     #[inline]
     fn default() -> GILPool {
-        let p: &'static mut ReleasePool = unsafe { &mut *POOL };
+        let p = unsafe { current_pool() };
         GILPool {owned: p.owned.len(),
                  borrowed: p.borrowed.len(),
+                 depth: increment_gil_count(),
                  pointers: true,
                  no_send: marker::PhantomData}
     }
@@ -200,9 +743,10 @@ impl GILPool {
     }
     #[inline]
     pub fn new_no_pointers() -> GILPool {
-        let p: &'static mut ReleasePool = unsafe { &mut *POOL };
+        let p = unsafe { current_pool() };
         GILPool {owned: p.owned.len(),
                  borrowed: p.borrowed.len(),
+                 depth: increment_gil_count(),
                  pointers: false,
                  no_send: marker::PhantomData}
     }
@@ -210,40 +754,213 @@ impl GILPool {
 
 impl Drop for GILPool {
     fn drop(&mut self) {
+        decrement_gil_count(self.depth, "GILPool");
         unsafe {
-            let pool: &'static mut ReleasePool = &mut *POOL;
+            let pool = current_pool();
             pool.drain(self.owned, self.borrowed, self.pointers);
         }
     }
 }
 
+/// Returns the objects cached for the interpreter the current thread runs in,
+/// i.e. the `collections.namedtuple` types of `#[derive(ToPyObject)]`.
+///
+/// The GIL must be held. The objects are released before the interpreter is finalized.
+pub(crate) unsafe fn interpreter_cache() -> &'static mut HashMap<&'static str, PyObject> {
+    &mut current_pool().cache
+}
+
 pub unsafe fn register_any<'p, T: 'static>(obj: T) -> &'p T
 {
-    let pool: &'static mut ReleasePool = &mut *POOL;
+    let pool = current_pool();
 
     pool.obj.push(Box::new(obj));
     pool.obj.last().unwrap().as_ref().downcast_ref::<T>().unwrap()
 }
 
+/// Default number of pending deferred decrefs at which a dropping thread
+/// acquires the GIL to release them, see `set_deferred_decref_high_water_mark`.
+pub const DEFAULT_DECREF_HIGH_WATER_MARK: usize = 64 * 1024;
+
+/// Decref deferred in the main interpreter, an entry of the `DEFERRED` stack.
+struct DeferredNode {
+    obj: *mut ffi::PyObject,
+    next: *mut DeferredNode,
+}
+
+/// Decrefs deferred in the main interpreter, a lock-free stack.
+///
+/// Any thread pushes a node with a compare-and-swap of the head, the owner of the GIL
+/// takes all nodes at once by swapping the head with null. Nodes are never popped one
+/// by one, so a node can not be freed while another thread reads it.
+static DEFERRED: AtomicPtr<DeferredNode> = AtomicPtr::new(0 as *mut _);
+
+static DEFERRED_PENDING: AtomicUsize = AtomicUsize::new(0);
+static DEFERRED_TOTAL: AtomicUsize = AtomicUsize::new(0);
+static RELEASED_TOTAL: AtomicUsize = AtomicUsize::new(0);
+static HIGH_WATER_FLUSHES: AtomicUsize = AtomicUsize::new(0);
+static HIGH_WATER_MARK: AtomicUsize = AtomicUsize::new(DEFAULT_DECREF_HIGH_WATER_MARK);
+static FLUSHING: AtomicBool = AtomicBool::new(false);
+
+/// Counters of the decrefs deferred until the GIL is released,
+/// see [`deferred_decref_stats`](fn.deferred_decref_stats.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeferredDecrefStats {
+    /// Number of dropped references waiting to be released
+    pub pending: usize,
+    /// Number of references deferred since the start of the process
+    pub deferred: usize,
+    /// Number of deferred references released since the start of the process
+    pub released: usize,
+    /// Number of times the high-water mark made a dropping thread acquire the GIL
+    pub high_water_flushes: usize,
+}
+
+/// Returns the counters of the deferred decrefs.
+///
+/// Dropping a `PyObject` or `Py<T>` does not touch the reference count,
+/// the reference is queued and released when a `GILGuard` or `GILPool` is dropped.
+pub fn deferred_decref_stats() -> DeferredDecrefStats {
+    DeferredDecrefStats {
+        pending: DEFERRED_PENDING.load(Ordering::Relaxed),
+        deferred: DEFERRED_TOTAL.load(Ordering::Relaxed),
+        released: RELEASED_TOTAL.load(Ordering::Relaxed),
+        high_water_flushes: HIGH_WATER_FLUSHES.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets the number of pending deferred decrefs at which the thread dropping an object
+/// acquires the GIL and releases all of them, `0` disables flushing on drop.
+///
+/// Defaults to [`DEFAULT_DECREF_HIGH_WATER_MARK`](constant.DEFAULT_DECREF_HIGH_WATER_MARK.html).
+pub fn set_deferred_decref_high_water_mark(mark: usize) {
+    HIGH_WATER_MARK.store(mark, Ordering::Relaxed);
+}
+
+/// Releases the deferred decrefs queued by all threads.
+///
+/// Must be called with the GIL held.
+unsafe fn release_deferred() {
+    let mut node = DEFERRED.swap(ptr::null_mut(), Ordering::Acquire);
+    if node.is_null() {
+        return
+    }
+
+    let mut batch = Vec::new();
+    while !node.is_null() {
+        let entry = Box::from_raw(node);
+        batch.push(entry.obj);
+        node = entry.next;
+    }
+    // the stack holds the newest decref first, release them in the order of the drops
+    batch.reverse();
+    // the counters are incremented before a node is pushed
+    DEFERRED_PENDING.fetch_sub(batch.len(), Ordering::Relaxed);
+    RELEASED_TOTAL.fetch_add(batch.len(), Ordering::Relaxed);
+
+    // a decref may run `__del__`, which can queue new decrefs
+    for obj in batch {
+        ffi::Py_DECREF(obj);
+    }
+}
+
+/// Acquires the GIL and releases the deferred decrefs,
+/// unless another thread is already doing so.
+unsafe fn flush_deferred() {
+    if FLUSHING.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+        return
+    }
+    if ffi::Py_IsInitialized() != 0 {
+        HIGH_WATER_FLUSHES.fetch_add(1, Ordering::Relaxed);
+        if gil_is_acquired() {
+            release_deferred();
+        } else {
+            let gstate = ffi::PyGILState_Ensure();
+            release_deferred();
+            ffi::PyGILState_Release(gstate);
+        }
+    }
+    FLUSHING.store(false, Ordering::Release);
+}
+
+/// Records a `PyObject` or `Py<T>` handle of `obj` created while the current thread
+/// runs in a sub-interpreter, the reference it owns is released in the sub-interpreter.
+///
+/// Handles created in the main interpreter are not tracked.
+#[inline]
+pub(crate) unsafe fn track_pointer(obj: *mut ffi::PyObject) {
+    let pool = CURRENT_POOL.with(|p| p.get());
+    if !pool.is_null() {
+        *(*pool).tracked.entry(obj).or_insert(0) += 1;
+    }
+}
+
+/// Forgets a handle recorded by `track_pointer` whose reference was passed on
+/// with `IntoPyPointer::into_ptr`.
+#[inline]
+pub(crate) unsafe fn untrack_pointer(obj: *mut ffi::PyObject) {
+    let pool = CURRENT_POOL.with(|p| p.get());
+    if !pool.is_null() {
+        (*pool).untrack(obj);
+    }
+}
+
+/// Queues a decref of `obj` until the GIL is released, the GIL does not need to be held.
+///
+/// A reference is released by the interpreter whose handle owned it: references of handles
+/// created in the sub-interpreter the current thread runs in are queued in its release pool,
+/// all other references are released in the main interpreter.
 pub unsafe fn register_pointer(obj: *mut ffi::PyObject)
 {
-    let pool: &'static mut ReleasePool = &mut *POOL;
+    let pool = CURRENT_POOL.with(|p| p.get());
+    if !pool.is_null() {
+        if (*pool).untrack(obj) {
+            DEFERRED_TOTAL.fetch_add(1, Ordering::Relaxed);
+            DEFERRED_PENDING.fetch_add(1, Ordering::Relaxed);
+            (*pool).deferred.push(obj);
+        } else {
+            // the main interpreter can not be entered here,
+            // the reference is released after `SubInterpreter::with` returns
+            push_deferred(obj);
+        }
+        return
+    }
 
-    let mut v = pool.p.lock();
-    let pool: &'static mut Vec<*mut ffi::PyObject> = &mut *(*v);
-    pool.push(obj);
+    let pending = push_deferred(obj);
+    let mark = HIGH_WATER_MARK.load(Ordering::Relaxed);
+    if mark != 0 && pending >= mark {
+        flush_deferred();
+    }
+}
+
+/// Pushes `obj` onto the deferred decrefs of the main interpreter,
+/// returns the number of pending decrefs.
+unsafe fn push_deferred(obj: *mut ffi::PyObject) -> usize {
+    // counted first, `release_deferred` subtracts the nodes it takes
+    DEFERRED_TOTAL.fetch_add(1, Ordering::Relaxed);
+    let pending = DEFERRED_PENDING.fetch_add(1, Ordering::Relaxed) + 1;
+
+    let node = Box::into_raw(Box::new(DeferredNode { obj: obj, next: ptr::null_mut() }));
+    let mut head = DEFERRED.load(Ordering::Relaxed);
+    loop {
+        (*node).next = head;
+        match DEFERRED.compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed) {
+            Ok(_) => return pending,
+            Err(current) => head = current,
+        }
+    }
 }
 
 pub unsafe fn register_owned(_py: Python, obj: *mut ffi::PyObject) -> &PyObjectRef
 {
-    let pool: &'static mut ReleasePool = &mut *POOL;
+    let pool = current_pool();
     pool.owned.push(obj);
     mem::transmute(&pool.owned[pool.owned.len()-1])
 }
 
 pub unsafe fn register_borrowed(_py: Python, obj: *mut ffi::PyObject) -> &PyObjectRef
 {
-    let pool: &'static mut ReleasePool = &mut *POOL;
+    let pool = current_pool();
     pool.borrowed.push(obj);
     mem::transmute(&pool.borrowed[pool.borrowed.len()-1])
 }
@@ -253,14 +970,23 @@ impl GILGuard {
     ///
     /// If the Python runtime is not already initialized, this function will initialize it.
     /// See [prepare_freethreaded_python()](fn.prepare_freethreaded_python.html) for details.
+    ///
+    /// If the current thread already holds the GIL, only a new release pool is started.
     pub fn acquire() -> GILGuard {
         prepare_freethreaded_python();
 
         unsafe {
-            let gstate = ffi::PyGILState_Ensure(); // acquire GIL
-            let pool: &'static mut ReleasePool = &mut *POOL;
+            // `PyGILState_Ensure` does not know about sub-interpreters,
+            // it must not be called while a sub-interpreter is entered
+            let gstate = if gil_is_acquired() {
+                None
+            } else {
+                Some(ffi::PyGILState_Ensure()) // acquire GIL
+            };
+            let pool = current_pool();
             GILGuard { owned: pool.owned.len(),
                        borrowed: pool.borrowed.len(),
+                       depth: increment_gil_count(),
                        gstate: gstate,
                        no_send: marker::PhantomData }
         }
@@ -273,12 +999,187 @@ impl GILGuard {
     }
 }
 
+/// Handle of a sub-interpreter created with `Py_NewInterpreter`.
+///
+/// A sub-interpreter has its own modules, `sys` state and release pool,
+/// objects must not be passed between interpreters.
+/// `with` only returns values which hold no Python objects, see `NotPython`.
+/// A `PyObject` or `Py<T>` created inside `with` is released in the sub-interpreter,
+/// one created in the main interpreter and dropped inside `with` is released
+/// in the main interpreter after `with` returns.
+///
+/// Classes are initialized by the first interpreter which uses them,
+/// since Python 3.9 using them in another interpreter raises `RuntimeError`.
+/// On Python 3.5 to 3.8 this is not checked: the interpreters share the type object,
+/// which refers to the modules of the interpreter that initialized it,
+/// so a `#[py::class]` must only be used by one interpreter on these versions.
+///
+/// The interpreter is bound to the thread which created it and
+/// is ended with `Py_EndInterpreter` when the handle is dropped.
+///
+/// ```
+/// use pyo3::{Python, SubInterpreter, ObjectProtocol};
+///
+/// let gil = Python::acquire_gil();
+/// let interp = SubInterpreter::new(gil.python()).unwrap();
+/// let value: i32 = interp.with(gil.python(), |py| {
+///     py.run("import sys; sys.answer = 42", None, None).unwrap();
+///     py.eval("sys.answer", None, None).unwrap().extract().unwrap()
+/// });
+/// assert_eq!(value, 42);
+/// ```
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+pub struct SubInterpreter {
+    tstate: *mut ffi::PyThreadState,
+    pool: *mut ReleasePool,
+    no_send: marker::PhantomData<rc::Rc<()>>,
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl SubInterpreter {
+    /// Creates a new sub-interpreter, the current interpreter stays active.
+    pub fn new(_py: Python) -> PyResult<SubInterpreter> {
+        unsafe {
+            let current = ffi::PyThreadState_Get();
+            let tstate = ffi::Py_NewInterpreter();
+            ffi::PyThreadState_Swap(current);
+            if tstate.is_null() {
+                return Err(PyErr::new::<exc::RuntimeError, _>("Failed to create sub-interpreter"))
+            }
+            Ok(SubInterpreter {
+                tstate: tstate,
+                pool: Box::into_raw(Box::new(ReleasePool::new())),
+                no_send: marker::PhantomData,
+            })
+        }
+    }
+
+    /// Runs `f` in the sub-interpreter.
+    ///
+    /// The objects created in `f` are released when it returns,
+    /// the `Python` token of the calling interpreter must not be used in `f`.
+    pub fn with<F, R>(&self, _py: Python, f: F) -> R
+        where F: for<'py> FnOnce(Python<'py>) -> R, R: NotPython
+    {
+        let _enter = unsafe { EnterInterpreter::new(self.tstate, self.pool) };
+        let _pool = GILPool::new();
+        f(unsafe { Python::assume_gil_acquired() })
+    }
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl Drop for SubInterpreter {
+    fn drop(&mut self) {
+        let _gil = GILGuard::acquire();
+        unsafe {
+            let enter = EnterInterpreter::new(self.tstate, self.pool);
+            // the cached objects are queued in the release pool of the sub-interpreter
+            (*self.pool).cache.clear();
+            (*self.pool).release_deferred();
+            (*self.pool).tracked.clear();
+            ffi::Py_EndInterpreter(self.tstate);
+            drop(enter);
+            drop(Box::from_raw(self.pool));
+        }
+    }
+}
+
+/// Marker for values which hold no Python objects, the values `SubInterpreter::with`
+/// can return.
+///
+/// It is implemented for primitive types, strings and the standard collections of such values.
+/// `PyObject`, `Py<T>`, `PyErr` and references to Python objects belong to
+/// the interpreter that created them and must not implement it.
+///
+/// # Safety
+/// Implementors must not hold Python objects, directly or indirectly.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+pub unsafe trait NotPython: Send + 'static {}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+macro_rules! not_python_impl {
+    ($($t:ty),*) => {
+        $(unsafe impl NotPython for $t {})*
+    };
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+not_python_impl!((), bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize,
+                 f32, f64, String, &'static str, PathBuf, OsString, ::std::time::Duration);
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+mod not_python_impls {
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+    use super::NotPython;
+
+    unsafe impl<T: NotPython> NotPython for Box<T> {}
+    unsafe impl<T: NotPython> NotPython for Option<T> {}
+    unsafe impl<T: NotPython, E: NotPython> NotPython for Result<T, E> {}
+    unsafe impl<T: NotPython> NotPython for Vec<T> {}
+    unsafe impl<T: NotPython> NotPython for VecDeque<T> {}
+    unsafe impl<T: NotPython> NotPython for HashSet<T> {}
+    unsafe impl<T: NotPython> NotPython for BTreeSet<T> {}
+    unsafe impl<K: NotPython, V: NotPython> NotPython for HashMap<K, V> {}
+    unsafe impl<K: NotPython, V: NotPython> NotPython for BTreeMap<K, V> {}
+    unsafe impl<A: NotPython, B: NotPython> NotPython for (A, B) {}
+    unsafe impl<A: NotPython, B: NotPython, C: NotPython> NotPython for (A, B, C) {}
+    unsafe impl<A: NotPython, B: NotPython, C: NotPython, D: NotPython> NotPython for (A, B, C, D) {}
+}
+
+/// Makes the thread state `tstate` and the release pool `pool` current until it is dropped.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+struct EnterInterpreter {
+    tstate: *mut ffi::PyThreadState,
+    pool: *mut ReleasePool,
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl EnterInterpreter {
+    unsafe fn new(tstate: *mut ffi::PyThreadState, pool: *mut ReleasePool) -> EnterInterpreter {
+        EnterInterpreter {
+            tstate: ffi::PyThreadState_Swap(tstate),
+            pool: CURRENT_POOL.with(|p| p.replace(pool)),
+        }
+    }
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+impl Drop for EnterInterpreter {
+    fn drop(&mut self) {
+        CURRENT_POOL.with(|p| p.set(self.pool));
+        unsafe { ffi::PyThreadState_Swap(self.tstate); }
+    }
+}
+
+/// Returns the interpreter the current thread runs in, the GIL must be held.
+#[cfg(all(Py_3_9, not(any(Py_LIMITED_API, PyPy))))]
+pub(crate) unsafe fn current_interpreter() -> *mut ffi::PyInterpreterState {
+    ffi::PyInterpreterState_Get()
+}
+
 #[cfg(test)]
 mod test {
     use {ffi, pythonrun};
     use python::Python;
     use object::PyObject;
-    use super::{GILPool, ReleasePool, POOL};
+    use super::{GILPool, ReleasePool, POOL, DEFAULT_DECREF_HIGH_WATER_MARK,
+                deferred_decref_stats, set_deferred_decref_high_water_mark};
+    use objectprotocol::ObjectProtocol;
+    use std::thread;
+
+    #[test]
+    #[cfg(Py_3)]
+    fn test_python_version() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let minor: usize = py.import("sys").unwrap()
+            .getattr("version_info").unwrap()
+            .getattr("minor").unwrap()
+            .extract().unwrap();
+        assert!(pythonrun::is_python3_at_least(minor));
+        assert!(!pythonrun::is_python3_at_least(minor + 1));
+    }
 
     #[test]
     fn test_owned() {
@@ -437,4 +1338,217 @@ mod test {
             assert_eq!(cnt - 1, ffi::Py_REFCNT(empty));
         }
     }
+
+    #[test]
+    fn test_gil_count() {
+        thread::spawn(|| {
+            assert!(!Python::is_gil_held());
+            {
+                let gil = Python::acquire_gil();
+                assert!(Python::is_gil_held());
+                {
+                    let _nested = Python::acquire_gil();
+                    let _pool = GILPool::new();
+                    assert!(Python::is_gil_held());
+                }
+                gil.python().allow_threads(|| {
+                    assert!(!Python::is_gil_held());
+                });
+                assert!(Python::is_gil_held());
+            }
+            assert!(!Python::is_gil_held());
+
+            let len = Python::with_gil(|py| {
+                assert!(Python::is_gil_held());
+                py.eval("[1, 2, 3]", None, None).unwrap().len().unwrap()
+            });
+            assert_eq!(len, 3);
+            assert!(!Python::is_gil_held());
+        }).join().unwrap();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "GILPool dropped out of order")]
+    fn test_pool_dropped_out_of_order() {
+        let _gil = Python::acquire_gil();
+        let outer = GILPool::new();
+        let _inner = GILPool::new();
+        drop(outer);
+    }
+
+    #[test]
+    fn test_deferred_decref_high_water_mark() {
+        pythonrun::prepare_freethreaded_python();
+
+        unsafe {
+            let list;
+            {
+                let _gil = Python::acquire_gil();
+                list = ffi::PyList_New(0);
+                for _ in 0..4 {
+                    ffi::Py_INCREF(list);
+                }
+            }
+            // raw pointers are not Send
+            let addr = list as usize;
+
+            set_deferred_decref_high_water_mark(0);
+            let before = deferred_decref_stats();
+            thread::spawn(move || {
+                for _ in 0..3 {
+                    pythonrun::register_pointer(addr as *mut ffi::PyObject);
+                }
+            }).join().unwrap();
+            assert!(deferred_decref_stats().deferred >= before.deferred + 3);
+
+            set_deferred_decref_high_water_mark(1);
+            thread::spawn(move || {
+                pythonrun::register_pointer(addr as *mut ffi::PyObject);
+            }).join().unwrap();
+            set_deferred_decref_high_water_mark(DEFAULT_DECREF_HIGH_WATER_MARK);
+
+            let stats = deferred_decref_stats();
+            assert!(stats.high_water_flushes > before.high_water_flushes);
+            assert!(stats.released >= before.released + 4);
+            assert_eq!(ffi::Py_REFCNT(list), 1);
+
+            let _gil = Python::acquire_gil();
+            ffi::Py_DECREF(list);
+        }
+    }
+
+    #[test]
+    fn test_deferred_decref_concurrent() {
+        pythonrun::prepare_freethreaded_python();
+
+        unsafe {
+            let list;
+            {
+                let _gil = Python::acquire_gil();
+                list = ffi::PyList_New(0);
+                for _ in 0..4 * 1000 {
+                    ffi::Py_INCREF(list);
+                }
+            }
+            let addr = list as usize;
+
+            let threads: Vec<_> = (0..4).map(|_| thread::spawn(move || {
+                for _ in 0..1000 {
+                    pythonrun::register_pointer(addr as *mut ffi::PyObject);
+                }
+            })).collect();
+            // release while the other threads push
+            for _ in 0..10 {
+                let _gil = Python::acquire_gil();
+            }
+            for thread in threads {
+                thread.join().unwrap();
+            }
+
+            {
+                let _gil = Python::acquire_gil();
+            }
+            let _gil = Python::acquire_gil();
+            assert_eq!(ffi::Py_REFCNT(list), 1);
+            ffi::Py_DECREF(list);
+        }
+    }
+
+    #[test]
+    #[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+    fn test_sub_interpreter() {
+        use super::SubInterpreter;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        py.run("import sys; sys.pyo3_marker = 'main'", None, None).unwrap();
+
+        let interp = SubInterpreter::new(py).unwrap();
+        let marker: Option<String> = interp.with(py, |py| {
+            assert!(Python::is_gil_held());
+            py.eval("getattr(__import__('sys'), 'pyo3_marker', None)", None, None)
+                .unwrap().extract().unwrap()
+        });
+        assert_eq!(marker, None);
+
+        let marker: String = py.eval("__import__('sys').pyo3_marker", None, None)
+            .unwrap().extract().unwrap();
+        assert_eq!(marker, "main");
+        drop(interp);
+    }
+
+    #[test]
+    #[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+    fn test_sub_interpreter_deferred() {
+        use super::{SubInterpreter, push_deferred};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let interp = SubInterpreter::new(py).unwrap();
+
+        unsafe {
+            // queued by a thread which runs in the main interpreter
+            let list = ffi::PyList_New(0);
+            ffi::Py_INCREF(list);
+            push_deferred(list);
+
+            // a handle of the main interpreter dropped in the sub-interpreter
+            let main_list = ffi::PyList_New(0);
+            ffi::Py_INCREF(main_list);
+            let main_ob = PyObject::from_owned_ptr(py, main_list);
+
+            interp.with(py, move |py| {
+                let obj = ffi::PyList_New(0);
+                ffi::Py_INCREF(obj);
+                drop(PyObject::from_owned_ptr(py, obj));
+                drop(main_ob);
+                drop(GILPool::new());
+                assert_eq!(ffi::Py_REFCNT(obj), 1);
+                assert_eq!(ffi::Py_REFCNT(main_list), 2);
+                ffi::Py_DECREF(obj);
+            });
+            assert_eq!(ffi::Py_REFCNT(list), 2);
+            assert_eq!(ffi::Py_REFCNT(main_list), 2);
+
+            drop(GILPool::new());
+            assert_eq!(ffi::Py_REFCNT(list), 1);
+            assert_eq!(ffi::Py_REFCNT(main_list), 1);
+            ffi::Py_DECREF(list);
+            ffi::Py_DECREF(main_list);
+        }
+    }
+
+    #[test]
+    #[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+    fn test_interpreter_config_already_initialized() {
+        use super::InterpreterConfig;
+        use objects::exc;
+
+        pythonrun::prepare_freethreaded_python();
+        let err = InterpreterConfig::new().program_name("test").initialize().unwrap_err();
+
+        let gil = Python::acquire_gil();
+        assert!(err.is_instance::<exc::RuntimeError>(gil.python()));
+    }
+
+    #[test]
+    #[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+    fn test_interpreter_config_invalid_path() {
+        use objects::exc;
+
+        let entry = format!("lib{}app", if cfg!(windows) { ';' } else { ':' });
+        let err = super::InterpreterConfig::new().add_path(entry).initialize().unwrap_err();
+
+        // the error is returned without running the initialization
+        let gil = Python::acquire_gil();
+        assert!(err.is_instance::<exc::ValueError>(gil.python()));
+    }
+
+    #[test]
+    #[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+    #[should_panic(expected = "optimization level must be 0, 1 or 2")]
+    fn test_interpreter_config_optimize() {
+        super::InterpreterConfig::new().optimize(3);
+    }
 }
@@ -4,14 +4,25 @@
 
 use std;
 use std::mem;
+#[cfg(Py_3)]
+use std::ptr;
+#[cfg(Py_3)]
+use std::sync::atomic::{AtomicPtr, Ordering};
+#[cfg(not(Py_3))]
+use std::cell::UnsafeCell;
+use std::os::raw::{c_char, c_void};
+#[cfg(Py_3)]
+use std::os::raw::{c_int, c_uint, c_ulong};
 use std::ffi::{CStr, CString};
 use std::collections::HashMap;
 
 use {ffi, class, pythonrun};
 use err::{PyErr, PyResult};
 use instance::{Py, PyObjectWithToken, PyToken};
-use python::{Python, IntoPyPointer};
+use python::{Python, IntoPyPointer, ToPyPointer};
 use objects::PyType;
+#[cfg(Py_3)]
+use objects::exc;
 use class::methods::PyMethodDefType;
 
 
@@ -39,7 +50,13 @@ pub trait PyTypeInfo {
     type BaseType: PyTypeInfo;
 
     /// PyTypeObject instance for this type
-    unsafe fn type_object() -> &'static mut ffi::PyTypeObject;
+    fn type_object() -> *mut ffi::PyTypeObject;
+
+    /// Storage of the type object, only available for `#[py::class]` types
+    #[doc(hidden)]
+    fn type_storage() -> &'static TypeStorage {
+        panic!("{} is not a #[py::class]", Self::NAME)
+    }
 
     /// Check if `*mut ffi::PyObject` is instance of this type
     #[cfg_attr(feature = "cargo-clippy", allow(not_unsafe_ptr_arg_deref))]
@@ -80,10 +97,15 @@ impl<'a, T: ?Sized> PyTypeInfo for &'a T where T: PyTypeInfo {
     const FLAGS: usize = T::FLAGS;
 
     #[inline]
-    default unsafe fn type_object() -> &'static mut ffi::PyTypeObject {
+    default fn type_object() -> *mut ffi::PyTypeObject {
         <T as PyTypeInfo>::type_object()
     }
 
+    #[inline]
+    default fn type_storage() -> &'static TypeStorage {
+        <T as PyTypeInfo>::type_storage()
+    }
+
     #[inline]
     default fn is_instance(ptr: *mut ffi::PyObject) -> bool {
         <T as PyTypeInfo>::is_instance(ptr)
@@ -95,6 +117,98 @@ impl<'a, T: ?Sized> PyTypeInfo for &'a T where T: PyTypeInfo {
     }
 }
 
+/// Storage of the type object of a `#[py::class]`.
+///
+/// With Python 3 the class is a heap type created by `PyType_FromSpecWithBases`,
+/// the storage holds a reference to it once the class is initialized.
+/// With Python 2 the storage is the type object itself,
+/// it is initialized in place by `PyType_Ready`.
+///
+/// The heap type belongs to the interpreter which created it,
+/// instances can not be created in other sub-interpreters.
+#[doc(hidden)]
+pub struct TypeStorage {
+    #[cfg(Py_3)]
+    heap_type: AtomicPtr<ffi::PyTypeObject>,
+    #[cfg(all(Py_3_9, not(any(Py_LIMITED_API, PyPy))))]
+    interpreter: AtomicPtr<ffi::PyInterpreterState>,
+    #[cfg(not(Py_3))]
+    type_object: UnsafeCell<ffi::PyTypeObject>,
+}
+
+#[cfg(not(Py_3))]
+unsafe impl Sync for TypeStorage {}
+
+impl TypeStorage {
+    #[cfg(Py_3)]
+    pub const INIT: TypeStorage = TypeStorage {
+        heap_type: AtomicPtr::new(0 as *mut ffi::PyTypeObject),
+        #[cfg(all(Py_3_9, not(any(Py_LIMITED_API, PyPy))))]
+        interpreter: AtomicPtr::new(0 as *mut ffi::PyInterpreterState),
+    };
+
+    #[cfg(not(Py_3))]
+    pub const INIT: TypeStorage = TypeStorage {
+        type_object: UnsafeCell::new(ffi::PyTypeObject_INIT),
+    };
+
+    /// Returns the type object of the class, null as long as it is not initialized.
+    #[cfg(Py_3)]
+    pub fn get(&self) -> *mut ffi::PyTypeObject {
+        self.heap_type.load(Ordering::Acquire)
+    }
+
+    /// Returns the type object of the class.
+    #[cfg(not(Py_3))]
+    pub fn get(&self) -> *mut ffi::PyTypeObject {
+        self.type_object.get()
+    }
+
+    /// Checks whether the class is initialized.
+    #[cfg(Py_3)]
+    pub fn is_ready(&self) -> bool {
+        !self.get().is_null()
+    }
+
+    /// Checks whether the class is initialized.
+    #[cfg(not(Py_3))]
+    pub fn is_ready(&self) -> bool {
+        unsafe { ((*self.get()).tp_flags & ffi::Py_TPFLAGS_READY) != 0 }
+    }
+
+    /// Stores the created heap type, the storage owns the passed reference.
+    #[cfg(Py_3)]
+    fn set(&self, ty: *mut ffi::PyTypeObject) {
+        #[cfg(all(Py_3_9, not(any(Py_LIMITED_API, PyPy))))]
+        self.interpreter.store(unsafe { pythonrun::current_interpreter() }, Ordering::Relaxed);
+        self.heap_type.store(ty, Ordering::Release)
+    }
+
+    /// Checks that the class `name` is used in the interpreter which created it.
+    #[cfg(all(Py_3_9, not(any(Py_LIMITED_API, PyPy))))]
+    pub fn check_interpreter(&self, name: &str) -> PyResult<()> {
+        if !self.is_ready() {
+            return Ok(())
+        }
+        let interpreter = self.interpreter.load(Ordering::Relaxed);
+        if interpreter != unsafe { pythonrun::current_interpreter() } {
+            return Err(PyErr::new::<exc::RuntimeError, _>(format!(
+                "class {} was initialized by another interpreter, \
+                 classes can not be shared between sub-interpreters", name)))
+        }
+        Ok(())
+    }
+
+    /// Checks that the class `name` is used in the interpreter which created it.
+    ///
+    /// The interpreter can only be determined since Python 3.9,
+    /// it can't be determined with the limited API or on PyPy.
+    #[cfg(not(all(Py_3_9, not(any(Py_LIMITED_API, PyPy)))))]
+    pub fn check_interpreter(&self, _name: &str) -> PyResult<()> {
+        Ok(())
+    }
+}
+
 /// Special object that is used for python object creation.
 /// `pyo3` library automatically creates this object for class `__new__` method.
 /// Behavior is undefined if constructor of custom class does not initialze
@@ -133,8 +247,7 @@ impl PyRawObject {
     pub unsafe fn new(py: Python,
                       tp_ptr: *mut ffi::PyTypeObject,
                       curr_ptr: *mut ffi::PyTypeObject) -> PyResult<PyRawObject> {
-        let alloc = (*curr_ptr).tp_alloc.unwrap_or(ffi::PyType_GenericAlloc);
-        let ptr = alloc(curr_ptr, 0);
+        let ptr = tp_alloc(curr_ptr)(curr_ptr, 0);
 
         if !ptr.is_null() {
             Ok(PyRawObject {
@@ -242,8 +355,7 @@ impl<T> PyObjectAlloc<T> for T where T : PyTypeInfo {
         T::init_type();
 
         let tp_ptr = T::type_object();
-        let alloc = (*tp_ptr).tp_alloc.unwrap_or(ffi::PyType_GenericAlloc);
-        let obj = alloc(tp_ptr, 0);
+        let obj = tp_alloc(tp_ptr)(tp_ptr, 0);
 
         Ok(obj)
     }
@@ -252,51 +364,96 @@ impl<T> PyObjectAlloc<T> for T where T : PyTypeInfo {
     default unsafe fn dealloc(py: Python, obj: *mut ffi::PyObject) {
         Self::drop(py, obj);
 
-        if ffi::PyObject_CallFinalizerFromDealloc(obj) < 0 {
+        if call_finalizer_from_dealloc(obj) < 0 {
             return
         }
 
-        match (*T::type_object()).tp_free {
-            Some(free) => free(obj as *mut ::c_void),
-            None => {
-                let ty = ffi::Py_TYPE(obj);
-                if ffi::PyType_IS_GC(ty) != 0 {
-                    ffi::PyObject_GC_Del(obj as *mut ::c_void);
-                } else {
-                    ffi::PyObject_Free(obj as *mut ::c_void);
-                }
-
-                // For heap types, PyType_GenericAlloc calls INCREF on the type objects,
-                // so we need to call DECREF here:
-                if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
-                    ffi::Py_DECREF(ty as *mut ffi::PyObject);
-                }
-            }
-        }
+        free_object::<T>(obj)
     }
 
     #[cfg(not(Py_3))]
     default unsafe fn dealloc(py: Python, obj: *mut ffi::PyObject) {
         Self::drop(py, obj);
 
-        match (*T::type_object()).tp_free {
-            Some(free) => free(obj as *mut ::c_void),
-            None => {
-                let ty = ffi::Py_TYPE(obj);
-                if ffi::PyType_IS_GC(ty) != 0 {
-                    ffi::PyObject_GC_Del(obj as *mut ::c_void);
-                } else {
-                    ffi::PyObject_Free(obj as *mut ::c_void);
-                }
+        free_object::<T>(obj)
+    }
+}
 
-                // For heap types, PyType_GenericAlloc calls INCREF on the type objects,
-                // so we need to call DECREF here:
-                if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
-                    ffi::Py_DECREF(ty as *mut ffi::PyObject);
-                }
+/// Frees the memory of `obj` and releases the reference to its type
+/// taken by `tp_alloc`.
+pub(crate) unsafe fn free_object<T: PyTypeInfo>(obj: *mut ffi::PyObject) {
+    let ty = ffi::Py_TYPE(obj);
+    match tp_free(ty) {
+        Some(free) => free(obj as *mut c_void),
+        None => {
+            if ffi::PyType_IS_GC(ty) != 0 {
+                ffi::PyObject_GC_Del(obj as *mut c_void);
+            } else {
+                ffi::PyObject_Free(obj as *mut c_void);
             }
         }
     }
+
+    // For heap types, PyType_GenericAlloc calls INCREF on the type objects,
+    // so we need to call DECREF here. Before python 3.8 the type of instances
+    // of python subclasses is released by `subtype_dealloc`.
+    if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 &&
+        (pythonrun::is_python3_at_least(8) || ty == T::type_object())
+    {
+        ffi::Py_DECREF(ty as *mut ffi::PyObject);
+    }
+}
+
+/// Returns the `tp_alloc` slot of `ty`, `PyType_GenericAlloc` if it is not set.
+#[cfg(not(Py_LIMITED_API))]
+unsafe fn tp_alloc(ty: *mut ffi::PyTypeObject) -> ffi::allocfunc {
+    (*ty).tp_alloc.unwrap_or(ffi::PyType_GenericAlloc)
+}
+
+/// Returns the `tp_alloc` slot of `ty`, `PyType_GenericAlloc` if it is not set.
+///
+/// Only the slots of heap types can be read with the limited API.
+#[cfg(Py_LIMITED_API)]
+unsafe fn tp_alloc(ty: *mut ffi::PyTypeObject) -> ffi::allocfunc {
+    if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
+        let slot = ffi::PyType_GetSlot(ty, ffi::Py_tp_alloc);
+        if !slot.is_null() {
+            return mem::transmute(slot)
+        }
+    }
+    ffi::PyType_GenericAlloc
+}
+
+/// Returns the `tp_free` slot of `ty`.
+#[cfg(not(Py_LIMITED_API))]
+unsafe fn tp_free(ty: *mut ffi::PyTypeObject) -> Option<ffi::freefunc> {
+    (*ty).tp_free
+}
+
+/// Returns the `tp_free` slot of `ty`.
+///
+/// Only the slots of heap types can be read with the limited API.
+#[cfg(Py_LIMITED_API)]
+unsafe fn tp_free(ty: *mut ffi::PyTypeObject) -> Option<ffi::freefunc> {
+    if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
+        let slot = ffi::PyType_GetSlot(ty, ffi::Py_tp_free);
+        if !slot.is_null() {
+            return Some(mem::transmute(slot))
+        }
+    }
+    None
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+pub(crate) unsafe fn call_finalizer_from_dealloc(obj: *mut ffi::PyObject) -> c_int {
+    ffi::PyObject_CallFinalizerFromDealloc(obj)
+}
+
+/// `tp_finalize` can not be called from `tp_dealloc` with the limited API or on PyPy,
+/// classes do not define it.
+#[cfg(all(Py_3, any(Py_LIMITED_API, PyPy)))]
+pub(crate) unsafe fn call_finalizer_from_dealloc(_obj: *mut ffi::PyObject) -> c_int {
+    0
 }
 
 /// Trait implemented by Python object types that have a corresponding type object.
@@ -314,6 +471,7 @@ pub trait PyTypeObject {
         where Self: Sized + PyObjectAlloc<Self> + PyTypeInfo
     {
         <Self as PyTypeObject>::init_type();
+        <Self as PyTypeInfo>::type_storage().check_interpreter(<Self as PyTypeInfo>::NAME)?;
 
         unsafe {
             let ptr = <Self as PyObjectAlloc<Self>>::alloc(py)?;
@@ -329,15 +487,13 @@ impl<T> PyTypeObject for T where T: PyObjectAlloc<T> + PyTypeInfo {
 
     #[inline]
     default fn init_type() {
-        unsafe {
-            if ((*<T>::type_object()).tp_flags & ffi::Py_TPFLAGS_READY) == 0 {
-                // automatically initialize the class on-demand
-                let gil = Python::acquire_gil();
-                let py = gil.python();
+        if !<T>::type_storage().is_ready() {
+            // automatically initialize the class on-demand
+            let gil = Python::acquire_gil();
+            let py = gil.python();
 
-                initialize_type::<T>(py, None).expect(
-                    format!("An error occurred while initializing class {}", T::NAME).as_ref());
-            }
+            initialize_type::<T>(py, None).expect(
+                format!("An error occurred while initializing class {}", T::NAME).as_ref());
         }
     }
 
@@ -361,13 +517,31 @@ pub fn initialize_type<'p, T>(py: Python<'p>, module_name: Option<&str>) -> PyRe
     let name = name.expect(
         "Module name/type name must not contain NUL byte").into_raw();
 
-    let type_object: &mut ffi::PyTypeObject = unsafe{&mut *T::type_object()};
-    let base_type_object: &mut ffi::PyTypeObject = unsafe {
-        &mut *<T::BaseType as PyTypeInfo>::type_object() };
+    // register type object
+    let type_object = create_type::<T>(py, name)?;
+
+    // class attributes, instances of the type can only be created once it is ready
+    let attrs = <T as class::methods::PyClassAttrsProtocolImpl>::class_attrs(py)?;
+    if !attrs.is_empty() {
+        unsafe {
+            for (name, value) in attrs {
+                let name = CString::new(name).expect(
+                    "Class attribute name must not contain NUL byte");
+                set_class_attr(py, type_object, &name, value.as_ptr())?;
+            }
+            ffi::PyType_Modified(type_object);
+        }
+    }
+    Ok(())
+}
 
+/// Fills in `type_object` with the slots of `T`.
+fn fill_type_object<T>(type_object: &mut ffi::PyTypeTemplate, name: *const c_char) -> PyResult<()>
+    where T: PyObjectAlloc<T> + PyTypeInfo
+{
     type_object.tp_name = name;
     type_object.tp_doc = T::DESCRIPTION.as_ptr() as *const _;
-    type_object.tp_base = base_type_object;
+    type_object.tp_base = <T::BaseType as PyTypeInfo>::type_object();
 
     // dealloc
     type_object.tp_dealloc = Some(tp_dealloc_callback::<T>);
@@ -425,6 +599,7 @@ pub fn initialize_type<'p, T>(py: Python<'p>, module_name: Option<&str>) -> PyRe
     async_methods::<T>(type_object);
 
     // buffer protocol
+    #[cfg(not(Py_LIMITED_API))]
     if let Some(meth) = <T as class::buffer::PyBufferProtocolImpl>::tp_as_buffer() {
         type_object.tp_as_buffer = Box::into_raw(Box::new(meth));
     } else {
@@ -465,18 +640,319 @@ pub fn initialize_type<'p, T>(py: Python<'p>, module_name: Option<&str>) -> PyRe
         type_object.tp_flags |= ffi::Py_TPFLAGS_HEAPTYPE
     }
 
-    // register type object
+    Ok(())
+}
+
+/// Initializes the static type object of `T` in place.
+#[cfg(not(Py_3))]
+fn create_type<T>(py: Python, name: *const c_char) -> PyResult<*mut ffi::PyTypeObject>
+    where T: PyObjectAlloc<T> + PyTypeInfo
+{
+    let type_object = T::type_storage().get();
     unsafe {
-        if ffi::PyType_Ready(type_object) == 0 {
-            Ok(())
-        } else {
-            PyErr::fetch(py).into()
+        fill_type_object::<T>(&mut *type_object, name)?;
+        if ffi::PyType_Ready(type_object) != 0 {
+            return Err(PyErr::fetch(py))
         }
     }
+    Ok(type_object)
+}
+
+/// Creates the heap type of `T`.
+///
+/// The slots of `T` are filled in a temporary type object,
+/// which is converted to the `PyType_Spec` of the heap type.
+#[cfg(Py_3)]
+fn create_type<T>(py: Python, name: *const c_char) -> PyResult<*mut ffi::PyTypeObject>
+    where T: PyObjectAlloc<T> + PyTypeInfo
+{
+    let mut template = ffi::PyTypeObject_INIT;
+    fill_type_object::<T>(&mut template, name)?;
+    unsafe {
+        let ty = type_from_template::<T>(py, &template);
+        free_slot_tables(&mut template);
+        let ty = ty?;
+        T::type_storage().set(ty);
+        Ok(ty)
+    }
+}
+
+#[cfg(any(Py_LIMITED_API, PyPy))]
+unsafe fn check_template<T: PyTypeInfo>(template: &ffi::PyTypeTemplate) -> PyResult<()> {
+    let api = if cfg!(PyPy) { "on PyPy" } else { "with the limited API" };
+    if !template.tp_as_buffer.is_null() {
+        return Err(PyErr::new::<exc::TypeError, _>(format!(
+            "{}: the buffer protocol is not supported {}", T::NAME, api)))
+    }
+    if template.tp_weaklistoffset != 0 || template.tp_dictoffset != 0 {
+        return Err(PyErr::new::<exc::TypeError, _>(format!(
+            "{}: weakref and dict support are not available {}", T::NAME, api)))
+    }
+    Ok(())
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+unsafe fn check_template<T: PyTypeInfo>(_template: &ffi::PyTypeTemplate) -> PyResult<()> {
+    Ok(())
+}
+
+/// Sets the slots which can not be passed to `PyType_FromSpec`.
+///
+/// The buffer procs are copied into the heap type object, offsets are set directly.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+unsafe fn fixup_heap_type(ty: *mut ffi::PyTypeObject, template: &ffi::PyTypeTemplate) {
+    if !template.tp_as_buffer.is_null() {
+        let heap_type = ty as *mut ffi::PyHeapTypeObject;
+        (*heap_type).as_buffer = *template.tp_as_buffer;
+        (*ty).tp_as_buffer = &mut (*heap_type).as_buffer;
+    }
+    if template.tp_weaklistoffset != 0 {
+        (*ty).tp_weaklistoffset = template.tp_weaklistoffset;
+    }
+    if template.tp_dictoffset != 0 {
+        (*ty).tp_dictoffset = template.tp_dictoffset;
+    }
+}
+
+#[cfg(any(Py_LIMITED_API, PyPy))]
+unsafe fn fixup_heap_type(_ty: *mut ffi::PyTypeObject, _template: &ffi::PyTypeTemplate) {}
+
+/// Frees the boxed slot tables of `template`, the heap type holds copies of them.
+#[cfg(Py_3)]
+unsafe fn free_slot_tables(template: &mut ffi::PyTypeTemplate) {
+    if !template.tp_as_number.is_null() {
+        drop(Box::from_raw(template.tp_as_number));
+    }
+    if !template.tp_as_sequence.is_null() {
+        drop(Box::from_raw(template.tp_as_sequence));
+    }
+    if !template.tp_as_mapping.is_null() {
+        drop(Box::from_raw(template.tp_as_mapping));
+    }
+    if !template.tp_as_async.is_null() {
+        drop(Box::from_raw(template.tp_as_async));
+    }
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        if !template.tp_as_buffer.is_null() {
+            drop(Box::from_raw(template.tp_as_buffer));
+        }
+    }
+}
+
+/// Creates a heap type from the slots of `template`.
+#[cfg(Py_3)]
+unsafe fn type_from_template<T>(py: Python, template: &ffi::PyTypeTemplate)
+                                -> PyResult<*mut ffi::PyTypeObject> where T: PyTypeInfo
+{
+    check_template::<T>(template)?;
+
+    let mut slots = Vec::new();
+    macro_rules! slot {
+        ($slot:ident, $value:expr) => {
+            if let Some(func) = $value {
+                slots.push(ffi::PyType_Slot { slot: ffi::$slot, pfunc: func as *mut c_void });
+            }
+        };
+    }
+    macro_rules! ptr_slot {
+        ($slot:ident, $value:expr) => {
+            if !$value.is_null() {
+                slots.push(ffi::PyType_Slot { slot: ffi::$slot, pfunc: $value as *mut c_void });
+            }
+        };
+    }
+
+    slot!(Py_tp_dealloc, template.tp_dealloc);
+    slot!(Py_tp_getattr, template.tp_getattr);
+    slot!(Py_tp_setattr, template.tp_setattr);
+    slot!(Py_tp_repr, template.tp_repr);
+    slot!(Py_tp_hash, template.tp_hash);
+    slot!(Py_tp_call, template.tp_call);
+    slot!(Py_tp_str, template.tp_str);
+    slot!(Py_tp_getattro, template.tp_getattro);
+    slot!(Py_tp_setattro, template.tp_setattro);
+    // `PyType_FromSpec` rejects gc types without a traverse function
+    if template.tp_flags & ffi::Py_TPFLAGS_HAVE_GC != 0 && template.tp_traverse.is_none() {
+        slot!(Py_tp_traverse, Some(tp_traverse_noop as ffi::traverseproc));
+    } else {
+        slot!(Py_tp_traverse, template.tp_traverse);
+    }
+    slot!(Py_tp_clear, template.tp_clear);
+    slot!(Py_tp_richcompare, template.tp_richcompare);
+    slot!(Py_tp_iter, template.tp_iter);
+    slot!(Py_tp_iternext, template.tp_iternext);
+    slot!(Py_tp_descr_get, template.tp_descr_get);
+    slot!(Py_tp_descr_set, template.tp_descr_set);
+    slot!(Py_tp_init, template.tp_init);
+    slot!(Py_tp_alloc, template.tp_alloc);
+    // heap types inherit `tp_new` of the base, which would create uninitialized instances
+    slot!(Py_tp_new, template.tp_new.or(Some(tp_new_no_constructor as ffi::newfunc)));
+    slot!(Py_tp_free, template.tp_free);
+    ptr_slot!(Py_tp_doc, template.tp_doc);
+    ptr_slot!(Py_tp_methods, template.tp_methods);
+    ptr_slot!(Py_tp_members, template.tp_members);
+    ptr_slot!(Py_tp_getset, template.tp_getset);
+
+    if let Some(nb) = template.tp_as_number.as_ref() {
+        slot!(Py_nb_add, nb.nb_add);
+        slot!(Py_nb_subtract, nb.nb_subtract);
+        slot!(Py_nb_multiply, nb.nb_multiply);
+        slot!(Py_nb_remainder, nb.nb_remainder);
+        slot!(Py_nb_divmod, nb.nb_divmod);
+        slot!(Py_nb_power, nb.nb_power);
+        slot!(Py_nb_negative, nb.nb_negative);
+        slot!(Py_nb_positive, nb.nb_positive);
+        slot!(Py_nb_absolute, nb.nb_absolute);
+        slot!(Py_nb_bool, nb.nb_bool);
+        slot!(Py_nb_invert, nb.nb_invert);
+        slot!(Py_nb_lshift, nb.nb_lshift);
+        slot!(Py_nb_rshift, nb.nb_rshift);
+        slot!(Py_nb_and, nb.nb_and);
+        slot!(Py_nb_xor, nb.nb_xor);
+        slot!(Py_nb_or, nb.nb_or);
+        slot!(Py_nb_int, nb.nb_int);
+        slot!(Py_nb_float, nb.nb_float);
+        slot!(Py_nb_inplace_add, nb.nb_inplace_add);
+        slot!(Py_nb_inplace_subtract, nb.nb_inplace_subtract);
+        slot!(Py_nb_inplace_multiply, nb.nb_inplace_multiply);
+        slot!(Py_nb_inplace_remainder, nb.nb_inplace_remainder);
+        slot!(Py_nb_inplace_power, nb.nb_inplace_power);
+        slot!(Py_nb_inplace_lshift, nb.nb_inplace_lshift);
+        slot!(Py_nb_inplace_rshift, nb.nb_inplace_rshift);
+        slot!(Py_nb_inplace_and, nb.nb_inplace_and);
+        slot!(Py_nb_inplace_xor, nb.nb_inplace_xor);
+        slot!(Py_nb_inplace_or, nb.nb_inplace_or);
+        slot!(Py_nb_floor_divide, nb.nb_floor_divide);
+        slot!(Py_nb_true_divide, nb.nb_true_divide);
+        slot!(Py_nb_inplace_floor_divide, nb.nb_inplace_floor_divide);
+        slot!(Py_nb_inplace_true_divide, nb.nb_inplace_true_divide);
+        slot!(Py_nb_index, nb.nb_index);
+        slot!(Py_nb_matrix_multiply, nb.nb_matrix_multiply);
+        slot!(Py_nb_inplace_matrix_multiply, nb.nb_inplace_matrix_multiply);
+    }
+    if let Some(sq) = template.tp_as_sequence.as_ref() {
+        slot!(Py_sq_length, sq.sq_length);
+        slot!(Py_sq_concat, sq.sq_concat);
+        slot!(Py_sq_repeat, sq.sq_repeat);
+        slot!(Py_sq_item, sq.sq_item);
+        slot!(Py_sq_ass_item, sq.sq_ass_item);
+        slot!(Py_sq_contains, sq.sq_contains);
+        slot!(Py_sq_inplace_concat, sq.sq_inplace_concat);
+        slot!(Py_sq_inplace_repeat, sq.sq_inplace_repeat);
+    }
+    if let Some(mp) = template.tp_as_mapping.as_ref() {
+        slot!(Py_mp_length, mp.mp_length);
+        slot!(Py_mp_subscript, mp.mp_subscript);
+        slot!(Py_mp_ass_subscript, mp.mp_ass_subscript);
+    }
+    if let Some(am) = template.tp_as_async.as_ref() {
+        slot!(Py_am_await, am.am_await);
+        slot!(Py_am_aiter, am.am_aiter);
+        slot!(Py_am_anext, am.am_anext);
+    }
+    slots.push(ffi::PyType_Slot { slot: 0, pfunc: ptr::null_mut() });
+
+    let mut spec = ffi::PyType_Spec {
+        name: template.tp_name,
+        basicsize: template.tp_basicsize as c_int,
+        itemsize: 0,
+        flags: template.tp_flags as c_uint,
+        slots: slots.as_mut_ptr(),
+    };
+    let bases = ffi::PyTuple_Pack(1, template.tp_base as *mut ffi::PyObject);
+    if bases.is_null() {
+        return Err(PyErr::fetch(py))
+    }
+    let base_flags = match mark_base_type(py, template.tp_base) {
+        Ok(flags) => flags,
+        Err(err) => {
+            ffi::Py_DECREF(bases);
+            return Err(err)
+        }
+    };
+    let ty = ffi::PyType_FromSpecWithBases(&mut spec, bases);
+    unmark_base_type(template.tp_base, base_flags);
+    ffi::Py_DECREF(bases);
+    if ty.is_null() {
+        return Err(PyErr::fetch(py))
+    }
+    let ty = ty as *mut ffi::PyTypeObject;
+    fixup_heap_type(ty, template);
+    Ok(ty)
+}
+
+/// Allows `PyType_FromSpecWithBases` to use the class `base` as a base type.
+///
+/// Python code can only subclass classes declared with `subclass`, so the flag
+/// is set while the subclass is created. Returns the original flags of `base`.
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+unsafe fn mark_base_type(_py: Python, base: *mut ffi::PyTypeObject) -> PyResult<c_ulong> {
+    let flags = (*base).tp_flags;
+    if flags & ffi::Py_TPFLAGS_HEAPTYPE != 0 {
+        (*base).tp_flags |= ffi::Py_TPFLAGS_BASETYPE;
+    }
+    Ok(flags)
+}
+
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+unsafe fn unmark_base_type(base: *mut ffi::PyTypeObject, flags: c_ulong) {
+    (*base).tp_flags = flags;
+}
+
+/// The type object of the base can't be changed with the limited API and on PyPy,
+/// so only classes declared with `subclass` can be used as a base.
+#[cfg(any(Py_LIMITED_API, PyPy))]
+unsafe fn mark_base_type(py: Python, base: *mut ffi::PyTypeObject) -> PyResult<c_ulong> {
+    let flags = ffi::PyType_GetFlags(base);
+    if flags & ffi::Py_TPFLAGS_HEAPTYPE != 0 && flags & ffi::Py_TPFLAGS_BASETYPE == 0 {
+        return Err(exc::TypeError::new(format!(
+            "{} can't be used as a base class, it is not declared with `subclass`",
+            PyType::from_type_ptr(py, base).name())))
+    }
+    Ok(flags)
+}
+
+#[cfg(any(Py_LIMITED_API, PyPy))]
+unsafe fn unmark_base_type(_base: *mut ffi::PyTypeObject, _flags: c_ulong) {}
+
+#[cfg(Py_3)]
+unsafe extern "C" fn tp_new_no_constructor(_subtype: *mut ffi::PyTypeObject,
+                                           _args: *mut ffi::PyObject,
+                                           _kwds: *mut ffi::PyObject) -> *mut ffi::PyObject
+{
+    let _pool = pythonrun::GILPool::new_no_pointers();
+    let py = Python::assume_gil_acquired();
+    PyErr::new::<exc::TypeError, _>("No constructor defined").restore(py);
+    ptr::null_mut()
+}
+
+#[cfg(Py_3)]
+unsafe extern "C" fn tp_traverse_noop(_obj: *mut ffi::PyObject, _visit: ffi::visitproc,
+                                      _arg: *mut c_void) -> c_int {
+    0
+}
+
+#[cfg(not(Py_3))]
+unsafe fn set_class_attr(py: Python, type_object: *mut ffi::PyTypeObject,
+                         name: &CStr, value: *mut ffi::PyObject) -> PyResult<()> {
+    if ffi::PyDict_SetItemString((*type_object).tp_dict, name.as_ptr(), value) != 0 {
+        return Err(PyErr::fetch(py))
+    }
+    Ok(())
+}
+
+#[cfg(Py_3)]
+unsafe fn set_class_attr(py: Python, type_object: *mut ffi::PyTypeObject,
+                         name: &CStr, value: *mut ffi::PyObject) -> PyResult<()> {
+    if ffi::PyObject_SetAttrString(type_object as *mut ffi::PyObject, name.as_ptr(), value) != 0 {
+        return Err(PyErr::fetch(py))
+    }
+    Ok(())
 }
 
 #[cfg(Py_3)]
-fn async_methods<T>(type_info: &mut ffi::PyTypeObject) {
+fn async_methods<T>(type_info: &mut ffi::PyTypeTemplate) {
     if let Some(meth) = <T as class::async::PyAsyncProtocolImpl>::tp_as_async() {
         type_info.tp_as_async = Box::into_raw(Box::new(meth));
     } else {
@@ -485,11 +961,12 @@ fn async_methods<T>(type_info: &mut ffi::PyTypeObject) {
 }
 
 #[cfg(not(Py_3))]
-fn async_methods<T>(_type_info: &mut ffi::PyTypeObject) {}
+fn async_methods<T>(_type_info: &mut ffi::PyTypeTemplate) {}
 
 unsafe extern "C" fn tp_dealloc_callback<T>(obj: *mut ffi::PyObject)
     where T: PyObjectAlloc<T>
 {
+    #[cfg(not(Py_LIMITED_API))]
     debug!("DEALLOC: {:?} - {:?}", obj,
            CStr::from_ptr((*(*obj).ob_type).tp_name).to_string_lossy());
     let _pool = pythonrun::GILPool::new_no_pointers();
@@ -498,7 +975,7 @@ unsafe extern "C" fn tp_dealloc_callback<T>(obj: *mut ffi::PyObject)
 }
 
 #[cfg(Py_3)]
-fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeObject) {
+fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeTemplate) {
     if type_object.tp_traverse != None || type_object.tp_clear != None ||
         T::FLAGS & PY_TYPE_FLAG_GC != 0
     {
@@ -512,7 +989,7 @@ fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeObject) {
 }
 
 #[cfg(not(Py_3))]
-fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeObject) {
+fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeTemplate) {
     if type_object.tp_traverse != None || type_object.tp_clear != None ||
         T::FLAGS & PY_TYPE_FLAG_GC != 0
     {
@@ -0,0 +1,230 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Control of the Python `warnings` filters and capturing of issued warnings.
+//!
+//! Warnings are issued with [`PyErr::warn`](../struct.PyErr.html#method.warn),
+//! [`PyErr::warn_typed`](../struct.PyErr.html#method.warn_typed)
+//! and [`PyErr::warn_explicit`](../struct.PyErr.html#method.warn_explicit).
+
+use err::PyResult;
+use python::Python;
+use instance::{Py, AsPyRef};
+use objectprotocol::ObjectProtocol;
+use objects::{PyObjectRef, PyType, PyDict, PyModule};
+use typeob::PyTypeObject;
+use noargs::NoArgs;
+
+/// Action of a warnings filter, see `warnings.simplefilter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Turn matching warnings into exceptions
+    Error,
+    /// Never print matching warnings
+    Ignore,
+    /// Always print matching warnings
+    Always,
+    /// Print the first occurrence of matching warnings for each location
+    Default,
+    /// Print the first occurrence of matching warnings for each module
+    Module,
+    /// Print only the first occurrence of matching warnings
+    Once,
+}
+
+impl Action {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Action::Error => "error",
+            Action::Ignore => "ignore",
+            Action::Always => "always",
+            Action::Default => "default",
+            Action::Module => "module",
+            Action::Once => "once",
+        }
+    }
+}
+
+fn warnings_module(py: Python) -> PyResult<&PyModule> {
+    py.import("warnings")
+}
+
+/// Inserts a filter for warnings of category `T` at the front of the filters list,
+/// like `warnings.simplefilter(action, category=T)`.
+pub fn simplefilter<T>(py: Python, action: Action) -> PyResult<()> where T: PyTypeObject {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("category", py.get_type::<T>())?;
+    warnings_module(py)?.call("simplefilter", (action.as_str(),), kwargs)?;
+    Ok(())
+}
+
+/// Resets the warnings filters, like `warnings.resetwarnings()`.
+pub fn resetwarnings(py: Python) -> PyResult<()> {
+    warnings_module(py)?.call("resetwarnings", NoArgs, NoArgs)?;
+    Ok(())
+}
+
+/// A warning captured by [`CatchWarnings`](struct.CatchWarnings.html).
+#[derive(Debug)]
+pub struct WarningMessage {
+    /// The warning category
+    pub category: Py<PyType>,
+    /// The warning message
+    pub message: String,
+    /// The file the warning was attributed to
+    pub filename: String,
+    /// The line number the warning was attributed to
+    pub lineno: u32,
+}
+
+impl WarningMessage {
+    /// Checks whether the category of this warning is `T` or a subclass of `T`.
+    pub fn is_instance<T>(&self, py: Python) -> bool where T: PyTypeObject {
+        self.category.as_ref(py).is_subclass::<T>().unwrap_or(false)
+    }
+
+    fn from_object(ob: &PyObjectRef) -> PyResult<WarningMessage> {
+        let category: &PyType = ob.getattr("category")?.extract()?;
+        Ok(WarningMessage {
+            category: category.into(),
+            message: ob.getattr("message")?.str()?.to_string_lossy().into_owned(),
+            filename: ob.getattr("filename")?.extract()?,
+            lineno: ob.getattr("lineno")?.extract()?,
+        })
+    }
+}
+
+/// Records the warnings issued while it is alive,
+/// like `warnings.catch_warnings(record=True)`.
+///
+/// All warnings are recorded regardless of the active filters,
+/// and the previous filters are restored when the guard is finished or dropped.
+///
+/// ```
+/// use pyo3::{Python, PyErr, exc};
+/// use pyo3::warnings::CatchWarnings;
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+///
+/// let guard = CatchWarnings::new(py).unwrap();
+/// PyErr::warn_typed::<exc::DeprecationWarning>(py, "use bar() instead", 1).unwrap();
+/// let warnings = guard.finish().unwrap();
+///
+/// assert_eq!(warnings.len(), 1);
+/// assert!(warnings[0].is_instance::<exc::DeprecationWarning>(py));
+/// ```
+pub struct CatchWarnings<'p> {
+    py: Python<'p>,
+    manager: Option<&'p PyObjectRef>,
+    log: &'p PyObjectRef,
+}
+
+impl<'p> CatchWarnings<'p> {
+    /// Starts recording warnings.
+    pub fn new(py: Python<'p>) -> PyResult<CatchWarnings<'p>> {
+        let warnings = warnings_module(py)?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("record", true)?;
+        let manager = warnings.call("catch_warnings", NoArgs, kwargs)?;
+        let log = manager.call_method0("__enter__")?;
+
+        let guard = CatchWarnings { py: py, manager: Some(manager), log: log };
+        warnings.call("simplefilter", ("always",), NoArgs)?;
+        Ok(guard)
+    }
+
+    /// Stops recording and returns the warnings issued since the guard was created.
+    pub fn finish(mut self) -> PyResult<Vec<WarningMessage>> {
+        self.exit()?;
+        let mut messages = Vec::new();
+        for ob in self.log.iter()? {
+            messages.push(WarningMessage::from_object(ob?)?);
+        }
+        Ok(messages)
+    }
+
+    fn exit(&mut self) -> PyResult<()> {
+        if let Some(manager) = self.manager.take() {
+            let none = self.py.None();
+            manager.call_method1("__exit__", (&none, &none, &none))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'p> Drop for CatchWarnings<'p> {
+    fn drop(&mut self) {
+        if let Err(err) = self.exit() {
+            err.print(self.py);
+        }
+    }
+}
+
+/// Calls `f` and returns its result together with the warnings it issued.
+///
+/// See [`CatchWarnings`](struct.CatchWarnings.html).
+pub fn catch_warnings<'p, F, R>(py: Python<'p>, f: F) -> PyResult<(R, Vec<WarningMessage>)>
+    where F: FnOnce() -> R
+{
+    let guard = CatchWarnings::new(py)?;
+    let result = f();
+    Ok((result, guard.finish()?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use python::Python;
+    use err::PyErr;
+    use objects::exc;
+    use super::{catch_warnings, simplefilter, Action, CatchWarnings};
+
+    #[test]
+    fn test_catch_warnings() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let (result, warnings) = catch_warnings(py, || {
+            PyErr::warn_typed::<exc::DeprecationWarning>(py, "deprecated", 1).unwrap();
+            PyErr::warn_typed::<exc::UserWarning>(py, "careful", 1).unwrap();
+            42
+        }).unwrap();
+        assert_eq!(result, 42);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].is_instance::<exc::DeprecationWarning>(py));
+        assert!(warnings[0].is_instance::<exc::Warning>(py));
+        assert!(!warnings[0].is_instance::<exc::UserWarning>(py));
+        assert_eq!(warnings[0].message, "deprecated");
+        assert!(warnings[1].is_instance::<exc::UserWarning>(py));
+    }
+
+    #[test]
+    fn test_warn_explicit() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let guard = CatchWarnings::new(py).unwrap();
+        let category = py.get_type::<exc::FutureWarning>();
+        PyErr::warn_explicit(
+            py, category.as_ref(), "changes soon", "config.py", 12, None).unwrap();
+        let warnings = guard.finish().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].is_instance::<exc::FutureWarning>(py));
+        assert_eq!(warnings[0].filename, "config.py");
+        assert_eq!(warnings[0].lineno, 12);
+    }
+
+    #[test]
+    fn test_simplefilter_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let _guard = CatchWarnings::new(py).unwrap();
+        simplefilter::<exc::RuntimeWarning>(py, Action::Error).unwrap();
+        let err = PyErr::warn_typed::<exc::RuntimeWarning>(py, "boom", 1).unwrap_err();
+        assert!(err.is_instance::<exc::RuntimeWarning>(py));
+
+        simplefilter::<exc::RuntimeWarning>(py, Action::Ignore).unwrap();
+        PyErr::warn_typed::<exc::RuntimeWarning>(py, "quiet", 1).unwrap();
+    }
+}
@@ -92,6 +92,32 @@ fn empty_class_in_module() {
     assert_eq!(ty.getattr("__module__").unwrap().extract::<String>().unwrap(), "test_module.nested");
 }
 
+#[py::class]
+struct HeapTypeClass {
+    token: PyToken,
+}
+
+#[test]
+#[cfg(Py_3)]
+fn class_is_heap_type() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let module = PyModule::new(py, "test_module.heap").unwrap();
+    module.add_class::<HeapTypeClass>().unwrap();
+
+    let ty = module.getattr("HeapTypeClass").unwrap();
+    py_assert!(py, ty, "ty.__flags__ & (1 << 9)");
+    py_assert!(py, ty, "ty.__qualname__ == 'HeapTypeClass'");
+    py_assert!(py, ty, "ty.__module__ == 'test_module.heap'");
+
+    // instances hold a reference to their type
+    let refcnt = ty.get_refcnt();
+    let inst = py.init(|t| HeapTypeClass{token: t}).unwrap();
+    assert_eq!(ty.get_refcnt(), refcnt + 1);
+    unsafe { ffi::Py_DECREF(inst.into_ptr()); }
+    assert_eq!(ty.get_refcnt(), refcnt);
+}
+
 #[py::class]
 struct EmptyClassWithNew {
     token: PyToken
@@ -1228,13 +1254,92 @@ fn meth_args() {
     py_run!(py, inst, "assert inst.get_default() == 10");
     py_run!(py, inst, "assert inst.get_default(100) == 100");
     py_run!(py, inst, "assert inst.get_kwarg() == 10");
-    py_run!(py, inst, "assert inst.get_kwarg(100) == 10");
     py_run!(py, inst, "assert inst.get_kwarg(test=100) == 100");
     py_run!(py, inst, "assert inst.get_kwargs() == [(), None]");
     py_run!(py, inst, "assert inst.get_kwargs(1,2,3) == [(1,2,3), None]");
     py_run!(py, inst, "assert inst.get_kwargs(t=1,n=2) == [(), {'t': 1, 'n': 2}]");
     py_run!(py, inst, "assert inst.get_kwargs(1,2,3,t=1,n=2) == [(1,2,3), {'t': 1, 'n': 2}]");
-    // py_expect_exception!(py, inst, "inst.get_kwarg(100)", TypeError);
+    py_expect_exception!(py, inst, "inst.get_kwarg(100)", TypeError);
+}
+
+#[py::class]
+struct MethSignature {
+    token: PyToken
+}
+
+#[py::methods]
+impl MethSignature {
+    #[args(a, "/", b=1)]
+    fn pos_only(&self, a: i32, b: i32) -> PyResult<i32> {
+        Ok(a + b)
+    }
+    #[args(a, "*", b, c="vec![1, 2]")]
+    fn kw_only(&self, a: i32, b: i32, c: Vec<i32>) -> PyResult<i32> {
+        Ok(a + b + c.iter().sum::<i32>())
+    }
+    #[args(kwargs="**", a, args="*", b="-1")]
+    fn collect(&self, kwargs: Option<&PyDict>, a: i32, args: &PyTuple, b: i32) -> PyResult<PyObject> {
+        Ok((a, args, b, kwargs).to_object(self.py()))
+    }
+    #[args(a, "/", kwargs="**")]
+    fn pos_only_kwargs(&self, a: i32, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        Ok((a, kwargs).to_object(self.py()))
+    }
+}
+
+#[test]
+fn meth_signature() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let inst = py.init(|t| MethSignature{token: t}).unwrap();
+
+    py_run!(py, inst, "assert inst.pos_only(1) == 2");
+    py_run!(py, inst, "assert inst.pos_only(1, 2) == 3");
+    py_run!(py, inst, "assert inst.pos_only(1, b=3) == 4");
+    py_expect_exception!(py, inst, "inst.pos_only(a=1)", TypeError);
+
+    py_run!(py, inst, "assert inst.kw_only(1, b=2) == 6");
+    py_run!(py, inst, "assert inst.kw_only(1, b=2, c=[]) == 3");
+    py_expect_exception!(py, inst, "inst.kw_only(1, 2)", TypeError);
+    py_expect_exception!(py, inst, "inst.kw_only(1)", TypeError);
+
+    py_run!(py, inst, "assert inst.collect(1) == (1, (), -1, None)");
+    py_run!(py, inst, "assert inst.collect(1, 2, 3, b=4, c=5) == (1, (2, 3), 4, {'c': 5})");
+    py_run!(py, inst, "assert inst.collect(a=1) == (1, (), -1, None)");
+
+    py_run!(py, inst, "assert inst.pos_only_kwargs(1, a=2) == (1, {'a': 2})");
+}
+
+#[test]
+fn meth_signature_errors() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let inst = py.init(|t| MethSignature{token: t}).unwrap();
+    let d = PyDict::new(py);
+    d.set_item("inst", &inst).unwrap();
+
+    let check = |code: &str, msg: &str| {
+        let err = py.run(code, None, Some(d)).unwrap_err();
+        assert!(err.is_instance::<exc::TypeError>(py));
+        let value = err.to_object(py);
+        assert_eq!(value.as_ref(py).str().unwrap().to_string_lossy(), msg);
+    };
+
+    check("inst.pos_only(1, 2, 3)",
+          "MethSignature.pos_only() takes from 1 to 2 positional arguments but 3 were given");
+    check("inst.pos_only()",
+          "MethSignature.pos_only() missing 1 required positional argument: 'a'");
+    check("inst.pos_only(a=1, b=2)",
+          "MethSignature.pos_only() got some positional-only arguments passed as keyword \
+           arguments: 'a'");
+    check("inst.pos_only(1, b=2, c=3)",
+          "MethSignature.pos_only() got an unexpected keyword argument 'c'");
+    check("inst.pos_only(1, 2, b=3)",
+          "MethSignature.pos_only() got multiple values for argument 'b'");
+    check("inst.kw_only(1, 2)",
+          "MethSignature.kw_only() takes 1 positional argument but 2 were given");
+    check("inst.kw_only(1)",
+          "MethSignature.kw_only() missing 1 required keyword-only argument: 'b'");
 }
 
 #[py::class(subclass)]
@@ -1334,6 +1439,7 @@ impl SubClass {
 }
 
 #[test]
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
 fn inheritance_with_new_methods() {
     let gil = Python::acquire_gil();
     let py = gil.python();
@@ -1341,10 +1447,22 @@ fn inheritance_with_new_methods() {
     let typeobj = py.get_type::<SubClass>();
     let inst = typeobj.call(NoArgs, NoArgs).unwrap();
     py_run!(py, inst, "assert inst.val1 == 10; assert inst.val2 == 5");
+    py_expect_exception!(py, typebase, "class Sub(typebase): pass", TypeError);
 }
 
+#[test]
+#[cfg(any(Py_LIMITED_API, PyPy))]
+fn inheritance_requires_subclass() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typebase = py.get_type::<BaseClass>();
+    py_expect_exception!(py, typebase, "class Sub(typebase): pass", TypeError);
+    let err = pyo3::typeob::initialize_type::<SubClass>(py, None).unwrap_err();
+    assert!(err.is_instance::<exc::TypeError>(py));
+}
 
-#[py::class]
+
+#[py::class(subclass)]
 struct BaseClassWithDrop {
     token: PyToken,
     data: Option<Arc<AtomicBool>>,
@@ -1445,3 +1563,38 @@ fn mut_ref_arg() {
     py.run("inst1.set_other(inst2)", None, Some(d)).unwrap();
     assert_eq!(inst2.as_ref(py).n, 100);
 }
+
+#[py::class]
+#[derive(Debug, PartialEq)]
+enum State {
+    Idle,
+    Running = 5,
+    Done,
+}
+
+#[test]
+fn enum_class() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typeobj = py.get_type::<State>();
+
+    py_assert!(py, typeobj, "typeobj.Idle == typeobj.Idle");
+    py_assert!(py, typeobj, "typeobj.Idle != typeobj.Running");
+    py_assert!(py, typeobj, "typeobj.Idle != 0");
+    py_assert!(py, typeobj, "repr(typeobj.Running) == 'State.Running'");
+    py_assert!(py, typeobj, "int(typeobj.Running) == 5");
+    py_assert!(py, typeobj, "int(typeobj.Done) == 6");
+    py_assert!(py, typeobj, "len({typeobj.Idle, typeobj.Idle, typeobj.Done}) == 2");
+    py_expect_exception!(py, typeobj, "typeobj()", TypeError);
+
+    let running = State::Running.into_object(py);
+    let d = PyDict::new(py);
+    d.set_item("typeobj", typeobj).unwrap();
+    d.set_item("running", &running).unwrap();
+    py.run("assert running is typeobj.Running", None, Some(d)).unwrap();
+
+    assert_eq!(running.extract::<State>(py).unwrap(), State::Running);
+    let done = py.eval("typeobj.Done", None, Some(d)).unwrap();
+    assert_eq!(done.extract::<State>().unwrap(), State::Done);
+    assert!(py.eval("5", None, None).unwrap().extract::<State>().is_err());
+}
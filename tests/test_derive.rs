@@ -0,0 +1,253 @@
+#![feature(proc_macro, specialization)]
+
+extern crate pyo3;
+
+use pyo3::*;
+use pyo3::py::{FromPyObject, ToPyObject, IntoPyObject};
+
+
+fn extract_err<'a, T: FromPyObject<'a>>(py: Python, ob: &'a PyObjectRef) -> String {
+    let err = ob.extract::<T>().err().expect("extraction should fail");
+    let value = err.to_object(py);
+    value.as_ref(py).str().unwrap().to_string_lossy().into_owned()
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+#[pyo3(item)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+#[pyo3(item)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+    #[pyo3(item = "max-connections")]
+    max_connections: usize,
+}
+
+#[test]
+fn derive_from_dict() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = py.eval(
+        "{'name': 'test', 'max-connections': 10, \
+          'servers': [{'host': 'localhost', 'port': 80}]}", None, None).unwrap();
+    let config: Config = ob.extract().unwrap();
+    assert_eq!(config, Config {
+        name: "test".to_owned(),
+        servers: vec![Server { host: "localhost".to_owned(), port: 80 }],
+        max_connections: 10,
+    });
+}
+
+#[test]
+fn derive_from_dict_error_path() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = py.eval(
+        "{'name': 'test', 'max-connections': 10, 'servers': [\
+            {'host': 'a', 'port': 1}, {'host': 'b', 'port': 2}, {'host': 'c', 'port': 'x'}]}",
+        None, None).unwrap();
+    let msg = extract_err::<Config>(py, ob);
+    assert!(msg.starts_with("failed to extract `Config.servers[2].port`: "), msg);
+
+    let ob = py.eval("{'name': 'test', 'servers': []}", None, None).unwrap();
+    let msg = extract_err::<Config>(py, ob);
+    assert!(msg.starts_with("failed to extract `Config.max-connections`: "), msg);
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+struct Point {
+    x: f64,
+    #[pyo3(attribute = "y_coord")]
+    y: f64,
+    #[pyo3(item)]
+    label: String,
+}
+
+#[test]
+fn derive_from_attributes() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let d = PyDict::new(py);
+    py.run("class P(dict):\n    x = 1.0\n    y_coord = 2.0\np = P(label='origin')",
+           None, Some(d)).unwrap();
+    let point: Point = d.get_item("p").unwrap().extract().unwrap();
+    assert_eq!(point, Point { x: 1.0, y: 2.0, label: "origin".to_owned() });
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+struct Pair(i32, String);
+
+#[derive(Debug, PartialEq, FromPyObject)]
+struct Wrapper(Vec<i32>);
+
+#[test]
+fn derive_tuple_struct() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = py.eval("(1, 'a')", None, None).unwrap();
+    assert_eq!(ob.extract::<Pair>().unwrap(), Pair(1, "a".to_owned()));
+
+    let ob = py.eval("[1, 2, 3]", None, None).unwrap();
+    assert_eq!(ob.extract::<Wrapper>().unwrap(), Wrapper(vec![1, 2, 3]));
+
+    let ob = py.eval("(1, 2, 3)", None, None).unwrap();
+    let msg = extract_err::<Pair>(py, ob);
+    assert!(msg.starts_with("failed to extract `Pair`: "), msg);
+
+    let ob = py.eval("(1, 2)", None, None).unwrap();
+    let msg = extract_err::<Pair>(py, ob);
+    assert!(msg.starts_with("failed to extract `Pair[1]`: "), msg);
+}
+
+#[derive(Debug, PartialEq, FromPyObject)]
+enum Value {
+    Int(i64),
+    Str(String),
+    #[pyo3(item)]
+    Range { start: i64, stop: i64 },
+}
+
+#[test]
+fn derive_enum() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = py.eval("42", None, None).unwrap();
+    assert_eq!(ob.extract::<Value>().unwrap(), Value::Int(42));
+    let ob = py.eval("'foo'", None, None).unwrap();
+    assert_eq!(ob.extract::<Value>().unwrap(), Value::Str("foo".to_owned()));
+    let ob = py.eval("{'start': 1, 'stop': 5}", None, None).unwrap();
+    assert_eq!(ob.extract::<Value>().unwrap(), Value::Range { start: 1, stop: 5 });
+
+    let ob = py.eval("None", None, None).unwrap();
+    let err = ob.extract::<Value>().unwrap_err();
+    assert!(err.is_instance::<exc::TypeError>(py));
+    let msg = extract_err::<Value>(py, ob);
+    assert!(msg.starts_with("failed to extract `Value`: no variant matched"), msg);
+}
+
+#[derive(ToPyObject, IntoPyObject)]
+struct Stats {
+    count: usize,
+    #[pyo3(item = "mean-value")]
+    mean: f64,
+    tags: Vec<String>,
+}
+
+#[test]
+fn derive_to_dict() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let stats = Stats { count: 3, mean: 1.5, tags: vec!["a".to_owned()] };
+    let ob = stats.to_object(py);
+    py_assert(py, &ob, "ob == {'count': 3, 'mean-value': 1.5, 'tags': ['a']}");
+    let ob = stats.into_object(py);
+    py_assert(py, &ob, "ob == {'count': 3, 'mean-value': 1.5, 'tags': ['a']}");
+}
+
+#[derive(ToPyObject, IntoPyObject)]
+#[pyo3(namespace)]
+struct Settings {
+    verbose: bool,
+    level: i32,
+}
+
+#[derive(ToPyObject, IntoPyObject)]
+#[pyo3(namedtuple)]
+struct Coord {
+    x: i32,
+    y: i32,
+}
+
+#[derive(ToPyObject, IntoPyObject)]
+struct Triple(i32, String, bool);
+
+#[derive(ToPyObject)]
+struct Meters(f64);
+
+#[test]
+fn derive_to_namespace_and_tuples() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = Settings { verbose: true, level: 2 }.into_object(py);
+    py_assert(py, &ob, "type(ob).__name__ == 'SimpleNamespace'");
+    py_assert(py, &ob, "ob.verbose is True and ob.level == 2");
+
+    let first = Coord { x: 1, y: 2 }.to_object(py);
+    py_assert(py, &first, "type(ob).__name__ == 'Coord' and ob == (1, 2) and ob.y == 2");
+    let second = Coord { x: 3, y: 4 }.into_object(py);
+    py_assert(py, &second, "type(ob).__name__ == 'Coord' and ob.x == 3");
+    assert_eq!(first.getattr(py, "__class__").unwrap(),
+               second.getattr(py, "__class__").unwrap());
+
+    let ob = Triple(1, "a".to_owned(), false).to_object(py);
+    py_assert(py, &ob, "ob == (1, 'a', False)");
+
+    let ob = Meters(2.5).to_object(py);
+    py_assert(py, &ob, "ob == 2.5");
+}
+
+#[test]
+#[cfg(all(Py_3, not(any(Py_LIMITED_API, PyPy))))]
+fn derive_namedtuple_per_interpreter() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let main_type = Coord { x: 1, y: 2 }.to_object(py).getattr(py, "__class__").unwrap();
+    let interp = SubInterpreter::new(py).unwrap();
+    let same_type = interp.with(py, |py| {
+        let first = Coord { x: 1, y: 2 }.to_object(py).getattr(py, "__class__").unwrap();
+        let second = Coord { x: 3, y: 4 }.to_object(py).getattr(py, "__class__").unwrap();
+        assert_eq!(first, second);
+        first.as_ptr() == main_type.as_ptr()
+    });
+    assert!(!same_type);
+}
+
+#[derive(Clone, Copy, ToPyObject, IntoPyObject)]
+enum Color {
+    Red,
+    #[pyo3(name = "green")]
+    Green,
+}
+
+#[derive(ToPyObject, IntoPyObject)]
+#[pyo3(int)]
+enum Level {
+    Low = 1,
+    High = 10,
+}
+
+#[test]
+fn derive_unit_enum() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = Color::Red.to_object(py);
+    py_assert(py, &ob, "ob == 'Red'");
+    let ob = Color::Green.into_object(py);
+    py_assert(py, &ob, "ob == 'green'");
+
+    let ob = Level::Low.to_object(py);
+    py_assert(py, &ob, "ob == 1");
+    let ob = Level::High.into_object(py);
+    py_assert(py, &ob, "ob == 10");
+}
+
+fn py_assert(py: Python, ob: &PyObject, assertion: &str) {
+    let d = PyDict::new(py);
+    d.set_item("ob", ob).unwrap();
+    py.run(&format!("assert {}", assertion), None, Some(d))
+        .map_err(|e| e.print(py)).expect(assertion);
+}
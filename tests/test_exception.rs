@@ -0,0 +1,107 @@
+#![feature(proc_macro, specialization)]
+
+extern crate pyo3;
+
+use std::fmt;
+use pyo3::*;
+
+
+/// Base class of all HTTP errors.
+#[py::exception(module = "http")]
+struct HttpError;
+
+/// Raised when the server responds with an error status.
+#[py::exception(module = "http", base = HttpError)]
+struct StatusError {
+    status_code: u16,
+    reason: String,
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.status_code, self.reason)
+    }
+}
+
+#[py::exception(module = "http", base = exc::ValueError)]
+struct InvalidUrl {
+    url: String,
+}
+
+fn check_status(status_code: u16) -> Result<(), StatusError> {
+    if status_code >= 400 {
+        Err(StatusError { status_code: status_code, reason: "Not Found".to_owned() })
+    } else {
+        Ok(())
+    }
+}
+
+fn fetch(status_code: u16) -> PyResult<()> {
+    check_status(status_code)?;
+    Ok(())
+}
+
+fn py_run(py: Python, m: &PyModule, code: &str) {
+    let d = PyDict::new(py);
+    d.set_item("http", m).unwrap();
+    py.run(code, None, Some(d)).map_err(|e| e.print(py)).expect(code);
+}
+
+#[test]
+fn exception_hierarchy() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let m = PyModule::new(py, "http").unwrap();
+    m.add_exception::<HttpError>().unwrap();
+    m.add_exception::<StatusError>().unwrap();
+    m.add_exception::<InvalidUrl>().unwrap();
+
+    py_run(py, m, "assert issubclass(http.StatusError, http.HttpError)");
+    py_run(py, m, "assert issubclass(http.HttpError, Exception)");
+    py_run(py, m, "assert issubclass(http.InvalidUrl, ValueError)");
+    py_run(py, m, "assert http.StatusError.__module__ == 'http'");
+    py_run(py, m, "assert http.StatusError.__name__ == 'StatusError'");
+    py_run(py, m, "assert http.HttpError.__doc__ == 'Base class of all HTTP errors.'");
+    py_run(py, m, "assert http.InvalidUrl.__doc__ is None");
+}
+
+#[test]
+fn exception_fields() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let m = PyModule::new(py, "http").unwrap();
+    m.add_exception::<HttpError>().unwrap();
+
+    let err = fetch(404).unwrap_err();
+    assert!(err.is_instance::<StatusError>(py));
+    assert!(err.is_instance::<HttpError>(py));
+    assert_eq!(err.to_string(), "http.StatusError: 404 Not Found");
+
+    let d = PyDict::new(py);
+    d.set_item("e", err.into_object(py)).unwrap();
+    d.set_item("http", m).unwrap();
+    py.run("assert isinstance(e, http.HttpError)", None, Some(d)).unwrap();
+    py.run("assert e.status_code == 404 and e.reason == 'Not Found'", None, Some(d)).unwrap();
+    py.run("assert str(e) == '404 Not Found'", None, Some(d)).unwrap();
+
+    let err: PyErr = InvalidUrl { url: "foo".to_owned() }.into();
+    assert!(err.is_instance::<exc::ValueError>(py));
+    let value = err.into_object(py);
+    assert_eq!(value.getattr(py, "url").unwrap().extract::<String>(py).unwrap(), "foo");
+    assert_eq!(value.getattr(py, "args").unwrap().extract::<Vec<String>>(py).unwrap().len(), 0);
+}
+
+#[test]
+fn add_exception_requires_exception_type() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let m = PyModule::new(py, "http").unwrap();
+    m.add_exception::<exc::KeyError>().unwrap();
+    py_run(py, m, "assert http.KeyError is KeyError");
+
+    let err = m.add_exception::<PyDict>().unwrap_err();
+    assert!(err.is_instance::<exc::TypeError>(py));
+}
@@ -0,0 +1,44 @@
+#![feature(proc_macro, specialization)]
+
+extern crate pyo3;
+
+use pyo3::*;
+
+// Python must not be initialized before `append_inittab` is called,
+// this binary contains a single test for that reason.
+
+#[py::modinit(inittab_mod)]
+fn init_mod(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("answer", 42)?;
+    Ok(())
+}
+
+#[cfg(Py_3)]
+fn init_func() -> ModuleInitFunc {
+    PyInit_inittab_mod
+}
+
+#[cfg(not(Py_3))]
+fn init_func() -> ModuleInitFunc {
+    initinittab_mod
+}
+
+#[test]
+fn append_inittab_before_initialization() {
+    append_inittab("inittab_mod", init_func()).unwrap();
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let sys = py.import("sys").unwrap();
+    let names: Vec<String> = sys.get("builtin_module_names").unwrap().extract().unwrap();
+    assert!(names.iter().any(|name| name == "inittab_mod"));
+
+    let d = PyDict::new(py);
+    py.run("import inittab_mod", None, Some(d)).map_err(|e| e.print(py)).unwrap();
+    let m: &PyModule = d.get_item("inittab_mod").unwrap().extract().unwrap();
+    assert_eq!(m.get("answer").unwrap().extract::<i32>().unwrap(), 42);
+
+    let err = append_inittab("nul\0mod", init_func()).unwrap_err();
+    assert!(err.is_instance::<exc::ValueError>(py));
+}
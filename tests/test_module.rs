@@ -0,0 +1,61 @@
+#![feature(proc_macro, specialization)]
+
+extern crate pyo3;
+
+use pyo3::*;
+
+
+#[cfg(Py_3)]
+struct Counter {
+    start: i32,
+}
+
+/// Module compiled into the test binary.
+#[py::modinit(builtin_mod)]
+fn init_mod(py: Python, m: &PyModule) -> PyResult<()> {
+
+    #[pyfn(m, "double")]
+    fn double(_py: Python, x: i32) -> PyResult<i32> {
+        Ok(x * 2)
+    }
+
+    #[cfg(Py_3)]
+    m.set_state(Counter { start: 10 })?;
+
+    m.add("answer", 42)?;
+    Ok(())
+}
+
+#[cfg(Py_3)]
+fn init_func() -> ModuleInitFunc {
+    PyInit_builtin_mod
+}
+
+#[cfg(not(Py_3))]
+fn init_func() -> ModuleInitFunc {
+    initbuiltin_mod
+}
+
+#[test]
+fn append_inittab_after_initialization() {
+    prepare_freethreaded_python();
+    append_inittab("builtin_mod", init_func()).unwrap();
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let d = PyDict::new(py);
+    py.run("import builtin_mod", None, Some(d)).map_err(|e| e.print(py)).unwrap();
+    let m: &PyModule = d.get_item("builtin_mod").unwrap().extract().unwrap();
+    assert_eq!(m.get("answer").unwrap().extract::<i32>().unwrap(), 42);
+    assert_eq!(m.call1("double", (21,)).unwrap().extract::<i32>().unwrap(), 42);
+    assert_eq!(m.get("__doc__").unwrap().extract::<String>().unwrap(),
+               "Module compiled into the test binary.");
+
+    #[cfg(Py_3)]
+    {
+        assert_eq!(m.state::<Counter>().unwrap().start, 10);
+        let err = m.set_state(Counter { start: 0 }).unwrap_err();
+        assert!(err.is_instance::<exc::RuntimeError>(py));
+    }
+}